@@ -48,6 +48,20 @@ pub struct ApiSpecInfo {
 
     /// Available implementations for this spec
     pub implementations: Vec<String>,
+
+    /// Custom verbs declared for this spec, in config order
+    #[facet(default)]
+    pub verbs: Vec<ApiVerb>,
+}
+
+/// A custom verb declared in the spec config
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct ApiVerb {
+    pub name: String,
+    /// Coverage the verb counts toward ("impl" or "verify"), if any
+    #[facet(default)]
+    pub counts_as: Option<String>,
 }
 
 /// Forward traceability: rules with their code references
@@ -57,21 +71,30 @@ pub struct ApiForwardData {
 }
 
 #[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
 pub struct ApiSpecForward {
     pub name: String,
     pub rules: Vec<ApiRule>,
+    /// Levels left out of the headline coverage (the impl's `exclude_levels`, normalized)
+    #[facet(default)]
+    pub exclude_levels: Vec<String>,
 }
 
 #[derive(Debug, Clone, Facet)]
 #[facet(rename_all = "camelCase")]
 pub struct ApiRule {
     pub id: String,
+    /// Requirement version from the `+N` suffix of the definition (1 if absent)
+    pub version: u32,
     /// Raw markdown source (without r[...] marker, but with `>` prefixes for blockquote rules)
     pub raw: String,
     /// Rendered HTML (for dashboard display)
     pub html: String,
+    /// Lifecycle status from metadata (e.g. draft, stable, deprecated, removed)
     #[facet(default)]
     pub status: Option<String>,
+    /// Requirement level (must, should, may), from metadata or inferred
+    /// from RFC 2119 keywords
     #[facet(default)]
     pub level: Option<String>,
     #[facet(default)]
@@ -89,6 +112,87 @@ pub struct ApiRule {
     pub impl_refs: Vec<ApiCodeRef>,
     pub verify_refs: Vec<ApiCodeRef>,
     pub depends_refs: Vec<ApiCodeRef>,
+    /// References written against an older version of this rule.
+    /// These don't count towards coverage.
+    pub stale_refs: Vec<ApiCodeRef>,
+    /// Outcome of the tests behind `verify_refs`, when test results are configured
+    #[facet(default)]
+    pub verify_status: Option<VerifyStatus>,
+    /// References using custom verbs, one entry per verb that has any.
+    /// Those that count toward coverage also appear in `impl_refs` or `verify_refs`.
+    #[facet(default)]
+    pub custom_refs: Vec<ApiVerbRefs>,
+}
+
+/// A rule's references using one custom verb
+#[derive(Debug, Clone, Facet)]
+pub struct ApiVerbRefs {
+    pub verb: String,
+    pub refs: Vec<ApiCodeRef>,
+}
+
+/// Outcome of the tests behind a rule's `verify` references
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Facet)]
+#[facet(rename_all = "kebab-case")]
+#[repr(u8)]
+pub enum VerifyStatus {
+    /// At least one verifying test passed and none failed
+    Passing,
+    /// At least one verifying test failed
+    Failing,
+    /// None of the verifying tests appear in the test results
+    NotRun,
+}
+
+impl ApiRule {
+    /// Whether some code still references an older version of this rule
+    pub fn is_stale(&self) -> bool {
+        !self.stale_refs.is_empty()
+    }
+
+    /// Whether this rule is still being written
+    pub fn is_draft(&self) -> bool {
+        self.status.as_deref() == Some(STATUS_DRAFT)
+    }
+
+    /// Whether this rule is on its way out; new implementations are discouraged
+    pub fn is_deprecated(&self) -> bool {
+        self.status.as_deref() == Some(STATUS_DEPRECATED)
+    }
+
+    /// Whether this rule no longer applies; references to it are errors
+    pub fn is_removed(&self) -> bool {
+        self.status.as_deref() == Some(STATUS_REMOVED)
+    }
+
+    /// References using the custom verb `verb`
+    pub fn custom_refs(&self, verb: &str) -> &[ApiCodeRef] {
+        self.custom_refs
+            .iter()
+            .find(|c| c.verb == verb)
+            .map_or(&[], |c| c.refs.as_slice())
+    }
+
+    /// Whether this rule counts toward coverage (draft, deprecated and removed
+    /// rules don't)
+    pub fn counts_toward_coverage(&self) -> bool {
+        status_counts_toward_coverage(self.status.as_deref())
+    }
+}
+
+/// Status of a requirement that is still being written
+pub const STATUS_DRAFT: &str = "draft";
+/// Status of a requirement on its way out
+pub const STATUS_DEPRECATED: &str = "deprecated";
+/// Status of a requirement that no longer applies
+pub const STATUS_REMOVED: &str = "removed";
+
+/// Whether requirements with `status` count toward coverage
+pub fn status_counts_toward_coverage(status: Option<&str>) -> bool {
+    !matches!(
+        status,
+        Some(STATUS_DRAFT | STATUS_DEPRECATED | STATUS_REMOVED)
+    )
 }
 
 #[derive(Debug, Clone, Facet)]
@@ -163,6 +267,8 @@ pub struct OutlineCoverage {
     pub impl_count: usize,
     /// Number of rules with verification refs
     pub verify_count: usize,
+    /// Number of rules referenced against an older version
+    pub stale_count: usize,
     /// Total number of rules
     pub total: usize,
 }
@@ -182,6 +288,27 @@ pub struct OutlineEntry {
     pub aggregated: OutlineCoverage,
 }
 
+/// An entry in the requirement ID tree: one dotted ID prefix with coverage info
+///
+/// The tree is flattened in depth-first order, like the spec outline; `depth`
+/// gives the nesting.
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct ApiRuleTreeEntry {
+    /// Full ID prefix (`channel.id`)
+    pub prefix: String,
+    /// Last segment of the prefix (`id`)
+    pub segment: String,
+    /// Number of segments in the prefix (1 for top-level entries)
+    pub depth: usize,
+    /// Whether a requirement has exactly this ID
+    pub is_rule: bool,
+    /// Direct coverage (the requirement with exactly this ID, if any)
+    pub coverage: OutlineCoverage,
+    /// Aggregated coverage (includes every requirement under this prefix)
+    pub aggregated: OutlineCoverage,
+}
+
 /// Spec content (may span multiple files)
 #[derive(Debug, Clone, Facet)]
 pub struct ApiSpecData {
@@ -192,6 +319,50 @@ pub struct ApiSpecData {
     pub outline: Vec<OutlineEntry>,
 }
 
+// ============================================================================
+// Coverage History
+// ============================================================================
+
+/// Coverage of one spec/impl pair at one commit
+#[derive(Debug, Clone, PartialEq, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct ApiHistoryEntry {
+    /// Full commit SHA
+    pub commit: String,
+    /// Commit time, in seconds since the Unix epoch
+    pub timestamp: i64,
+    pub spec: String,
+    pub impl_name: String,
+    pub total_rules: usize,
+    pub impl_covered: usize,
+    pub verify_covered: usize,
+    pub impl_percent: f64,
+    pub verify_percent: f64,
+}
+
+/// A drop in coverage from one history entry to the next
+#[derive(Debug, Clone, PartialEq, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct ApiRegression {
+    /// Commit at which coverage dropped
+    pub commit: String,
+    pub timestamp: i64,
+    /// Which coverage dropped: `impl` or `verify`
+    pub metric: String,
+    pub from_percent: f64,
+    pub to_percent: f64,
+}
+
+/// Coverage history of a spec/impl pair, oldest first
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct ApiHistory {
+    pub spec: String,
+    pub impl_name: String,
+    pub entries: Vec<ApiHistoryEntry>,
+    pub regressions: Vec<ApiRegression>,
+}
+
 // ============================================================================
 // Validation
 // ============================================================================
@@ -238,6 +409,23 @@ pub enum ValidationErrorCode {
     UnknownPrefix,
     /// Impl annotation in test file (only verify allowed)
     ImplInTestFile,
+    /// Reference written against an older version of the requirement
+    StaleReference,
+    /// A test behind a `verify` reference failed
+    FailingVerification,
+    /// Impl reference to a deprecated requirement (a warning)
+    DeprecatedRequirement,
+    /// Reference to a requirement whose status is `removed`
+    RemovedRequirement,
+    /// Wildcard reference that matches no requirement
+    UnmatchedWildcard,
+}
+
+impl ValidationErrorCode {
+    /// Whether errors with this code are warnings, which don't fail validation
+    pub fn is_warning(self) -> bool {
+        matches!(self, Self::DeprecatedRequirement)
+    }
 }
 
 /// Validation results for a spec/implementation pair
@@ -13,6 +13,12 @@ pub struct Config {
     /// Specifications to track coverage against
     #[facet(default)]
     pub specs: Vec<SpecConfig>,
+
+    /// Keys whose string values are scanned for requirement references in
+    /// JSON files, which have no comments (default: `$comment`)
+    /// r[impl config.json-keys]
+    #[facet(default)]
+    pub json_keys: Vec<String>,
 }
 
 /// Configuration for a single specification
@@ -42,6 +48,24 @@ pub struct SpecConfig {
     /// Each impl block specifies which source files to scan
     #[facet(default)]
     pub impls: Vec<Impl>,
+
+    /// Verbs accepted in references to this spec on top of the built-in ones
+    /// (e.g. `bench`, `fuzz`, `doc`)
+    /// r[impl config.spec.verbs]
+    #[facet(default)]
+    pub verbs: Vec<Verb>,
+}
+
+/// A custom verb, like `bench` in `r[bench rule.id]`
+#[derive(Debug, Clone, Facet)]
+pub struct Verb {
+    /// The verb as written in annotations
+    pub name: String,
+
+    /// Coverage the verb counts toward: `impl` or `verify`. Without it,
+    /// references are tracked but don't count toward coverage.
+    #[facet(default)]
+    pub counts_as: Option<String>,
 }
 
 /// Configuration for a single implementation of a spec
@@ -65,4 +89,49 @@ pub struct Impl {
     /// r[impl config.impl.test_include]
     #[facet(default)]
     pub test_include: Vec<String>,
+
+    /// Minimum coverage percentage (0-100) enforced by `tracey check`
+    /// r[impl config.impl.threshold]
+    #[facet(default)]
+    pub threshold: Option<f64>,
+
+    /// Minimum coverage percentage for the requirements at each level
+    /// r[impl config.impl.level_thresholds]
+    #[facet(default)]
+    pub level_thresholds: LevelThresholds,
+
+    /// Requirement levels left out of the headline coverage percentage that
+    /// `threshold` applies to (e.g. `(may)`)
+    /// r[impl config.impl.exclude_levels]
+    #[facet(default)]
+    pub exclude_levels: Vec<String>,
+
+    /// Test result files (libtest JSON or JUnit XML) used to tell whether
+    /// `verify` references pass
+    /// r[impl config.impl.test_results]
+    #[facet(default)]
+    pub test_results: Vec<String>,
+}
+
+/// Per-level coverage thresholds (0-100) for an implementation
+#[derive(Debug, Clone, Default, Facet)]
+pub struct LevelThresholds {
+    #[facet(default)]
+    pub must: Option<f64>,
+    #[facet(default)]
+    pub should: Option<f64>,
+    #[facet(default)]
+    pub may: Option<f64>,
+}
+
+impl LevelThresholds {
+    /// Threshold for `level` (must, should, may), if one is set
+    pub fn get(&self, level: &str) -> Option<f64> {
+        match level {
+            "must" => self.must,
+            "should" => self.should,
+            "may" => self.may,
+            _ => None,
+        }
+    }
 }
@@ -14,11 +14,13 @@
 //! - Code added without updating the spec
 //! - Potential dead code or technical debt
 
-use arborium::tree_sitter::{Node, Parser};
+use crate::lexer::{ExtractOptions, split_version};
+use arborium::tree_sitter::{Language, Node, Parser};
+use facet::Facet;
 use std::path::{Path, PathBuf};
 
 /// A semantic unit of code (function, struct, impl, etc.)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Facet)]
 pub struct CodeUnit {
     /// The kind of code unit (e.g., "function", "struct", "impl")
     pub kind: CodeUnitKind,
@@ -39,7 +41,8 @@ pub struct CodeUnit {
 }
 
 /// The kind of code unit
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Facet)]
+#[repr(u8)]
 pub enum CodeUnitKind {
     /// A function or method
     Function,
@@ -155,7 +158,31 @@ pub fn extract(path: &Path, source: &str) -> CodeUnits {
         "py" => extract_python(path, source),
         "ts" | "tsx" | "js" | "jsx" | "mts" | "cts" => extract_typescript(path, source),
         "php" => extract_php(path, source),
-        _ => CodeUnits::new(),
+        _ => match GRAMMARS.iter().find(|(exts, _, _)| exts.contains(&ext)) {
+            Some((_, language, kinds)) => extract_with(&language(), *kinds, path, source),
+            // OCaml, Elixir and R are only scanned for references: their
+            // definitions are let-bindings, macro calls and assignments, which
+            // can't be told apart from ordinary expressions by node kind alone.
+            // YAML, TOML, JSON, SQL and Markdown files have no code units.
+            None => CodeUnits::new(),
+        },
+    }
+}
+
+/// The line comment that introduces an annotation above a code unit, by file
+/// extension (doc comments where the language has a line form of them).
+///
+/// Covers the languages [`extract`] finds code units in; `None` otherwise.
+pub fn line_comment_prefix(path: &Path) -> Option<&'static str> {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    match ext {
+        "rs" | "swift" => Some("///"),
+        "go" | "java" | "ts" | "tsx" | "js" | "jsx" | "mts" | "cts" | "php" | "c" | "h" | "cpp"
+        | "hpp" | "cc" | "hh" | "cxx" | "hxx" | "kt" | "kts" | "scala" | "sc" | "zig" => Some("//"),
+        "py" | "rb" | "sh" | "bash" => Some("#"),
+        "lua" => Some("--"),
+        "hs" => Some("-- |"),
+        _ => None,
     }
 }
 
@@ -363,6 +390,149 @@ pub fn extract_php(path: &Path, source: &str) -> CodeUnits {
     units
 }
 
+fn c_node_kind(kind: &str) -> Option<CodeUnitKind> {
+    match kind {
+        "function_definition" => Some(CodeUnitKind::Function),
+        "struct_specifier" => Some(CodeUnitKind::Struct),
+        "union_specifier" => Some(CodeUnitKind::Struct),
+        "enum_specifier" => Some(CodeUnitKind::Enum),
+        "type_definition" => Some(CodeUnitKind::TypeAlias),
+        "preproc_function_def" => Some(CodeUnitKind::Macro),
+        _ => None,
+    }
+}
+
+fn cpp_node_kind(kind: &str) -> Option<CodeUnitKind> {
+    match kind {
+        "class_specifier" => Some(CodeUnitKind::Struct),
+        "namespace_definition" => Some(CodeUnitKind::Module),
+        "alias_declaration" => Some(CodeUnitKind::TypeAlias),
+        _ => c_node_kind(kind),
+    }
+}
+
+fn kotlin_node_kind(kind: &str) -> Option<CodeUnitKind> {
+    match kind {
+        "function_declaration" => Some(CodeUnitKind::Function),
+        "secondary_constructor" => Some(CodeUnitKind::Function),
+        "class_declaration" => Some(CodeUnitKind::Struct), // Also covers interfaces and enum classes
+        "object_declaration" => Some(CodeUnitKind::Struct),
+        "type_alias" => Some(CodeUnitKind::TypeAlias),
+        _ => None,
+    }
+}
+
+fn scala_node_kind(kind: &str) -> Option<CodeUnitKind> {
+    match kind {
+        "function_definition" => Some(CodeUnitKind::Function),
+        "function_declaration" => Some(CodeUnitKind::Function),
+        "class_definition" => Some(CodeUnitKind::Struct),
+        "object_definition" => Some(CodeUnitKind::Struct),
+        "trait_definition" => Some(CodeUnitKind::Trait),
+        "enum_definition" => Some(CodeUnitKind::Enum),
+        "type_definition" => Some(CodeUnitKind::TypeAlias),
+        _ => None,
+    }
+}
+
+fn ruby_node_kind(kind: &str) -> Option<CodeUnitKind> {
+    match kind {
+        "method" => Some(CodeUnitKind::Function),
+        "singleton_method" => Some(CodeUnitKind::Function),
+        "class" => Some(CodeUnitKind::Struct),
+        "module" => Some(CodeUnitKind::Module),
+        _ => None,
+    }
+}
+
+fn lua_node_kind(kind: &str) -> Option<CodeUnitKind> {
+    match kind {
+        "function_declaration" => Some(CodeUnitKind::Function),
+        _ => None,
+    }
+}
+
+fn bash_node_kind(kind: &str) -> Option<CodeUnitKind> {
+    match kind {
+        "function_definition" => Some(CodeUnitKind::Function),
+        _ => None,
+    }
+}
+
+fn zig_node_kind(kind: &str) -> Option<CodeUnitKind> {
+    match kind {
+        "function_declaration" => Some(CodeUnitKind::Function),
+        "test_declaration" => Some(CodeUnitKind::Function),
+        _ => None,
+    }
+}
+
+fn haskell_node_kind(kind: &str) -> Option<CodeUnitKind> {
+    match kind {
+        "function" => Some(CodeUnitKind::Function),
+        "data_type" => Some(CodeUnitKind::Enum), // ADTs are sum types
+        "newtype" => Some(CodeUnitKind::Struct),
+        "class" => Some(CodeUnitKind::Trait),
+        "instance" => Some(CodeUnitKind::Impl),
+        "type_synomym" => Some(CodeUnitKind::TypeAlias), // sic, the grammar's spelling
+        _ => None,
+    }
+}
+
+/// Node-kind mapping for a grammar.
+type NodeKinds = fn(&str) -> Option<CodeUnitKind>;
+
+/// Grammars whose code units are found by node kind alone: the file
+/// extensions they cover, the grammar, and its node-kind mapping.
+const GRAMMARS: &[(&[&str], fn() -> Language, NodeKinds)] = &[
+    (&["c", "h"], || arborium_c::language().into(), c_node_kind),
+    (
+        &["cpp", "hpp", "cc", "hh", "cxx", "hxx"],
+        || arborium_cpp::language().into(),
+        cpp_node_kind,
+    ),
+    (
+        &["kt", "kts"],
+        || arborium_kotlin::language().into(),
+        kotlin_node_kind,
+    ),
+    (
+        &["scala", "sc"],
+        || arborium_scala::language().into(),
+        scala_node_kind,
+    ),
+    (&["rb"], || arborium_ruby::language().into(), ruby_node_kind),
+    (&["lua"], || arborium_lua::language().into(), lua_node_kind),
+    (
+        &["sh", "bash"],
+        || arborium_bash::language().into(),
+        bash_node_kind,
+    ),
+    (&["zig"], || arborium_zig::language().into(), zig_node_kind),
+    (
+        &["hs"],
+        || arborium_haskell::language().into(),
+        haskell_node_kind,
+    ),
+];
+
+/// Extract code units from source code with the given grammar and node-kind mapping
+pub fn extract_with(language: &Language, kinds: NodeKinds, path: &Path, source: &str) -> CodeUnits {
+    let mut parser = Parser::new();
+    parser
+        .set_language(language)
+        .expect("Failed to load grammar");
+
+    let Some(tree) = parser.parse(source, None) else {
+        return CodeUnits::new();
+    };
+
+    let mut units = CodeUnits::new();
+    let root = tree.root_node();
+    extract_units_recursive(path, source, root, &mut units, kinds);
+    units
+}
+
 fn extract_units_recursive<F>(
     path: &Path,
     source: &str,
@@ -394,8 +564,20 @@ fn node_to_code_unit<F>(
 where
     F: Fn(&str) -> Option<CodeUnitKind>,
 {
+    // Keyword tokens share their kind with the node they introduce in some
+    // grammars (Ruby's `class`, Haskell's `instance`), so skip anonymous nodes.
+    if !node.is_named() {
+        return None;
+    }
+
     let kind = node_kind_mapper(node.kind())?;
 
+    // C-family specifiers also appear as bare type references
+    // (`struct foo *p`); only the ones with a body define anything.
+    if node.kind().ends_with("_specifier") && node.child_by_field_name("body").is_none() {
+        return None;
+    }
+
     // Get the name if available
     let name = get_node_name(source, node);
 
@@ -446,12 +628,17 @@ fn get_node_name(source: &str, node: Node) -> Option<String> {
     // Most tree-sitter grammars use "name" for the identifier field
     let name_node = node
         .child_by_field_name("name")
+        .or_else(|| declarator_name(node)) // For C/C++ functions and typedefs
         .or_else(|| node.child_by_field_name("type")) // For impl blocks
         .or_else(|| {
             // For some languages, the first identifier child is the name
             let mut cursor = node.walk();
-            node.children(&mut cursor)
-                .find(|c| c.kind() == "identifier" || c.kind() == "type_identifier")
+            node.children(&mut cursor).find(|c| {
+                matches!(
+                    c.kind(),
+                    "identifier" | "type_identifier" | "simple_identifier"
+                )
+            })
         });
 
     // Legacy Rust-specific handling (kept for compatibility)
@@ -472,6 +659,18 @@ fn get_node_name(source: &str, node: Node) -> Option<String> {
     name_node.map(|n| source[n.byte_range()].to_string())
 }
 
+/// Follow a C/C++ `declarator` chain down to the declared name.
+///
+/// `int *foo(void)` nests as pointer_declarator -> function_declarator ->
+/// identifier, so the name is the innermost declarator.
+fn declarator_name(node: Node) -> Option<Node> {
+    let mut current = node.child_by_field_name("declarator")?;
+    while let Some(inner) = current.child_by_field_name("declarator") {
+        current = inner;
+    }
+    Some(current)
+}
+
 /// Returns (requirement refs, earliest comment line if any)
 fn extract_req_refs_from_comments(source: &str, node: Node) -> (Vec<String>, Option<usize>) {
     let mut refs = Vec::new();
@@ -506,6 +705,7 @@ fn extract_req_refs_from_comments(source: &str, node: Node) -> (Vec<String>, Opt
                     | "attribute_item"
                     | "decorator"       // Python decorators
                     | "multiline_comment"
+                    | "haddock" // Haskell doc comments
             );
             if is_comment_like {
                 collect_comment_refs(source, sibling, &mut refs);
@@ -531,7 +731,7 @@ fn collect_inner_comment_refs(source: &str, node: Node, refs: &mut Vec<String>)
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
         match child.kind() {
-            "line_comment" | "block_comment" | "comment" | "multiline_comment" => {
+            "line_comment" | "block_comment" | "comment" | "multiline_comment" | "haddock" => {
                 extract_refs_from_comment_text(source, child, refs);
             }
             // Doc comments are in attributes -> line_outer_doc_comment -> doc_comment
@@ -558,7 +758,7 @@ fn collect_inner_comment_refs(source: &str, node: Node, refs: &mut Vec<String>)
 
 fn collect_comment_refs(source: &str, node: Node, refs: &mut Vec<String>) {
     match node.kind() {
-        "line_comment" | "block_comment" | "comment" | "multiline_comment" => {
+        "line_comment" | "block_comment" | "comment" | "multiline_comment" | "haddock" => {
             extract_refs_from_comment_text(source, node, refs);
         }
         "attribute_item" | "decorator" => {
@@ -608,8 +808,10 @@ pub struct FullReqRef {
     pub prefix: String,
     /// The verb (impl, verify, depends, related, define)
     pub verb: String,
-    /// The requirement ID
+    /// The requirement ID, without any version suffix
     pub req_id: String,
+    /// Requirement version the reference was written against (1 if unversioned)
+    pub version: u32,
     /// Line number (1-indexed)
     pub line: usize,
     /// Byte offset of the reference start
@@ -624,6 +826,13 @@ pub struct FullReqRef {
 /// r[impl ref.parser.languages]
 /// r[impl ref.parser.unified]
 pub fn extract_refs(path: &Path, source: &str) -> Vec<FullReqRef> {
+    extract_refs_with(path, source, &ExtractOptions::default())
+}
+
+/// Extract ALL requirement references from a file, with the given options
+///
+/// r[impl ref.parser.artifacts]
+pub fn extract_refs_with(path: &Path, source: &str, options: &ExtractOptions) -> Vec<FullReqRef> {
     let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
     let language = match ext {
@@ -634,6 +843,23 @@ pub fn extract_refs(path: &Path, source: &str) -> Vec<FullReqRef> {
         "py" => arborium_python::language(),
         "ts" | "tsx" | "js" | "jsx" | "mts" | "cts" => arborium_typescript::language(),
         "php" => arborium_php::language(),
+        "c" | "h" => arborium_c::language(),
+        "cpp" | "hpp" | "cc" | "hh" | "cxx" | "hxx" => arborium_cpp::language(),
+        "kt" | "kts" => arborium_kotlin::language(),
+        "scala" | "sc" => arborium_scala::language(),
+        "rb" => arborium_ruby::language(),
+        "lua" => arborium_lua::language(),
+        "sh" | "bash" => arborium_bash::language(),
+        "zig" => arborium_zig::language(),
+        "hs" => arborium_haskell::language(),
+        "ml" | "mli" => arborium_ocaml::language(),
+        "ex" | "exs" => arborium_elixir::language(),
+        "r" | "R" => arborium_r::language(),
+        "yaml" | "yml" => arborium_yaml::language(),
+        "toml" => arborium_toml::language(),
+        "json" => arborium_json::language(),
+        "sql" => arborium_sql::language(),
+        "md" => arborium_markdown::language(),
         _ => return Vec::new(),
     };
 
@@ -648,10 +874,114 @@ pub fn extract_refs(path: &Path, source: &str) -> Vec<FullReqRef> {
 
     let mut refs = Vec::new();
     let mut ignore_state = IgnoreState::default();
-    extract_refs_recursive(source, tree.root_node(), &mut refs, &mut ignore_state);
+    match ext {
+        // r[impl ref.json.keys]
+        "json" => extract_json_refs(source, tree.root_node(), options, &mut refs),
+        // r[impl ref.comments.html]
+        "md" => extract_markdown_refs(
+            source,
+            tree.root_node(),
+            options,
+            &mut refs,
+            &mut ignore_state,
+        ),
+        _ => extract_refs_recursive(
+            source,
+            tree.root_node(),
+            options,
+            &mut refs,
+            &mut ignore_state,
+        ),
+    }
     refs
 }
 
+/// Extract references from the string values of the configured keys in a
+/// JSON document.
+///
+/// A matching value is either a string or an array of strings; keys are
+/// matched at any depth.
+fn extract_json_refs(
+    source: &str,
+    node: Node,
+    options: &ExtractOptions,
+    refs: &mut Vec<FullReqRef>,
+) {
+    if node.kind() == "pair"
+        && let Some(key) = node.child_by_field_name("key")
+        && let Some(value) = node.child_by_field_name("value")
+        && options
+            .json_keys
+            .iter()
+            .any(|k| k == source[key.byte_range()].trim_matches('"'))
+    {
+        let strings: Vec<Node> = if value.kind() == "array" {
+            let mut cursor = value.walk();
+            value
+                .named_children(&mut cursor)
+                .filter(|n| n.kind() == "string")
+                .collect()
+        } else if value.kind() == "string" {
+            vec![value]
+        } else {
+            Vec::new()
+        };
+        for string in strings {
+            let text = &source[string.byte_range()];
+            let line = string.start_position().row + 1;
+            extract_full_refs_from_text(text, line, string.start_byte(), options, refs);
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        extract_json_refs(source, child, options, refs);
+    }
+}
+
+/// Extract references from the HTML comments (`<!-- ... -->`) in a Markdown
+/// document. Comments inside code blocks are left alone.
+fn extract_markdown_refs(
+    source: &str,
+    node: Node,
+    options: &ExtractOptions,
+    refs: &mut Vec<FullReqRef>,
+    ignore_state: &mut IgnoreState,
+) {
+    match node.kind() {
+        "fenced_code_block" | "indented_code_block" => return,
+        "html_block" | "inline" => {
+            let text = &source[node.byte_range()];
+            let mut searched = 0;
+            while let Some(start) = text[searched..].find("<!--").map(|i| searched + i) {
+                let end = text[start..]
+                    .find("-->")
+                    .map(|i| start + i + 3)
+                    .unwrap_or(text.len());
+                let comment = &text[start..end];
+                let line = node.start_position().row + 1 + text[..start].matches('\n').count();
+                if check_ignore_directives(comment, line, ignore_state) {
+                    extract_full_refs_from_text(
+                        comment,
+                        line,
+                        node.start_byte() + start,
+                        options,
+                        refs,
+                    );
+                }
+                searched = end;
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        extract_markdown_refs(source, child, options, refs, ignore_state);
+    }
+}
+
 /// State for tracking ignore directives across comment nodes.
 ///
 /// r[impl ref.ignore.prefix]
@@ -709,6 +1039,7 @@ fn check_ignore_directives(text: &str, line: usize, state: &mut IgnoreState) ->
 fn extract_refs_recursive(
     source: &str,
     node: Node,
+    options: &ExtractOptions,
     refs: &mut Vec<FullReqRef>,
     ignore_state: &mut IgnoreState,
 ) {
@@ -718,13 +1049,19 @@ fn extract_refs_recursive(
     //         line_outer_doc_comment (///), line_inner_doc_comment (//!),
     //         block_outer_doc_comment (/** */), block_inner_doc_comment (/*! */)
     // - Swift/Go/TypeScript: comment
-    // - Python: comment
+    // - Python/C/C++/Ruby/Lua/Bash/Zig/OCaml/Elixir/R: comment
+    // - Kotlin: line_comment, multiline_comment
+    // - Haskell: comment, haddock (-- |)
+    // - YAML/TOML: comment (#)
+    // - SQL: comment (--), marginalia (/* */)
     let is_comment = matches!(
         node.kind(),
         "line_comment"
             | "block_comment"
             | "comment"
             | "multiline_comment"
+            | "marginalia"
+            | "haddock"
             | "line_outer_doc_comment"
             | "line_inner_doc_comment"
             | "block_outer_doc_comment"
@@ -738,14 +1075,14 @@ fn extract_refs_recursive(
 
         // Check ignore directives and determine if we should extract refs
         if check_ignore_directives(text, line, ignore_state) {
-            extract_full_refs_from_text(text, line, base_offset, refs);
+            extract_full_refs_from_text(text, line, base_offset, options, refs);
         }
     }
 
     // Recurse into children
     let mut cursor = node.walk();
     for child in node.children(&mut cursor) {
-        extract_refs_recursive(source, child, refs, ignore_state);
+        extract_refs_recursive(source, child, options, refs, ignore_state);
     }
 }
 
@@ -754,6 +1091,7 @@ fn extract_full_refs_from_text(
     text: &str,
     line: usize,
     base_offset: usize,
+    options: &ExtractOptions,
     refs: &mut Vec<FullReqRef>,
 ) {
     let mut chars = text.char_indices().peekable();
@@ -784,11 +1122,18 @@ fn extract_full_refs_from_text(
             chars.next(); // consume '['
 
             // Parse: [verb req.id] or [req.id]
-            if let Some((verb, req_id, end_idx)) = try_parse_full_ref(&mut chars) {
+            if let Some((verb, req_id, end_idx)) = try_parse_full_ref(&mut chars, |word| {
+                options.resolve_verb(&prefix, word).is_some()
+            }) {
+                let (base_id, version) = split_version(&req_id);
+                if base_id.contains('+') || base_id.ends_with('.') {
+                    continue;
+                }
                 refs.push(FullReqRef {
                     prefix,
                     verb,
-                    req_id,
+                    req_id: base_id.to_string(),
+                    version,
                     line,
                     byte_offset: base_offset + prefix_start,
                     byte_length: end_idx - prefix_start + 1,
@@ -801,6 +1146,7 @@ fn extract_full_refs_from_text(
 // r[impl ref.syntax.req-id]
 fn try_parse_full_ref(
     chars: &mut std::iter::Peekable<impl Iterator<Item = (usize, char)>>,
+    is_verb: impl Fn(&str) -> bool,
 ) -> Option<(String, String, usize)> {
     // First char must be lowercase letter
     let first_char = chars.peek().map(|(_, c)| *c)?;
@@ -818,7 +1164,13 @@ fn try_parse_full_ref(
         end_idx = idx;
         if c == ']' || c == ' ' {
             break;
-        } else if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.' {
+        } else if c.is_ascii_lowercase()
+            || c.is_ascii_digit()
+            || c == '-'
+            || c == '.'
+            || c == '+'
+            || c == '*'
+        {
             first_word.push(c);
             chars.next();
         } else {
@@ -830,8 +1182,7 @@ fn try_parse_full_ref(
     match chars.peek().map(|(_, c)| *c) {
         Some(' ') => {
             // Might be [verb req.id]
-            let verbs = ["impl", "verify", "define", "depends", "related"];
-            if verbs.contains(&first_word.as_str()) {
+            if is_verb(&first_word) {
                 let verb = first_word;
                 chars.next(); // consume space
 
@@ -854,7 +1205,13 @@ fn try_parse_full_ref(
                     if c == ']' {
                         chars.next();
                         break;
-                    } else if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_' {
+                    } else if c.is_ascii_lowercase()
+                        || c.is_ascii_digit()
+                        || c == '-'
+                        || c == '_'
+                        || c == '+'
+                        || c == '*'
+                    {
                         req_id.push(c);
                         chars.next();
                     } else if c == '.' {
@@ -902,7 +1259,13 @@ fn try_parse_req_ref(
     while let Some(&(_, c)) = chars.peek() {
         if c == ']' || c == ' ' {
             break;
-        } else if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.' {
+        } else if c.is_ascii_lowercase()
+            || c.is_ascii_digit()
+            || c == '-'
+            || c == '.'
+            || c == '+'
+            || c == '*'
+        {
             first_word.push(c);
             chars.next();
         } else {
@@ -936,7 +1299,12 @@ fn try_parse_req_ref(
                     if c == ']' {
                         chars.next();
                         break;
-                    } else if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' {
+                    } else if c.is_ascii_lowercase()
+                        || c.is_ascii_digit()
+                        || c == '-'
+                        || c == '+'
+                        || c == '*'
+                    {
                         req_id.push(c);
                         chars.next();
                     } else if c == '.' {
@@ -948,8 +1316,9 @@ fn try_parse_req_ref(
                     }
                 }
 
-                if has_dot && !req_id.ends_with('.') && !req_id.is_empty() {
-                    return Some(req_id);
+                let (base_id, _) = split_version(&req_id);
+                if has_dot && !base_id.ends_with('.') && !base_id.contains('+') {
+                    return Some(base_id.to_string());
                 }
             }
             None
@@ -957,8 +1326,9 @@ fn try_parse_req_ref(
         Some(']') => {
             chars.next(); // consume ]
             // [req.id] format - must contain dot
-            if first_word.contains('.') && !first_word.ends_with('.') {
-                Some(first_word)
+            let (base_id, _) = split_version(&first_word);
+            if base_id.contains('.') && !base_id.ends_with('.') && !base_id.contains('+') {
+                Some(base_id.to_string())
             } else {
                 None
             }
@@ -971,6 +1341,92 @@ fn try_parse_req_ref(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_line_comment_prefix() {
+        assert_eq!(line_comment_prefix(Path::new("src/lib.rs")), Some("///"));
+        assert_eq!(line_comment_prefix(Path::new("main.go")), Some("//"));
+        assert_eq!(line_comment_prefix(Path::new("app.py")), Some("#"));
+        assert_eq!(line_comment_prefix(Path::new("init.lua")), Some("--"));
+        assert_eq!(line_comment_prefix(Path::new("settings.yaml")), None);
+    }
+
+    // r[verify ref.parser.artifacts]
+    #[test]
+    fn test_extract_refs_config_and_sql_comments() {
+        let yaml = "# r[impl config.retries]\nretries: 3 # r[verify config.limits]\nname: \"r[impl not.a.comment]\"\n";
+        let refs = extract_refs(Path::new("settings.yaml"), yaml);
+        let ids: Vec<_> = refs.iter().map(|r| r.req_id.as_str()).collect();
+        assert_eq!(ids, vec!["config.retries", "config.limits"]);
+        assert_eq!(refs[1].verb, "verify");
+        assert_eq!(refs[1].line, 2);
+
+        let toml = "# r[impl config.retries]\nretries = 3\n";
+        let refs = extract_refs(Path::new("settings.toml"), toml);
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].req_id, "config.retries");
+
+        let sql = "-- r[impl storage.users.table]\nCREATE TABLE users (id INT);\n/* r[impl storage.users.index] */\nCREATE INDEX users_id ON users (id);\n";
+        let refs = extract_refs(Path::new("001_users.sql"), sql);
+        let ids: Vec<_> = refs.iter().map(|r| r.req_id.as_str()).collect();
+        assert_eq!(ids, vec!["storage.users.table", "storage.users.index"]);
+        assert_eq!(refs[1].line, 3);
+    }
+
+    // r[verify ref.comments.html]
+    #[test]
+    fn test_extract_refs_markdown_html_comments() {
+        let md = r#"# Login test plan
+
+<!-- r[verify auth.login] -->
+1. Open the login page.
+
+Then check the session <!-- r[verify auth.session] --> cookie.
+
+```html
+<!-- r[verify auth.logout] -->
+```
+
+The spec says r[auth.token] but that's not in a comment.
+"#;
+        let refs = extract_refs(Path::new("plan.md"), md);
+        let ids: Vec<_> = refs.iter().map(|r| r.req_id.as_str()).collect();
+        assert_eq!(ids, vec!["auth.login", "auth.session"]);
+        assert_eq!(refs[0].line, 3);
+        assert_eq!(refs[1].line, 6);
+        assert_eq!(
+            &md[refs[1].byte_offset..refs[1].byte_offset + refs[1].byte_length],
+            "r[verify auth.session]"
+        );
+    }
+
+    // r[verify ref.json.keys]
+    #[test]
+    fn test_extract_refs_json_keys() {
+        let json = r#"{
+  "$comment": "r[impl config.retries]",
+  "retries": 3,
+  "limits": {
+    "$comment": ["r[impl config.limits]", "r[verify config.limits.max]"],
+    "max": "r[impl not.a.comment]"
+  }
+}"#;
+        let refs = extract_refs(Path::new("settings.json"), json);
+        let ids: Vec<_> = refs.iter().map(|r| r.req_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["config.retries", "config.limits", "config.limits.max"]
+        );
+        assert_eq!(refs[1].line, 5);
+
+        let options = ExtractOptions {
+            json_keys: vec!["max".to_string()],
+            ..Default::default()
+        };
+        let refs = extract_refs_with(Path::new("settings.json"), json, &options);
+        let ids: Vec<_> = refs.iter().map(|r| r.req_id.as_str()).collect();
+        assert_eq!(ids, vec!["not.a.comment"]);
+    }
+
     #[test]
     fn test_extract_refs_doc_comment() {
         let source = r#"
@@ -983,6 +1439,22 @@ fn next_channel_id() {}
         assert_eq!(refs[1].req_id, "channel.id.no-reuse");
     }
 
+    // r[verify ref.version.suffix]
+    #[test]
+    fn test_extract_refs_versioned() {
+        let source = r#"
+// r[impl foo.bar+2]
+fn do_thing() {}
+"#;
+        let refs = extract_refs(Path::new("test.rs"), source);
+        assert_eq!(refs.len(), 1, "Expected 1 ref, got {:?}", refs);
+        assert_eq!(refs[0].req_id, "foo.bar");
+        assert_eq!(refs[0].version, 2);
+
+        let units = extract_rust(Path::new("test.rs"), source);
+        assert_eq!(units.units[0].req_refs, vec!["foo.bar"]);
+    }
+
     #[test]
     fn test_extract_refs_line_comment() {
         let source = r#"
@@ -1521,6 +1993,520 @@ enum MyEnum {
         assert!(enum_unit.is_some(), "Should find MyEnum");
     }
 
+    // r[verify code-unit.definition]
+    // r[verify code-unit.boundary.include-comments]
+    #[test]
+    fn test_c_code_units() {
+        let source = r#"// r[impl c.feature]
+int do_something(int x) {
+    return x;
+}
+
+/* r[verify c.test] */
+struct Point {
+    int x;
+    int y;
+};
+
+typedef struct Point point_t;
+
+static char *make_name(void) {
+    return 0;
+}
+
+#define MAX(a, b) ((a) > (b) ? (a) : (b))
+"#;
+        let units = extract(Path::new("test.c"), source);
+
+        // Function
+        let func_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("do_something"));
+        assert!(func_unit.is_some(), "Should find do_something function");
+        let func_unit = func_unit.unwrap();
+        assert_eq!(func_unit.kind, CodeUnitKind::Function);
+        assert_eq!(func_unit.start_line, 1, "Should include comment");
+        assert_eq!(func_unit.req_refs, vec!["c.feature"]);
+
+        // Struct with a body
+        let struct_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("Point"));
+        assert!(struct_unit.is_some(), "Should find Point struct");
+        let struct_unit = struct_unit.unwrap();
+        assert_eq!(struct_unit.kind, CodeUnitKind::Struct);
+        assert_eq!(struct_unit.start_line, 6, "Should include comment");
+        assert_eq!(struct_unit.req_refs, vec!["c.test"]);
+
+        // The `struct Point` inside the typedef is a reference, not a definition
+        assert_eq!(
+            units
+                .units
+                .iter()
+                .filter(|u| u.kind == CodeUnitKind::Struct)
+                .count(),
+            1
+        );
+
+        // Typedef
+        let typedef_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("point_t"));
+        assert!(typedef_unit.is_some(), "Should find point_t typedef");
+        assert_eq!(typedef_unit.unwrap().kind, CodeUnitKind::TypeAlias);
+
+        // Function returning a pointer
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("make_name")),
+            "Should find make_name through the pointer declarator"
+        );
+
+        // Function-like macro
+        let macro_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("MAX"));
+        assert!(macro_unit.is_some(), "Should find MAX macro");
+        assert_eq!(macro_unit.unwrap().kind, CodeUnitKind::Macro);
+    }
+
+    // r[verify code-unit.definition]
+    // r[verify code-unit.boundary.include-comments]
+    #[test]
+    fn test_cpp_code_units() {
+        let source = r#"// r[impl cpp.feature]
+namespace engine {
+
+// r[impl cpp.widget]
+class Widget {
+public:
+    void draw() {}
+};
+
+using WidgetPtr = Widget*;
+
+}
+
+int Widget_count() {
+    return 0;
+}
+"#;
+        let units = extract(Path::new("test.cpp"), source);
+
+        // Namespace
+        let ns_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("engine"));
+        assert!(ns_unit.is_some(), "Should find engine namespace");
+        let ns_unit = ns_unit.unwrap();
+        assert_eq!(ns_unit.kind, CodeUnitKind::Module);
+        assert_eq!(ns_unit.req_refs, vec!["cpp.feature"]);
+
+        // Class
+        let class_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("Widget"));
+        assert!(class_unit.is_some(), "Should find Widget class");
+        let class_unit = class_unit.unwrap();
+        assert_eq!(class_unit.kind, CodeUnitKind::Struct);
+        assert_eq!(class_unit.start_line, 4, "Should include comment");
+        assert_eq!(class_unit.req_refs, vec!["cpp.widget"]);
+
+        // Inline method
+        let method_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("draw"));
+        assert!(method_unit.is_some(), "Should find draw method");
+        assert_eq!(method_unit.unwrap().kind, CodeUnitKind::Function);
+
+        // Alias
+        let alias_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("WidgetPtr"));
+        assert!(alias_unit.is_some(), "Should find WidgetPtr alias");
+        assert_eq!(alias_unit.unwrap().kind, CodeUnitKind::TypeAlias);
+
+        // Free function
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("Widget_count")),
+            "Should find Widget_count function"
+        );
+    }
+
+    // r[verify code-unit.definition]
+    // r[verify code-unit.boundary.include-comments]
+    #[test]
+    fn test_kotlin_code_units() {
+        let source = r#"// r[impl kotlin.feature]
+fun doSomething() {
+    println("hello")
+}
+
+/* r[verify kotlin.test] */
+class MyClass {
+    fun method() {}
+}
+
+object Registry {}
+
+typealias Handler = (String) -> Unit
+"#;
+        let units = extract(Path::new("test.kt"), source);
+
+        // Function
+        let func_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("doSomething"));
+        assert!(func_unit.is_some(), "Should find doSomething function");
+        let func_unit = func_unit.unwrap();
+        assert_eq!(func_unit.kind, CodeUnitKind::Function);
+        assert_eq!(func_unit.start_line, 1, "Should include comment");
+        assert_eq!(func_unit.req_refs, vec!["kotlin.feature"]);
+
+        // Class
+        let class_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("MyClass"));
+        assert!(class_unit.is_some(), "Should find MyClass");
+        let class_unit = class_unit.unwrap();
+        assert_eq!(class_unit.start_line, 6, "Should include comment");
+        assert_eq!(class_unit.req_refs, vec!["kotlin.test"]);
+
+        // Method inside class
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("method")),
+            "Should find method inside class"
+        );
+
+        // Object
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("Registry")),
+            "Should find Registry object"
+        );
+
+        // Type alias
+        let alias_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("Handler"));
+        assert!(alias_unit.is_some(), "Should find Handler alias");
+        assert_eq!(alias_unit.unwrap().kind, CodeUnitKind::TypeAlias);
+    }
+
+    // r[verify code-unit.definition]
+    // r[verify code-unit.boundary.include-comments]
+    #[test]
+    fn test_ruby_code_units() {
+        let source = r#"# r[impl ruby.feature]
+module Billing
+  # r[impl ruby.invoice]
+  class Invoice
+    def total
+      0
+    end
+
+    def self.build
+      new
+    end
+  end
+end
+"#;
+        let units = extract(Path::new("test.rb"), source);
+
+        // Module
+        let module_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("Billing"));
+        assert!(module_unit.is_some(), "Should find Billing module");
+        let module_unit = module_unit.unwrap();
+        assert_eq!(module_unit.kind, CodeUnitKind::Module);
+        assert_eq!(module_unit.req_refs, vec!["ruby.feature"]);
+
+        // Class
+        let class_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("Invoice"));
+        assert!(class_unit.is_some(), "Should find Invoice class");
+        let class_unit = class_unit.unwrap();
+        assert_eq!(class_unit.start_line, 3, "Should include comment");
+        assert_eq!(class_unit.req_refs, vec!["ruby.invoice"]);
+
+        // Instance and singleton methods
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("total")),
+            "Should find total method"
+        );
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("build")),
+            "Should find self.build method"
+        );
+
+        // Keyword tokens must not be mistaken for definitions
+        assert!(units.units.iter().all(|u| u.name.is_some()));
+    }
+
+    // r[verify code-unit.definition]
+    // r[verify code-unit.boundary.include-comments]
+    #[test]
+    fn test_lua_code_units() {
+        let source = r#"-- r[impl lua.feature]
+function do_something()
+  print("hello")
+end
+
+local function helper()
+end
+"#;
+        let units = extract(Path::new("test.lua"), source);
+
+        let func_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("do_something"));
+        assert!(func_unit.is_some(), "Should find do_something function");
+        let func_unit = func_unit.unwrap();
+        assert_eq!(func_unit.kind, CodeUnitKind::Function);
+        assert_eq!(func_unit.start_line, 1, "Should include comment");
+        assert_eq!(func_unit.req_refs, vec!["lua.feature"]);
+
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("helper")),
+            "Should find local function"
+        );
+    }
+
+    // r[verify code-unit.definition]
+    // r[verify code-unit.boundary.include-comments]
+    #[test]
+    fn test_scala_code_units() {
+        let source = r#"// r[impl scala.feature]
+def doSomething(): Unit = {
+  println("hello")
+}
+
+/* r[verify scala.test] */
+class MyClass {
+  def method(): Int = 1
+}
+
+trait Shape
+
+object Registry
+"#;
+        let units = extract(Path::new("test.scala"), source);
+
+        // Function
+        let func_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("doSomething"));
+        assert!(func_unit.is_some(), "Should find doSomething function");
+        let func_unit = func_unit.unwrap();
+        assert_eq!(func_unit.kind, CodeUnitKind::Function);
+        assert_eq!(func_unit.start_line, 1, "Should include comment");
+        assert_eq!(func_unit.req_refs, vec!["scala.feature"]);
+
+        // Class
+        let class_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("MyClass"));
+        assert!(class_unit.is_some(), "Should find MyClass");
+        let class_unit = class_unit.unwrap();
+        assert_eq!(class_unit.kind, CodeUnitKind::Struct);
+        assert_eq!(class_unit.start_line, 6, "Should include comment");
+        assert_eq!(class_unit.req_refs, vec!["scala.test"]);
+
+        // Method inside class
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("method")),
+            "Should find method inside class"
+        );
+
+        // Trait and object
+        let trait_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("Shape"));
+        assert!(trait_unit.is_some(), "Should find Shape trait");
+        assert_eq!(trait_unit.unwrap().kind, CodeUnitKind::Trait);
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("Registry")),
+            "Should find Registry object"
+        );
+    }
+
+    // r[verify code-unit.definition]
+    // r[verify code-unit.boundary.include-comments]
+    #[test]
+    fn test_bash_code_units() {
+        let source = r#"# r[impl bash.feature]
+do_something() {
+  echo "hello"
+}
+
+function helper {
+  :
+}
+"#;
+        let units = extract(Path::new("test.sh"), source);
+
+        let func_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("do_something"));
+        assert!(func_unit.is_some(), "Should find do_something function");
+        let func_unit = func_unit.unwrap();
+        assert_eq!(func_unit.kind, CodeUnitKind::Function);
+        assert_eq!(func_unit.start_line, 1, "Should include comment");
+        assert_eq!(func_unit.req_refs, vec!["bash.feature"]);
+
+        assert!(
+            units
+                .units
+                .iter()
+                .any(|u| u.name.as_deref() == Some("helper")),
+            "Should find function declared with the `function` keyword"
+        );
+    }
+
+    // r[verify code-unit.definition]
+    // r[verify code-unit.boundary.include-comments]
+    #[test]
+    fn test_zig_code_units() {
+        let source = r#"// r[impl zig.feature]
+pub fn doSomething() void {
+}
+
+// r[verify zig.test]
+test "does something" {
+    doSomething();
+}
+"#;
+        let units = extract(Path::new("test.zig"), source);
+
+        // Function
+        let func_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("doSomething"));
+        assert!(func_unit.is_some(), "Should find doSomething function");
+        let func_unit = func_unit.unwrap();
+        assert_eq!(func_unit.kind, CodeUnitKind::Function);
+        assert_eq!(func_unit.start_line, 1, "Should include comment");
+        assert_eq!(func_unit.req_refs, vec!["zig.feature"]);
+
+        // Test block
+        let test_unit = units.units.iter().find(|u| u.req_refs == vec!["zig.test"]);
+        assert!(test_unit.is_some(), "Should find the test declaration");
+        let test_unit = test_unit.unwrap();
+        assert_eq!(test_unit.kind, CodeUnitKind::Function);
+        assert_eq!(test_unit.start_line, 5, "Should include comment");
+    }
+
+    // r[verify code-unit.definition]
+    // r[verify code-unit.boundary.include-comments]
+    #[test]
+    fn test_haskell_code_units() {
+        let source = r#"-- r[impl haskell.feature]
+doSomething x = x + 1
+
+-- | r[verify haskell.test]
+data Shape = Circle | Square
+
+class Describe a where
+  describe :: a -> String
+"#;
+        let units = extract(Path::new("test.hs"), source);
+
+        // Function
+        let func_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("doSomething"));
+        assert!(func_unit.is_some(), "Should find doSomething function");
+        let func_unit = func_unit.unwrap();
+        assert_eq!(func_unit.kind, CodeUnitKind::Function);
+        assert_eq!(func_unit.start_line, 1, "Should include comment");
+        assert_eq!(func_unit.req_refs, vec!["haskell.feature"]);
+
+        // Data type, with a haddock comment
+        let data_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("Shape"));
+        assert!(data_unit.is_some(), "Should find Shape data type");
+        let data_unit = data_unit.unwrap();
+        assert_eq!(data_unit.kind, CodeUnitKind::Enum);
+        assert_eq!(data_unit.start_line, 4, "Should include haddock comment");
+        assert_eq!(data_unit.req_refs, vec!["haskell.test"]);
+
+        // Type class
+        let class_unit = units
+            .units
+            .iter()
+            .find(|u| u.name.as_deref() == Some("Describe"));
+        assert!(class_unit.is_some(), "Should find Describe class");
+        assert_eq!(class_unit.unwrap().kind, CodeUnitKind::Trait);
+    }
+
+    // r[verify ref.parser.languages]
+    #[test]
+    fn test_extract_refs_reference_only_languages() {
+        let cases = [
+            ("test.ml", "(* r[impl ocaml.feature] *)\nlet x = 1\n"),
+            ("test.ex", "# r[impl elixir.feature]\ndefmodule A do\nend\n"),
+            ("test.R", "# r[impl r.feature]\nf <- function() 1\n"),
+            ("test.hs", "-- r[impl haskell.feature]\nmain = pure ()\n"),
+            ("test.sh", "# r[impl bash.feature]\nmain() { :; }\n"),
+        ];
+
+        for (file, source) in cases {
+            let refs = extract_refs(Path::new(file), source);
+            assert_eq!(refs.len(), 1, "Expected 1 ref in {}, got {:?}", file, refs);
+            assert_eq!(refs[0].verb, "impl");
+            assert!(refs[0].req_id.ends_with(".feature"));
+        }
+    }
+
     // =========================================================================
     // Ignore directive tests
     // =========================================================================
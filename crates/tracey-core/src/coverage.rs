@@ -1,6 +1,7 @@
 //! Coverage analysis and reporting
 
 use crate::lexer::{RefVerb, ReqReference, Reqs};
+use crate::wildcard::{is_wildcard, wildcard_matches};
 use facet::Facet;
 use std::collections::{HashMap, HashSet};
 
@@ -48,21 +49,45 @@ impl CoverageReport {
         let mut references_by_verb: HashMap<RefVerb, HashMap<String, Vec<ReqReference>>> =
             HashMap::new();
 
+        let mut record = |reference: ReqReference| {
+            // r[impl ref.verb.custom]
+            if !reference.is_tracked_only() {
+                covered_rules.insert(reference.req_id.clone());
+            }
+            references_by_rule
+                .entry(reference.req_id.clone())
+                .or_default()
+                .push(reference.clone());
+
+            // Also group by verb
+            references_by_verb
+                .entry(reference.verb)
+                .or_default()
+                .entry(reference.req_id.clone())
+                .or_default()
+                .push(reference);
+        };
+
         for reference in &reqs.references {
             if known_rule_ids.contains(&reference.req_id) {
-                covered_rules.insert(reference.req_id.clone());
-                references_by_rule
-                    .entry(reference.req_id.clone())
-                    .or_default()
-                    .push(reference.clone());
-
-                // Also group by verb
-                references_by_verb
-                    .entry(reference.verb)
-                    .or_default()
-                    .entry(reference.req_id.clone())
-                    .or_default()
-                    .push(reference.clone());
+                record(reference.clone());
+            } else if is_wildcard(&reference.req_id) {
+                // r[impl ref.wildcard.expand]
+                let mut matched: Vec<&String> = known_rule_ids
+                    .iter()
+                    .filter(|id| wildcard_matches(&reference.req_id, id))
+                    .collect();
+                if matched.is_empty() {
+                    invalid_references.push(reference.clone());
+                    continue;
+                }
+                matched.sort();
+                for id in matched {
+                    record(ReqReference {
+                        req_id: id.clone(),
+                        ..reference.clone()
+                    });
+                }
             } else {
                 invalid_references.push(reference.clone());
             }
@@ -72,6 +72,26 @@ impl RefVerb {
     }
 }
 
+/// Split a requirement ID like `auth.token+2` into its base ID and version.
+///
+/// IDs without a `+N` suffix are version 1. A malformed suffix is left in
+/// place, so the returned base ID fails the usual ID validation.
+///
+/// r[impl markdown.version.suffix]
+pub fn split_version(id: &str) -> (&str, u32) {
+    match id.rsplit_once('+') {
+        Some((base, suffix))
+            if !suffix.is_empty() && suffix.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            match suffix.parse::<u32>() {
+                Ok(version) if version >= 1 => (base, version),
+                _ => (id, 1),
+            }
+        }
+        _ => (id, 1),
+    }
+}
+
 impl std::fmt::Display for RefVerb {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.as_str())
@@ -85,10 +105,18 @@ impl std::fmt::Display for RefVerb {
 pub struct ReqReference {
     /// The prefix identifying which spec this reference belongs to
     pub prefix: String,
-    /// The relationship type (impl, verify, depends, etc.)
+    /// The relationship type (impl, verify, depends, etc.). For a custom
+    /// verb, the built-in verb whose coverage it counts toward (`Related` if none).
     pub verb: RefVerb,
-    /// The requirement ID (e.g., "channel.id.allocation")
+    /// Name of the custom verb declared in config, if this reference uses one
+    #[facet(default)]
+    pub custom_verb: Option<String>,
+    /// The requirement ID (e.g., "channel.id.allocation"), without any version suffix
     pub req_id: String,
+    /// Requirement version the reference was written against (`+N` suffix, 1 if absent)
+    ///
+    /// r[impl ref.version.suffix]
+    pub version: u32,
     /// File where the reference was found
     pub file: PathBuf,
     /// Line number (1-indexed)
@@ -97,6 +125,19 @@ pub struct ReqReference {
     pub span: SourceSpan,
 }
 
+impl ReqReference {
+    /// The verb as written in the reference, custom or built-in
+    pub fn verb_name(&self) -> &str {
+        self.custom_verb.as_deref().unwrap_or(self.verb.as_str())
+    }
+
+    /// Whether this reference uses a custom verb that doesn't count toward
+    /// coverage, so it is only tracked
+    pub fn is_tracked_only(&self) -> bool {
+        self.custom_verb.is_some() && self.verb == RefVerb::Related
+    }
+}
+
 /// Warning during parsing
 #[derive(Debug, Clone, Facet)]
 pub struct ParseWarning {
@@ -122,6 +163,57 @@ pub enum WarningKind {
     MalformedReference,
 }
 
+/// Key read for references in JSON files unless configured otherwise
+pub const DEFAULT_JSON_KEY: &str = "$comment";
+
+/// A verb declared in config on top of the built-in ones, like `bench` in
+/// `r[bench rule.id]`
+///
+/// r[impl ref.verb.custom]
+#[derive(Debug, Clone, PartialEq, Eq, Facet)]
+pub struct CustomVerb {
+    /// Prefix of the spec that declares the verb
+    pub prefix: String,
+    /// The verb as written in annotations
+    pub name: String,
+    /// Built-in verb whose coverage this one counts toward: `Impl`, `Verify`,
+    /// or `Related` for a verb that is tracked but doesn't count
+    pub counts_as: RefVerb,
+}
+
+/// Options for reference extraction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractOptions {
+    /// JSON has no comments, so references are read from the string values of
+    /// these keys instead (at any depth; a value may also be an array of strings)
+    pub json_keys: Vec<String>,
+    /// Verbs accepted in addition to the built-in ones
+    pub custom_verbs: Vec<CustomVerb>,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        Self {
+            json_keys: vec![DEFAULT_JSON_KEY.to_string()],
+            custom_verbs: Vec::new(),
+        }
+    }
+}
+
+impl ExtractOptions {
+    /// Resolve `word` in a reference with `prefix` to a verb: the built-in verb
+    /// it names or counts as, plus the custom verb's name if it is one
+    pub fn resolve_verb(&self, prefix: &str, word: &str) -> Option<(RefVerb, Option<String>)> {
+        if let Some(verb) = RefVerb::parse(word) {
+            return Some((verb, None));
+        }
+        self.custom_verbs
+            .iter()
+            .find(|v| v.prefix == prefix && v.name == word)
+            .map(|v| (v.counts_as, Some(v.name.clone())))
+    }
+}
+
 /// Collection of requirement references extracted from source files
 #[derive(Debug, Clone, Default, Facet)]
 pub struct Reqs {
@@ -154,8 +246,13 @@ impl Reqs {
 
     /// Extract requirements from raw content (no I/O)
     pub fn extract_from_content(path: &Path, content: &str) -> Self {
+        Self::extract_from_content_with(path, content, &ExtractOptions::default())
+    }
+
+    /// Extract requirements from raw content with the given options (no I/O)
+    pub fn extract_from_content_with(path: &Path, content: &str, options: &ExtractOptions) -> Self {
         let mut reqs = Reqs::new();
-        extract_from_content(path, content, &mut reqs);
+        extract_from_content(path, content, options, &mut reqs);
         reqs
     }
 
@@ -170,26 +267,29 @@ impl Reqs {
 ///
 /// When the "reverse" feature is enabled, this uses tree-sitter for proper
 /// comment parsing. Otherwise, falls back to text-based scanning.
-pub(crate) fn extract_from_content(path: &Path, content: &str, reqs: &mut Reqs) {
+pub(crate) fn extract_from_content(
+    path: &Path,
+    content: &str,
+    options: &ExtractOptions,
+    reqs: &mut Reqs,
+) {
     #[cfg(feature = "reverse")]
     {
         // Use tree-sitter based extraction
         // r[impl ref.comments.line]
         // r[impl ref.comments.doc]
         // r[impl ref.comments.block]
-        for full_ref in crate::code_units::extract_refs(path, content) {
-            let verb = match full_ref.verb.as_str() {
-                "define" => RefVerb::Define,
-                "impl" => RefVerb::Impl,
-                "verify" => RefVerb::Verify,
-                "depends" => RefVerb::Depends,
-                "related" => RefVerb::Related,
-                _ => continue,
+        for full_ref in crate::code_units::extract_refs_with(path, content, options) {
+            let Some((verb, custom_verb)) = options.resolve_verb(&full_ref.prefix, &full_ref.verb)
+            else {
+                continue;
             };
             reqs.references.push(ReqReference {
                 prefix: full_ref.prefix,
                 verb,
+                custom_verb,
                 req_id: full_ref.req_id,
+                version: full_ref.version,
                 file: path.to_path_buf(),
                 line: full_ref.line,
                 span: SourceSpan::new(full_ref.byte_offset, full_ref.byte_length),
@@ -199,8 +299,8 @@ pub(crate) fn extract_from_content(path: &Path, content: &str, reqs: &mut Reqs)
 
     #[cfg(not(feature = "reverse"))]
     {
-        // Fallback: text-based scanning
-        extract_from_content_text_based(path, content, reqs);
+        // Fallback: text-based scanning (comments only, so JSON keys don't apply)
+        extract_from_content_text_based(path, content, options, reqs);
     }
 }
 
@@ -267,7 +367,12 @@ fn check_ignore_directives(text: &str, line: usize, state: &mut IgnoreState) ->
 }
 
 #[cfg(not(feature = "reverse"))]
-fn extract_from_content_text_based(path: &Path, content: &str, reqs: &mut Reqs) {
+fn extract_from_content_text_based(
+    path: &Path,
+    content: &str,
+    options: &ExtractOptions,
+    reqs: &mut Reqs,
+) {
     // Track line starts for computing line numbers from byte offsets
     let line_starts: Vec<usize> = std::iter::once(0)
         .chain(content.match_indices('\n').map(|(i, _)| i + 1))
@@ -294,7 +399,7 @@ fn extract_from_content_text_based(path: &Path, content: &str, reqs: &mut Reqs)
 
             // Check ignore directives before extracting
             if check_ignore_directives(comment, line_num, &mut ignore_state) {
-                extract_references_from_text(path, comment, comment_start, line_num, reqs);
+                extract_references_from_text(path, comment, comment_start, line_num, options, reqs);
             }
         }
     }
@@ -317,6 +422,7 @@ fn extract_from_content_text_based(path: &Path, content: &str, reqs: &mut Reqs)
                         block_content,
                         block_start,
                         block_line,
+                        options,
                         reqs,
                     );
                 }
@@ -342,6 +448,7 @@ fn extract_references_from_text(
     text: &str,
     text_offset: usize,
     base_line: usize,
+    options: &ExtractOptions,
     reqs: &mut Reqs,
 ) {
     let mut chars = text.char_indices().peekable();
@@ -401,7 +508,13 @@ fn extract_references_from_text(
                 while let Some(&(_, c)) = chars.peek() {
                     if c == ']' || c == ' ' {
                         break;
-                    } else if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '.' {
+                    } else if c.is_ascii_lowercase()
+                        || c.is_ascii_digit()
+                        || c == '-'
+                        || c == '.'
+                        || c == '+'
+                        || c == '*'
+                    {
                         first_word.push(c);
                         chars.next();
                     } else {
@@ -420,7 +533,7 @@ fn extract_references_from_text(
                 if next_char == ' ' {
                     // Space after first word - might be r[verb rule.id]
                     // r[impl ref.syntax.verb]
-                    if let Some(verb) = RefVerb::parse(&first_word) {
+                    if let Some((verb, custom_verb)) = options.resolve_verb(&prefix, &first_word) {
                         chars.next(); // consume space
 
                         // Now read the rule ID
@@ -444,7 +557,12 @@ fn extract_references_from_text(
                             if c == ']' {
                                 chars.next();
                                 break;
-                            } else if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' {
+                            } else if c.is_ascii_lowercase()
+                                || c.is_ascii_digit()
+                                || c == '-'
+                                || c == '+'
+                                || c == '*'
+                            {
                                 req_id.push(c);
                                 chars.next();
                             } else if c == '.' {
@@ -457,12 +575,19 @@ fn extract_references_from_text(
                         }
 
                         // Validate rule ID
-                        if found_dot && !req_id.ends_with('.') && !req_id.is_empty() {
+                        let (base_id, version) = split_version(&req_id);
+                        if found_dot
+                            && !base_id.ends_with('.')
+                            && !base_id.is_empty()
+                            && !base_id.contains('+')
+                        {
                             let span = SourceSpan::new(bracket_start, final_idx - prefix_start + 1);
                             reqs.references.push(ReqReference {
                                 prefix: prefix.clone(),
                                 verb,
-                                req_id,
+                                custom_verb,
+                                req_id: base_id.to_string(),
+                                version,
                                 file: path.to_path_buf(),
                                 line: base_line,
                                 span,
@@ -470,7 +595,8 @@ fn extract_references_from_text(
                         }
                     } else {
                         // Not a known verb - just ignore it. We only match rule
-                        // references with known verbs: impl, verify, define, depends, related
+                        // references with known verbs: impl, verify, define, depends,
+                        // related, and the custom verbs declared in config.
                         // This avoids false positives on things like [payload bytes]
                     }
                 } else if next_char == ']' {
@@ -479,12 +605,15 @@ fn extract_references_from_text(
                     chars.next(); // consume ]
 
                     // Validate: must contain dot, not end with dot
-                    if first_word.contains('.') && !first_word.ends_with('.') {
+                    let (base_id, version) = split_version(&first_word);
+                    if base_id.contains('.') && !base_id.ends_with('.') && !base_id.contains('+') {
                         let span = SourceSpan::new(bracket_start, end_idx - prefix_start + 1);
                         reqs.references.push(ReqReference {
                             prefix: prefix.clone(),
                             verb: RefVerb::Impl, // default to impl
-                            req_id: first_word,
+                            custom_verb: None,
+                            req_id: base_id.to_string(),
+                            version,
                             file: path.to_path_buf(),
                             line: base_line,
                             span,
@@ -608,6 +737,47 @@ mod tests {
         assert_eq!(reqs.warnings.len(), 0); // No warnings for unknown verbs
     }
 
+    // r[verify ref.verb.custom]
+    #[test]
+    fn test_custom_verbs() {
+        let content = r#"
+            // r[bench rule.fast]
+            // r[fuzz rule.parse]
+            // o[bench rule.other]
+            // r[frobnicate rule.id]
+            fn foo() {}
+        "#;
+
+        let options = ExtractOptions {
+            custom_verbs: vec![
+                CustomVerb {
+                    prefix: "r".to_string(),
+                    name: "bench".to_string(),
+                    counts_as: RefVerb::Related,
+                },
+                CustomVerb {
+                    prefix: "r".to_string(),
+                    name: "fuzz".to_string(),
+                    counts_as: RefVerb::Verify,
+                },
+            ],
+            ..Default::default()
+        };
+        let reqs = Reqs::extract_from_content_with(Path::new("test.rs"), content, &options);
+        // Custom verbs only apply to the spec that declares them
+        assert_eq!(reqs.len(), 2);
+        assert_eq!(reqs.references[0].req_id, "rule.fast");
+        assert_eq!(reqs.references[0].verb, RefVerb::Related);
+        assert_eq!(reqs.references[0].verb_name(), "bench");
+        assert_eq!(reqs.references[1].req_id, "rule.parse");
+        assert_eq!(reqs.references[1].verb, RefVerb::Verify);
+        assert_eq!(reqs.references[1].custom_verb.as_deref(), Some("fuzz"));
+
+        // Without the config they're unknown verbs, and ignored
+        let reqs = Reqs::extract_from_content(Path::new("test.rs"), content);
+        assert_eq!(reqs.len(), 0);
+    }
+
     #[test]
     fn test_verb_display() {
         assert_eq!(RefVerb::Impl.to_string(), "impl");
@@ -626,6 +796,34 @@ mod tests {
         assert_eq!(reqs.references[0].span.offset, 3); // after "// ", points to 'r'
     }
 
+    // r[verify ref.version.suffix]
+    #[test]
+    fn test_versioned_reference() {
+        let content = r#"
+            // r[impl auth.token+2]
+            // r[verify auth.token]
+            // r[auth.login+3]
+        "#;
+        let reqs = Reqs::extract_from_content(Path::new("test.rs"), content);
+        assert_eq!(reqs.len(), 3);
+        assert_eq!(reqs.references[0].req_id, "auth.token");
+        assert_eq!(reqs.references[0].version, 2);
+        assert_eq!(reqs.references[1].req_id, "auth.token");
+        assert_eq!(reqs.references[1].version, 1);
+        assert_eq!(reqs.references[2].req_id, "auth.login");
+        assert_eq!(reqs.references[2].version, 3);
+    }
+
+    // r[verify markdown.version.suffix]
+    #[test]
+    fn test_split_version() {
+        assert_eq!(split_version("auth.token"), ("auth.token", 1));
+        assert_eq!(split_version("auth.token+2"), ("auth.token", 2));
+        assert_eq!(split_version("auth.token+0"), ("auth.token+0", 1));
+        assert_eq!(split_version("auth.token++2"), ("auth.token+", 2));
+        assert_eq!(split_version("auth.token+x"), ("auth.token+x", 1));
+    }
+
     #[test]
     fn test_multi_char_prefix() {
         let content = r#"
@@ -657,4 +855,53 @@ mod tests {
         assert_eq!(reqs.references[0].verb, RefVerb::Impl);
         assert_eq!(reqs.references[0].req_id, "dashboard.header.search");
     }
+
+    // r[verify ref.wildcard.syntax]
+    #[test]
+    fn test_wildcard_reference() {
+        let content = r#"
+            // r[verify channel.id.*]
+            // r[channel.**]
+            fn test_channel_ids() {}
+        "#;
+
+        let reqs = Reqs::extract_from_content(Path::new("test.rs"), content);
+        assert_eq!(reqs.len(), 2);
+        assert_eq!(reqs.references[0].verb, RefVerb::Verify);
+        assert_eq!(reqs.references[0].req_id, "channel.id.*");
+        assert_eq!(reqs.references[1].verb, RefVerb::Impl);
+        assert_eq!(reqs.references[1].req_id, "channel.**");
+    }
+
+    // r[verify ref.wildcard.expand]
+    #[test]
+    fn test_wildcard_reference_expands_in_coverage() {
+        let content = r#"
+            // r[verify channel.id.*]
+            // r[verify session.*]
+            fn test_channel_ids() {}
+        "#;
+
+        let reqs = Reqs::extract_from_content(Path::new("test.rs"), content);
+        let known: std::collections::HashSet<String> = [
+            "channel.id.allocation",
+            "channel.id.parity",
+            "channel.framing",
+        ]
+        .into_iter()
+        .map(String::from)
+        .collect();
+        let report = crate::CoverageReport::compute("test", &known, &reqs);
+
+        assert_eq!(report.covered_rules.len(), 2);
+        assert!(report.covered_rules.contains("channel.id.allocation"));
+        assert!(report.covered_rules.contains("channel.id.parity"));
+        assert_eq!(
+            report.references_by_verb[&RefVerb::Verify]["channel.id.parity"][0].req_id,
+            "channel.id.parity"
+        );
+        // A wildcard matching nothing is an invalid reference
+        assert_eq!(report.invalid_references.len(), 1);
+        assert_eq!(report.invalid_references[0].req_id, "session.*");
+    }
 }
@@ -8,17 +8,22 @@ mod coverage;
 mod lexer;
 mod sources;
 mod spec;
+mod wildcard;
 
 #[cfg(feature = "reverse")]
 pub mod code_units;
 
 pub use coverage::CoverageReport;
-pub use lexer::{ParseWarning, RefVerb, ReqReference, Reqs, SourceSpan, WarningKind};
+pub use lexer::{
+    CustomVerb, DEFAULT_JSON_KEY, ExtractOptions, ParseWarning, RefVerb, ReqReference, Reqs,
+    SourceSpan, WarningKind, split_version,
+};
 pub use sources::{
     ExtractionResult, MemorySources, PathSources, SUPPORTED_EXTENSIONS, Sources,
     is_supported_extension,
 };
 pub use spec::ReqDefinition;
+pub use wildcard::{is_wildcard, ref_matches, wildcard_matches};
 
 #[cfg(feature = "walk")]
-pub use sources::WalkSources;
+pub use sources::{WalkSources, WalkedFiles};
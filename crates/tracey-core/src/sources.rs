@@ -1,6 +1,6 @@
 //! Source providers for requirement extraction
 
-use crate::lexer::{Reqs, extract_from_content};
+use crate::lexer::{ExtractOptions, Reqs, extract_from_content};
 use eyre::Result;
 use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
@@ -14,7 +14,6 @@ pub struct ExtractionResult {
 }
 
 /// File extensions that tracey knows how to scan for requirement references.
-/// These all use `//` and `/* */` comment syntax.
 pub const SUPPORTED_EXTENSIONS: &[&str] = &[
     "rs",     // Rust
     "swift",  // Swift
@@ -29,6 +28,8 @@ pub const SUPPORTED_EXTENSIONS: &[&str] = &[
     "hpp",    // C++ headers
     "cc",     // C++
     "cxx",    // C++
+    "hh",     // C++ headers
+    "hxx",    // C++ headers
     "m",      // Objective-C
     "mm",     // Objective-C++
     "java",   // Java
@@ -39,6 +40,27 @@ pub const SUPPORTED_EXTENSIONS: &[&str] = &[
     "cs",     // C#
     "zig",    // Zig
     "php",    // PHP
+    "py",     // Python
+    "rb",     // Ruby
+    "lua",    // Lua
+    "sh",     // Shell
+    "bash",   // Bash
+    "hs",     // Haskell
+    "ml",     // OCaml
+    "mli",    // OCaml interfaces
+    "ex",     // Elixir
+    "exs",    // Elixir script
+    "r",      // R
+    "R",      // R
+    "sc",     // Scala script
+    "mts",    // TypeScript ES module
+    "cts",    // TypeScript CommonJS
+    "yaml",   // YAML
+    "yml",    // YAML
+    "toml",   // TOML
+    "json",   // JSON (string values of the configured keys)
+    "sql",    // SQL
+    "md",     // Markdown (HTML comments)
 ];
 
 /// Check if a file extension is supported for scanning
@@ -76,7 +98,7 @@ impl Sources for PathSources {
             self.0.par_iter().try_for_each(|path| -> Result<()> {
                 let content = std::fs::read_to_string(path)?;
                 let mut file_reqs = Reqs::new();
-                extract_from_content(path, &content, &mut file_reqs);
+                extract_from_content(path, &content, &ExtractOptions::default(), &mut file_reqs);
 
                 let mut guard = reqs_mutex.lock().unwrap();
                 guard.extend(file_reqs);
@@ -94,7 +116,7 @@ impl Sources for PathSources {
             let mut reqs = Reqs::new();
             for path in self.0 {
                 let content = std::fs::read_to_string(&path)?;
-                extract_from_content(&path, &content, &mut reqs);
+                extract_from_content(&path, &content, &ExtractOptions::default(), &mut reqs);
             }
             Ok(ExtractionResult {
                 reqs,
@@ -130,7 +152,7 @@ impl Sources for MemorySources {
     fn extract(self) -> Result<ExtractionResult> {
         let mut reqs = Reqs::new();
         for (path, content) in self.0 {
-            extract_from_content(&path, &content, &mut reqs);
+            extract_from_content(&path, &content, &ExtractOptions::default(), &mut reqs);
         }
         Ok(ExtractionResult {
             reqs,
@@ -171,14 +193,67 @@ impl WalkSources {
     }
 }
 
+/// Files matched by a [`WalkSources`], without their content
 #[cfg(feature = "walk")]
-impl Sources for WalkSources {
-    fn extract(self) -> Result<ExtractionResult> {
-        use ignore::WalkBuilder;
+#[derive(Debug, Default)]
+pub struct WalkedFiles {
+    /// Matching files, sorted by path
+    pub files: Vec<PathBuf>,
+    /// Warnings about missing cross-workspace paths
+    pub warnings: Vec<String>,
+}
+
+#[cfg(feature = "walk")]
+impl WalkSources {
+    /// List the files that [`Sources::extract`] would read, without reading them.
+    ///
+    /// Callers that cache per-file extraction results use this to decide
+    /// which files need re-parsing.
+    pub fn files(&self) -> WalkedFiles {
         use std::sync::Mutex;
 
-        let reqs = Mutex::new(Reqs::new());
-        let warnings = Mutex::new(Vec::new());
+        let files = Mutex::new(Vec::new());
+        let warnings = self.walk(|path| files.lock().unwrap().push(path.to_path_buf()));
+
+        let mut files = files.into_inner().unwrap();
+        files.sort();
+        WalkedFiles { files, warnings }
+    }
+
+    /// Whether a file at `relative` (to the root) would be picked up by the
+    /// local include/exclude patterns, ignoring `.gitignore`.
+    ///
+    /// Used to select files from a tree that isn't checked out, such as an
+    /// older commit. Cross-workspace (`../`) patterns never match.
+    pub fn matches(&self, relative: &Path) -> bool {
+        if relative
+            .extension()
+            .is_none_or(|ext| !is_supported_extension(ext))
+        {
+            return false;
+        }
+
+        let local_includes: Vec<String> = self
+            .include
+            .iter()
+            .filter(|p| !p.starts_with("../"))
+            .cloned()
+            .collect();
+        if local_includes.is_empty() && !self.include.is_empty() {
+            return false;
+        }
+
+        let path = self.root.join(relative);
+        is_included(&path, &self.root, &local_includes)
+            && !is_excluded(&path, &self.root, &self.exclude)
+    }
+
+    /// Walk all matching files, calling `visit` for each one (possibly in
+    /// parallel). Returns warnings about missing cross-workspace paths.
+    fn walk(&self, visit: impl Fn(&Path) + Sync) -> Vec<String> {
+        use ignore::WalkBuilder;
+
+        let mut warnings = Vec::new();
 
         // r[impl ref.cross-workspace.paths]
         // Separate include patterns into local and cross-workspace
@@ -201,7 +276,7 @@ impl Sources for WalkSources {
 
             // Process files in parallel using ignore's parallel walker
             walker.run(|| {
-                let reqs_ref = &reqs;
+                let visit = &visit;
                 let include_patterns = include_patterns.to_vec();
                 let exclude_patterns = exclude_patterns.to_vec();
                 let root = root.to_path_buf();
@@ -233,14 +308,7 @@ impl Sources for WalkSources {
                         return ignore::WalkState::Continue;
                     }
 
-                    // Read and extract
-                    if let Ok(content) = std::fs::read_to_string(path) {
-                        let mut file_reqs = Reqs::new();
-                        extract_from_content(path, &content, &mut file_reqs);
-
-                        let mut guard = reqs_ref.lock().unwrap();
-                        guard.extend(file_reqs);
-                    }
+                    visit(path);
 
                     ignore::WalkState::Continue
                 })
@@ -268,7 +336,7 @@ impl Sources for WalkSources {
                     "Warning: Cross-workspace path not found: {}\n  Pattern: {}",
                     base_path, pattern
                 );
-                warnings.lock().unwrap().push(warning);
+                warnings.push(warning);
                 continue;
             }
 
@@ -278,9 +346,31 @@ impl Sources for WalkSources {
             walk_with_patterns(&resolved_path, &[adjusted_pattern], &self.exclude);
         }
 
+        warnings
+    }
+}
+
+#[cfg(feature = "walk")]
+impl Sources for WalkSources {
+    fn extract(self) -> Result<ExtractionResult> {
+        use std::sync::Mutex;
+
+        let reqs = Mutex::new(Reqs::new());
+
+        let warnings = self.walk(|path| {
+            // Read and extract
+            if let Ok(content) = std::fs::read_to_string(path) {
+                let mut file_reqs = Reqs::new();
+                extract_from_content(path, &content, &ExtractOptions::default(), &mut file_reqs);
+
+                let mut guard = reqs.lock().unwrap();
+                guard.extend(file_reqs);
+            }
+        });
+
         Ok(ExtractionResult {
             reqs: reqs.into_inner().unwrap(),
-            warnings: warnings.into_inner().unwrap(),
+            warnings,
         })
     }
 }
@@ -514,10 +604,21 @@ mod tests {
         assert!(is_supported_extension(OsStr::new("js")));
         assert!(is_supported_extension(OsStr::new("go")));
         assert!(is_supported_extension(OsStr::new("php")));
+        assert!(is_supported_extension(OsStr::new("cpp")));
+        assert!(is_supported_extension(OsStr::new("kt")));
+        assert!(is_supported_extension(OsStr::new("py")));
+        assert!(is_supported_extension(OsStr::new("rb")));
+        assert!(is_supported_extension(OsStr::new("hs")));
+        assert!(is_supported_extension(OsStr::new("ml")));
+
+        assert!(is_supported_extension(OsStr::new("yaml")));
+        assert!(is_supported_extension(OsStr::new("toml")));
+        assert!(is_supported_extension(OsStr::new("json")));
+        assert!(is_supported_extension(OsStr::new("sql")));
+        assert!(is_supported_extension(OsStr::new("md")));
 
-        assert!(!is_supported_extension(OsStr::new("md")));
         assert!(!is_supported_extension(OsStr::new("txt")));
-        assert!(!is_supported_extension(OsStr::new("json")));
+        assert!(!is_supported_extension(OsStr::new("csv")));
     }
 
     #[cfg(feature = "walk")]
@@ -557,6 +658,24 @@ mod tests {
             assert!(!matches_glob("Tests/AppTests.swift", "Sources/**/*.swift"));
         }
 
+        #[test]
+        fn test_walk_sources_matches() {
+            use super::super::WalkSources;
+            use std::path::Path;
+
+            let sources = WalkSources::new("/project")
+                .include(["src/**/*.rs", "../other/**/*.rs"])
+                .exclude(["src/generated/**"]);
+            assert!(sources.matches(Path::new("src/lib.rs")));
+            assert!(!sources.matches(Path::new("src/generated/api.rs")));
+            assert!(!sources.matches(Path::new("tests/it.rs")));
+            assert!(!sources.matches(Path::new("src/notes.md")));
+
+            let cross_only = WalkSources::new("/project").include(["../other/**/*.rs"]);
+            assert!(!cross_only.matches(Path::new("src/lib.rs")));
+            assert!(WalkSources::new("/project").matches(Path::new("src/lib.rs")));
+        }
+
         #[test]
         fn test_matches_glob_exact() {
             assert!(matches_glob("foo.rs", "foo.rs"));
@@ -0,0 +1,119 @@
+//! Wildcard requirement references like `r[verify channel.id.*]`
+//!
+//! r[impl ref.wildcard.syntax]
+//!
+//! Patterns are matched segment by segment: `*` matches any run of characters
+//! within a single segment, and a segment that is exactly `**` matches any
+//! number of segments, including none.
+
+/// Whether a referenced requirement ID is a wildcard pattern.
+pub fn is_wildcard(req_id: &str) -> bool {
+    req_id.contains('*')
+}
+
+/// Whether `rule_id` matches the wildcard `pattern`.
+pub fn wildcard_matches(pattern: &str, rule_id: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('.').collect();
+    let segments: Vec<&str> = rule_id.split('.').collect();
+    match_segments(&pattern, &segments)
+}
+
+/// Whether a reference to `req_id` points at `rule_id`: either the same ID, or
+/// a wildcard matching it.
+pub fn ref_matches(req_id: &str, rule_id: &str) -> bool {
+    if is_wildcard(req_id) {
+        wildcard_matches(req_id, rule_id)
+    } else {
+        req_id == rule_id
+    }
+}
+
+fn match_segments(pattern: &[&str], segments: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => segments.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=segments.len()).any(|skip| match_segments(rest, &segments[skip..]))
+        }
+        Some((first, rest)) => segments.split_first().is_some_and(|(segment, tail)| {
+            match_segment(first, segment) && match_segments(rest, tail)
+        }),
+    }
+}
+
+/// Match one segment, where `*` stands for any run of characters.
+fn match_segment(pattern: &str, segment: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = segment.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*` at all
+        return rest.is_empty();
+    };
+
+    for part in middle {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // r[verify ref.wildcard.syntax]
+    #[test]
+    fn test_wildcard_matches_within_segment() {
+        assert!(wildcard_matches("channel.id.*", "channel.id.allocation"));
+        assert!(!wildcard_matches("channel.id.*", "channel.id"));
+        assert!(!wildcard_matches(
+            "channel.id.*",
+            "channel.id.allocation.retry"
+        ));
+        assert!(wildcard_matches(
+            "channel.*.allocation",
+            "channel.id.allocation"
+        ));
+        assert!(wildcard_matches(
+            "channel.id.alloc-*",
+            "channel.id.alloc-fast"
+        ));
+        assert!(!wildcard_matches(
+            "channel.id.alloc-*",
+            "channel.id.release"
+        ));
+        assert!(wildcard_matches(
+            "auth.*-token-*",
+            "auth.refresh-token-rotation"
+        ));
+        assert!(!wildcard_matches("auth.*-token-*", "auth.token"));
+    }
+
+    #[test]
+    fn test_double_star_matches_any_depth() {
+        assert!(wildcard_matches("channel.**", "channel.id"));
+        assert!(wildcard_matches(
+            "channel.**",
+            "channel.id.allocation.retry"
+        ));
+        assert!(wildcard_matches(
+            "channel.**.retry",
+            "channel.id.allocation.retry"
+        ));
+        assert!(wildcard_matches("channel.**.retry", "channel.retry"));
+        assert!(!wildcard_matches("channel.**", "connection.id"));
+    }
+
+    #[test]
+    fn test_ref_matches_exact_ids() {
+        assert!(ref_matches("auth.login", "auth.login"));
+        assert!(!ref_matches("auth.login", "auth.logout"));
+        assert!(ref_matches("auth.*", "auth.logout"));
+    }
+}
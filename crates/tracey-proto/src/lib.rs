@@ -28,6 +28,9 @@ pub struct UncoveredRequest {
     /// Filter rules by ID prefix (case-insensitive)
     #[facet(default)]
     pub prefix: Option<String>,
+    /// Only include rules at this level (must, should, may)
+    #[facet(default)]
+    pub level: Option<String>,
 }
 
 /// Response for uncovered rules query
@@ -40,6 +43,12 @@ pub struct UncoveredResponse {
     pub uncovered_count: usize,
     /// Rules grouped by section
     pub by_section: Vec<SectionRules>,
+    /// Rules with no current implementation because every impl reference is stale
+    #[facet(default)]
+    pub stale: Vec<StaleRuleRef>,
+    /// Coverage of each ID subtree under the requested prefix
+    #[facet(default)]
+    pub rollup: Vec<ApiRuleTreeEntry>,
 }
 
 /// Rules within a section
@@ -58,6 +67,17 @@ pub struct RuleRef {
     pub text: Option<String>,
 }
 
+/// A rule referenced against an older version than its current definition
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct StaleRuleRef {
+    pub id: String,
+    /// Current version of the rule
+    pub version: u32,
+    /// References written against an older version
+    pub stale_refs: Vec<ApiCodeRef>,
+}
+
 /// Request for untested rules query
 #[derive(Debug, Clone, Facet)]
 #[facet(rename_all = "camelCase")]
@@ -68,6 +88,8 @@ pub struct UntestedRequest {
     pub impl_name: Option<String>,
     #[facet(default)]
     pub prefix: Option<String>,
+    #[facet(default)]
+    pub level: Option<String>,
 }
 
 /// Response for untested rules query
@@ -79,6 +101,38 @@ pub struct UntestedResponse {
     pub total_rules: usize,
     pub untested_count: usize,
     pub by_section: Vec<SectionRules>,
+    /// Implemented rules whose verification references are all stale
+    #[facet(default)]
+    pub stale: Vec<StaleRuleRef>,
+    /// Coverage of each ID subtree under the requested prefix
+    #[facet(default)]
+    pub rollup: Vec<ApiRuleTreeEntry>,
+}
+
+/// Request for the requirement ID tree
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct RuleTreeRequest {
+    #[facet(default)]
+    pub spec: Option<String>,
+    #[facet(default)]
+    pub impl_name: Option<String>,
+    /// Only include entries under this ID prefix (case-insensitive)
+    #[facet(default)]
+    pub prefix: Option<String>,
+    /// Only count rules at this level (must, should, may)
+    #[facet(default)]
+    pub level: Option<String>,
+}
+
+/// Response for the requirement ID tree
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct RuleTreeResponse {
+    pub spec: String,
+    pub impl_name: String,
+    /// Tree entries in depth-first order
+    pub entries: Vec<ApiRuleTreeEntry>,
 }
 
 /// Request for unmapped code query
@@ -146,6 +200,19 @@ pub struct ImplStatus {
     pub total_rules: usize,
     pub covered_rules: usize,
     pub verified_rules: usize,
+    /// The same counts for each requirement level (must, should, may) that has rules
+    #[facet(default)]
+    pub by_level: Vec<LevelStatus>,
+}
+
+/// Coverage counts for the requirements at one level
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct LevelStatus {
+    pub level: String,
+    pub total_rules: usize,
+    pub covered_rules: usize,
+    pub verified_rules: usize,
 }
 
 /// Information about a specific rule
@@ -172,6 +239,9 @@ pub struct RuleCoverage {
     pub impl_name: String,
     pub impl_refs: Vec<ApiCodeRef>,
     pub verify_refs: Vec<ApiCodeRef>,
+    /// Outcome of the verifying tests, when test results are configured
+    #[facet(default)]
+    pub verify_status: Option<VerifyStatus>,
 }
 
 /// Response from reload command
@@ -240,6 +310,65 @@ pub struct ValidateRequest {
     pub impl_name: Option<String>,
 }
 
+/// Request for the changes a branch made since a git ref
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct DiffScopeRequest {
+    #[facet(default)]
+    pub spec: Option<String>,
+    #[facet(default)]
+    pub impl_name: Option<String>,
+    /// Git ref to compare the working tree against (e.g. `origin/main`)
+    pub since: String,
+}
+
+/// Requirements and code changed since a git ref
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct DiffScopeResponse {
+    pub spec: String,
+    pub impl_name: String,
+    pub since: String,
+    /// Requirements added or edited in spec markdown
+    pub changed_rules: Vec<ChangedRule>,
+    /// Code units touched by the diff that carry no requirement reference
+    pub unannotated_units: Vec<DiffUnit>,
+    /// False if a new requirement has no impl, or a new function in an
+    /// annotated file has no reference
+    pub passing: bool,
+}
+
+/// A requirement added or edited since the ref
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct ChangedRule {
+    pub id: String,
+    /// The requirement didn't exist at the ref (otherwise its text changed)
+    pub added: bool,
+    /// Whether the requirement has impl references
+    pub covered: bool,
+    #[facet(default)]
+    pub source_file: Option<String>,
+    #[facet(default)]
+    pub source_line: Option<usize>,
+}
+
+/// A code unit without requirement references that the diff touched
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct DiffUnit {
+    pub path: String,
+    pub kind: String,
+    #[facet(default)]
+    pub name: Option<String>,
+    pub start_line: usize,
+    pub end_line: usize,
+    /// Every line of the unit is new
+    pub added: bool,
+    /// Other code units in the same file carry requirement references
+    pub in_covered_file: bool,
+}
+
 /// Notification of data update (sent via streaming)
 #[derive(Debug, Clone, Facet)]
 #[facet(rename_all = "camelCase")]
@@ -332,6 +461,18 @@ pub struct HoverRef {
     pub line: usize,
 }
 
+/// A requirement matched by a wildcard reference
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct HoverMatch {
+    /// Rule ID
+    pub rule_id: String,
+    /// Number of impl references
+    pub impl_count: usize,
+    /// Number of verify references
+    pub verify_count: usize,
+}
+
 /// Hover information for a requirement reference
 #[derive(Debug, Clone, Facet)]
 #[facet(rename_all = "camelCase")]
@@ -358,6 +499,9 @@ pub struct HoverInfo {
     /// Verification references (file:line)
     #[facet(default)]
     pub verify_refs: Vec<HoverRef>,
+    /// Requirements matched by a wildcard reference (empty otherwise)
+    #[facet(default)]
+    pub matched_rules: Vec<HoverMatch>,
     /// Range of the reference (for highlighting)
     pub range_start_line: u32,
     pub range_start_char: u32,
@@ -424,6 +568,12 @@ pub struct LspSymbol {
     pub start_char: u32,
     pub end_line: u32,
     pub end_char: u32,
+    /// File the symbol is in, relative to the project root (workspace symbols only)
+    #[facet(default)]
+    pub path: Option<String>,
+    /// Enclosing symbol, e.g. the parent ID prefix of a requirement
+    #[facet(default)]
+    pub container: Option<String>,
 }
 
 /// A semantic token
@@ -500,7 +650,7 @@ pub struct LspCodeAction {
     pub title: String,
     /// Kind: "quickfix", "source", etc.
     pub kind: String,
-    /// Command name
+    /// Command name (empty for actions that only apply `edit`)
     pub command: String,
     /// Command arguments
     #[facet(default)]
@@ -508,6 +658,22 @@ pub struct LspCodeAction {
     /// Is this the preferred action?
     #[facet(default)]
     pub is_preferred: bool,
+    /// Edit to apply, before running the command if there is one
+    #[facet(default)]
+    pub edit: Option<LspTextEdit>,
+}
+
+/// A place a new requirement definition could go, for `tracey.createRequirement`
+#[derive(Debug, Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+pub struct RequirementStub {
+    /// Spec the requirement would belong to
+    pub spec: String,
+    /// Heading of the section the stub goes in (`None` before the first heading)
+    #[facet(default)]
+    pub section_title: Option<String>,
+    /// Insertion of the stub into the spec file
+    pub edit: LspTextEdit,
 }
 
 /// Request for inlay hints
@@ -586,11 +752,17 @@ pub trait TraceyDaemon {
     /// Get coverage status for all specs/impls
     async fn status(&self) -> StatusResponse;
 
-    /// Get uncovered rules (rules without implementation references)
-    async fn uncovered(&self, req: UncoveredRequest) -> UncoveredResponse;
+    /// Get uncovered rules (rules without implementation references).
+    /// Fails on an unknown level.
+    async fn uncovered(&self, req: UncoveredRequest) -> Result<UncoveredResponse, String>;
+
+    /// Get untested rules (rules with impl but no verify references).
+    /// Fails on an unknown level.
+    async fn untested(&self, req: UntestedRequest) -> Result<UntestedResponse, String>;
 
-    /// Get untested rules (rules with impl but no verify references)
-    async fn untested(&self, req: UntestedRequest) -> UntestedResponse;
+    /// Get the requirement ID tree with coverage rolled up per prefix.
+    /// Fails on an unknown level.
+    async fn rule_tree(&self, req: RuleTreeRequest) -> Result<RuleTreeResponse, String>;
 
     /// Get unmapped code (code units without requirement references)
     async fn unmapped(&self, req: UnmappedRequest) -> UnmappedResponse;
@@ -598,6 +770,9 @@ pub trait TraceyDaemon {
     /// Get details for a specific rule by ID
     async fn rule(&self, rule_id: String) -> Option<RuleInfo>;
 
+    /// Get requirements and unannotated code changed since a git ref
+    async fn diff_scope(&self, req: DiffScopeRequest) -> Result<DiffScopeResponse, String>;
+
     // === Configuration ===
 
     /// Get current configuration
@@ -645,6 +820,9 @@ pub trait TraceyDaemon {
     /// Get file content with syntax highlighting and code units
     async fn file(&self, req: FileRequest) -> Option<ApiFileData>;
 
+    /// Get coverage history recorded for a spec/impl pair, oldest first
+    async fn history(&self, spec: String, impl_name: String) -> ApiHistory;
+
     /// Get rendered spec content with outline
     async fn spec_content(&self, spec: String, impl_name: String) -> Option<ApiSpecData>;
 
@@ -709,6 +887,18 @@ pub trait TraceyDaemon {
     /// Get code actions for a position
     async fn lsp_code_actions(&self, req: LspPositionRequest) -> Vec<LspCodeAction>;
 
+    /// Places to define a new requirement, best match first
+    async fn lsp_requirement_stubs(&self, req_id: String) -> Vec<RequirementStub>;
+
+    /// The requirement at a position, as a call hierarchy item
+    async fn lsp_prepare_call_hierarchy(&self, req: LspPositionRequest) -> Vec<LspSymbol>;
+
+    /// Requirements the given requirement depends on
+    async fn lsp_dependencies(&self, req_id: String) -> Vec<LspSymbol>;
+
+    /// Requirements that depend on the given requirement
+    async fn lsp_dependents(&self, req_id: String) -> Vec<LspSymbol>;
+
     /// Get document highlight ranges (same requirement references)
     async fn lsp_document_highlight(&self, req: LspPositionRequest) -> Vec<LspLocation>;
 
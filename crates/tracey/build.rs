@@ -63,6 +63,9 @@ fn generate_typescript_types() {
     generator.add_type::<OutlineCoverage>();
     generator.add_type::<OutlineEntry>();
     generator.add_type::<ApiSpecData>();
+    generator.add_type::<ApiHistory>();
+    generator.add_type::<ApiRegression>();
+    generator.add_type::<ApiHistoryEntry>();
     generator.add_type::<ValidationResult>();
     generator.add_type::<ValidationError>();
 
@@ -37,7 +37,7 @@ export interface ValidationError {
 /**
  * Error codes for validation errors
  */
-export type ValidationErrorCode = "circular_dependency" | "invalid_naming" | "unknown_requirement" | "duplicate_requirement" | "unknown_prefix" | "impl_in_test_file";
+export type ValidationErrorCode = "circular_dependency" | "invalid_naming" | "unknown_requirement" | "duplicate_requirement" | "unknown_prefix" | "impl_in_test_file" | "stale_reference" | "failing_verification" | "deprecated_requirement" | "removed_requirement";
 
 /**
  * Validation results for a spec/implementation pair
@@ -65,6 +65,54 @@ export interface ValidationResult {
   errorCount: number;
 }
 
+/**
+ * Coverage of one spec/impl pair at one commit
+ */
+export interface ApiHistoryEntry {
+  /**
+   * Full commit SHA
+   */
+  commit: string;
+  /**
+   * Commit time, in seconds since the Unix epoch
+   */
+  timestamp: number;
+  spec: string;
+  implName: string;
+  totalRules: number;
+  implCovered: number;
+  verifyCovered: number;
+  implPercent: number;
+  verifyPercent: number;
+}
+
+/**
+ * A drop in coverage from one history entry to the next
+ */
+export interface ApiRegression {
+  /**
+   * Commit at which coverage dropped
+   */
+  commit: string;
+  timestamp: number;
+  /**
+   * Which coverage dropped: `impl` or `verify`
+   */
+  metric: string;
+  fromPercent: number;
+  toPercent: number;
+}
+
+/**
+ * Coverage history of a spec/impl pair, oldest first
+ */
+export interface ApiHistory {
+  spec: string;
+  implName: string;
+  entries: ApiHistoryEntry[];
+  regressions: ApiRegression[];
+}
+
 /**
  * Spec content (may span multiple files)
  */
@@ -118,6 +166,10 @@ export interface OutlineCoverage {
    * Number of rules with verification refs
    */
   verifyCount: number;
+  /**
+   * Number of rules referenced against an older version
+   */
+  staleCount: number;
   /**
    * Total number of rules
    */
@@ -206,6 +258,10 @@ export interface ApiCodeRef {
 
 export interface ApiRule {
   id: string;
+  /**
+   * Requirement version from the `+N` suffix of the definition (1 if absent)
+   */
+  version: number;
   /**
    * Raw markdown source (without r[...] marker, but with `>` prefixes for blockquote rules)
    */
@@ -214,7 +270,14 @@ export interface ApiRule {
    * Rendered HTML (for dashboard display)
    */
   html: string;
+  /**
+   * Lifecycle status from metadata (e.g. draft, stable, deprecated, removed)
+   */
   status?: string;
+  /**
+   * Requirement level (must, should, may), from metadata or inferred
+   * from RFC 2119 keywords
+   */
   level?: string;
   sourceFile?: string;
   sourceLine?: number;
@@ -230,11 +293,42 @@ export interface ApiRule {
   implRefs: ApiCodeRef[];
   verifyRefs: ApiCodeRef[];
   dependsRefs: ApiCodeRef[];
+  /**
+   * References written against an older version of this rule.
+   * These don't count towards coverage.
+   */
+  staleRefs: ApiCodeRef[];
+  /**
+   * Outcome of the tests behind `verify_refs`, when test results are configured
+   */
+  verifyStatus?: VerifyStatus;
+  /**
+   * References using custom verbs, one entry per verb that has any.
+   * Those that count toward coverage also appear in `impl_refs` or `verify_refs`.
+   */
+  customRefs: ApiVerbRefs[];
+}
+
+/**
+ * Outcome of the tests behind a rule's `verify` references
+ */
+export type VerifyStatus = "passing" | "failing" | "not-run";
+
+/**
+ * A rule's references using one custom verb
+ */
+export interface ApiVerbRefs {
+  verb: string;
+  refs: ApiCodeRef[];
 }
 
 export interface ApiSpecForward {
   name: string;
   rules: ApiRule[];
+  /**
+   * Levels left out of the headline coverage (the impl's `exclude_levels`, normalized)
+   */
+  excludeLevels: string[];
 }
 
 /**
@@ -263,6 +357,21 @@ export interface ApiSpecInfo {
    * Available implementations for this spec
    */
   implementations: string[];
+  /**
+   * Custom verbs declared for this spec, in config order
+   */
+  verbs: ApiVerb[];
+}
+
+/**
+ * A custom verb declared in the spec config
+ */
+export interface ApiVerb {
+  name: string;
+  /**
+   * Coverage the verb counts toward ("impl" or "verify"), if any
+   */
+  countsAs?: string;
 }
 
 /**
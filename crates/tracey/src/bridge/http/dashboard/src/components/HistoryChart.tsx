@@ -0,0 +1,70 @@
+// r[impl dashboard.coverage.history]
+import { html } from "../main";
+import type { HistoryData } from "../types";
+
+const WIDTH = 200;
+const HEIGHT = 36;
+
+interface HistoryChartProps {
+  history: HistoryData | null;
+}
+
+function shortCommit(commit: string): string {
+  return commit.slice(0, 8);
+}
+
+function formatDate(timestamp: number): string {
+  return new Date(timestamp * 1000).toLocaleDateString();
+}
+
+// Sparkline of impl and verify coverage per recorded commit, with regressions marked
+export function HistoryChart({ history }: HistoryChartProps) {
+  if (!history || history.entries.length < 2) return null;
+
+  const { entries, regressions } = history;
+  const x = (i: number) => (i / (entries.length - 1)) * WIDTH;
+  const y = (percent: number) => HEIGHT - (percent / 100) * HEIGHT;
+  const points = (key: "implPercent" | "verifyPercent") =>
+    entries.map((e, i) => `${x(i).toFixed(1)},${y(e[key]).toFixed(1)}`).join(" ");
+
+  const first = entries[0];
+  const last = entries[entries.length - 1];
+
+  return html`
+    <div class="stat history-stat">
+      <span class="stat-label">
+        Trend (${formatDate(first.timestamp)} – ${formatDate(last.timestamp)})
+        ${regressions.length > 0 &&
+        html`<span class="history-regressions">
+          ${regressions.length} regression${regressions.length === 1 ? "" : "s"}
+        </span>`}
+      </span>
+      <svg
+        class="history-chart"
+        width=${WIDTH}
+        height=${HEIGHT}
+        viewBox=${`0 0 ${WIDTH} ${HEIGHT}`}
+        preserveAspectRatio="none"
+      >
+        <polyline class="history-line history-line-impl" points=${points("implPercent")} />
+        <polyline class="history-line history-line-verify" points=${points("verifyPercent")} />
+        ${regressions.map((r) => {
+          const i = entries.findIndex((e) => e.commit === r.commit);
+          if (i < 0) return null;
+          return html`<circle
+            key=${`${r.commit}:${r.metric}`}
+            class="history-regression"
+            cx=${x(i)}
+            cy=${y(r.toPercent)}
+            r="3"
+          >
+            <title>
+              ${r.metric} coverage ${r.fromPercent.toFixed(1)}% → ${r.toPercent.toFixed(1)}% at
+              ${shortCommit(r.commit)} (${formatDate(r.timestamp)})
+            </title>
+          </circle>`;
+        })}
+      </svg>
+    </div>
+  `;
+}
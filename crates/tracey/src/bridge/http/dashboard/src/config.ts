@@ -85,6 +85,12 @@ export const TAB_ICON_NAMES: Record<string, string> = {
   sources: "folder-open",
 };
 
+// r[impl export.read-only]
+// Set when the dashboard was loaded from `tracey export-site` output
+export const READ_ONLY =
+  typeof document !== "undefined" &&
+  document.querySelector('meta[name="tracey-mode"]')?.getAttribute("content") === "static";
+
 // Detect platform for keyboard shortcuts
 export const isMac =
   typeof navigator !== "undefined" && navigator.platform.toUpperCase().indexOf("MAC") >= 0;
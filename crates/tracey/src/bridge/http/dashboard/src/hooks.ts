@@ -1,12 +1,16 @@
 // Custom hooks
 import { useCallback, useEffect, useState } from "preact/hooks";
+import { READ_ONLY } from "./config";
 import type {
   ApiData,
   Config,
   FileContent,
   ForwardData,
   HealthData,
+  HistoryData,
   ReverseData,
+  SearchResult,
+  SearchResults,
   SpecContent,
 } from "./types";
 
@@ -25,7 +29,16 @@ export class ApiError extends Error {
   }
 }
 
-async function fetchJson<T>(url: string): Promise<T> {
+export async function fetchJson<T>(url: string): Promise<T> {
+  if (!READ_ONLY) return getJson(url);
+  const resolved = await staticUrl(url);
+  if (!resolved) {
+    throw new ApiError(404, "not_found", `Not available in a static export: ${url}`);
+  }
+  return getJson(resolved);
+}
+
+async function getJson<T>(url: string): Promise<T> {
   const res = await fetch(url);
   if (!res.ok) {
     // Try to parse error response
@@ -40,6 +53,80 @@ async function fetchJson<T>(url: string): Promise<T> {
   return res.json();
 }
 
+// r[impl export.read-only]
+// A static export has the API responses as JSON files (see `tracey export-site`),
+// next to the `assets/` directory this bundle is loaded from. Resolving against
+// the bundle rather than the page keeps working under a subpath and after the
+// router changes the page URL.
+const SITE_ROOT = new URL("../", import.meta.url);
+
+function siteUrl(path: string): string {
+  return new URL(path, SITE_ROOT).href;
+}
+
+let staticConfig: Promise<Config> | null = null;
+const staticFileIndexes = new Map<string, Promise<Record<string, number>>>();
+
+async function staticUrl(url: string): Promise<string | null> {
+  const { pathname, searchParams } = new URL(url, window.location.origin);
+  const endpoint = pathname.replace(/^\/api\//, "");
+  if (endpoint === "config") return siteUrl("api/config.json");
+  if (!["forward", "reverse", "spec", "history", "file"].includes(endpoint)) return null;
+
+  // Same defaults as the HTTP bridge: first spec, first impl
+  let spec = searchParams.get("spec");
+  let impl = searchParams.get("impl");
+  if (!spec || !impl) {
+    staticConfig ??= getJson<Config>(siteUrl("api/config.json"));
+    const config = await staticConfig;
+    spec ||= config.specs?.[0]?.name || null;
+    impl ||= config.specs?.find((s) => s.name === spec)?.implementations?.[0] || null;
+  }
+  if (!spec || !impl) return null;
+
+  const base = siteUrl(`api/${encodeURIComponent(spec)}/${encodeURIComponent(impl)}`);
+  if (endpoint !== "file") return `${base}/${endpoint}.json`;
+
+  let index = staticFileIndexes.get(base);
+  if (!index) {
+    index = getJson<Record<string, number>>(`${base}/files.json`);
+    staticFileIndexes.set(base, index);
+  }
+  const n = (await index)[searchParams.get("path") || ""];
+  return n === undefined ? null : `${base}/files/${n}.json`;
+}
+
+let staticSearchEntries: Promise<Omit<SearchResult, "highlighted" | "score">[]> | null = null;
+
+function escapeHtml(s: string): string {
+  return s.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}
+
+// Case-insensitive substring search, like the daemon's fallback index
+async function staticSearch(query: string, limit: number): Promise<SearchResults> {
+  staticSearchEntries ??= getJson(siteUrl("api/search.json"));
+  const entries = await staticSearchEntries;
+  const needle = query.toLowerCase();
+  const pattern = new RegExp(escapeHtml(query).replace(/[.*+?^${}()|[\]\\]/g, "\\$&"), "gi");
+
+  const results = entries
+    .filter((e) => e.content.toLowerCase().includes(needle) || e.id.toLowerCase().includes(needle))
+    // r[impl dashboard.search.prioritize-spec]
+    .sort((a, b) => (a.kind === b.kind ? 0 : a.kind === "rule" ? -1 : 1))
+    .slice(0, limit)
+    .map((e) => ({
+      ...e,
+      highlighted: escapeHtml(e.content).replace(pattern, (m) => `<mark>${m}</mark>`),
+      score: 1,
+    }));
+  return { query, results };
+}
+
+export function search(query: string, limit: number): Promise<SearchResults> {
+  if (READ_ONLY) return staticSearch(query, limit);
+  return getJson<SearchResults>(`/api/search?q=${encodeURIComponent(query)}&limit=${limit}`);
+}
+
 // Parse spec and impl from URL pathname
 // URL format: /:spec/:impl/:view/...
 function getImplFromUrl(): { spec: string | null; impl: string | null } {
@@ -153,6 +240,9 @@ export function useApi(): UseApiResult {
   // r[impl dashboard.editing.reload.live-update]
   // Connect to WebSocket for live version updates
   useEffect(() => {
+    // A static export never changes
+    if (READ_ONLY) return;
+
     let ws: WebSocket | null = null;
     let reconnectTimeout: ReturnType<typeof setTimeout> | null = null;
     let lastVersion: number | null = null;
@@ -260,3 +350,21 @@ export function useSpec(name: string | null, version: string | null): SpecConten
 
   return spec;
 }
+
+// r[impl dashboard.coverage.history]
+export function useHistory(version: string | null): HistoryData | null {
+  const [history, setHistory] = useState<HistoryData | null>(null);
+  const path = window.location.pathname;
+
+  useEffect(() => {
+    const { spec, impl } = getImplFromUrl();
+    fetchJson<HistoryData>(apiUrl("/api/history", spec, impl))
+      .then(setHistory)
+      .catch((e) => {
+        console.error("Failed to load coverage history:", e);
+        setHistory(null);
+      });
+  }, [version, path]);
+
+  return history;
+}
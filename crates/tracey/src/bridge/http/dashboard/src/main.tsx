@@ -4,10 +4,10 @@ import { useCallback, useContext, useEffect, useRef, useState } from "preact/hoo
 import { LocationProvider, Route, Router, useLocation, useRoute } from "preact-iso";
 import "./style.scss";
 
-import { getDeviconClass, modKey, TAB_ICON_NAMES } from "./config";
+import { getDeviconClass, modKey, READ_ONLY, TAB_ICON_NAMES } from "./config";
 
 // Modules
-import { type UseApiResult, useApi } from "./hooks";
+import { search, type UseApiResult, useApi } from "./hooks";
 import { buildUrl } from "./router";
 // Types
 import type {
@@ -190,9 +190,7 @@ function SearchModal({ onClose, onSelect }: SearchModalProps) {
 
     searchTimeoutRef.current = setTimeout(async () => {
       try {
-        const res = await fetch(`/api/search?q=${encodeURIComponent(query)}&limit=50`);
-        const data = await res.json();
-        setResults(data);
+        setResults(await search(query, 50));
         setSelectedIndex(0);
       } catch (e) {
         console.error("Search failed:", e);
@@ -1005,7 +1003,7 @@ function SourcesViewRoute() {
 function CoverageViewRoute() {
   const { params, query } = useRoute();
   const { route } = useLocation();
-  const { data } = useApiContext();
+  const { data, version } = useApiContext();
 
   if (!data) return html`<div class="loading">Loading...</div>`;
 
@@ -1049,6 +1047,7 @@ function CoverageViewRoute() {
     <${CoverageView}
       data=${forward}
       config=${config}
+      version=${version}
       search=${search}
       onSearchChange=${setSearch}
       level=${level}
@@ -1065,6 +1064,9 @@ function CoverageViewRoute() {
 // Mount
 // ========================================================================
 
+// r[impl export.read-only]
+if (READ_ONLY) document.body.classList.add("read-only");
+
 render(
   html`
     <${LocationProvider}>
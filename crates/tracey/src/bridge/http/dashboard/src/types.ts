@@ -4,6 +4,9 @@ import type {
   ApiConfig,
   ApiFileData,
   ApiForwardData,
+  ApiHistory,
+  ApiHistoryEntry,
+  ApiRegression,
   ApiReverseData,
   ApiSpecData,
   OutlineCoverage,
@@ -19,6 +22,9 @@ export type ReverseData = ApiReverseData;
 export type FileContent = ApiFileData;
 export type CodeUnit = ApiCodeUnit;
 export type SpecContent = ApiSpecData;
+export type HistoryData = ApiHistory;
+export type HistoryEntry = ApiHistoryEntry;
+export type Regression = ApiRegression;
 export type { OutlineCoverage, OutlineEntry, SpecSection };
 
 // Health data from daemon
@@ -69,12 +75,15 @@ export interface FileRef {
   line: number;
 }
 
+export type VerifyStatus = "passing" | "failing" | "not-run";
+
 export interface Rule {
   id: string;
   html?: string;
   level?: string;
   implRefs: FileRef[];
   verifyRefs: FileRef[];
+  verifyStatus?: VerifyStatus;
 }
 
 export interface Spec {
@@ -210,6 +219,7 @@ export interface LucideIconProps {
 export interface CoverageViewProps {
   data: ForwardData;
   config: Config;
+  version: string | null;
   search: string;
   onSearchChange: (search: string) => void;
   level: string;
@@ -258,7 +268,7 @@ export interface CodeViewProps {
 export interface FileRefProps {
   file: string;
   line: number;
-  type: "impl" | "verify";
+  type: "impl" | "verify" | "source";
   onSelectFile: (path: string, line?: number | null) => void;
 }
 
@@ -1,9 +1,35 @@
 import { useCallback, useEffect, useMemo, useState } from "preact/hooks";
+import type { ApiVerb, ApiVerbRefs } from "../api-types";
+import { HistoryChart } from "../components/HistoryChart";
 import { LEVELS } from "../config";
+import { useHistory } from "../hooks";
 import { FileRef, html } from "../main";
-import type { CoverageViewProps } from "../types";
+import type { CoverageViewProps, VerifyStatus } from "../types";
 import { getStatClass } from "../utils";
 
+// r[impl test-results.dashboard]
+const VERIFY_STATUS_LABELS: Record<VerifyStatus, string> = {
+	passing: "tests passing",
+	failing: "tests failing",
+	"not-run": "tests not run",
+};
+
+// r[impl status.coverage]
+// Rules with these statuses aren't expected to be covered
+const INACTIVE_STATUSES = ["draft", "deprecated", "removed"];
+
+function isActive(rule: { status?: string }) {
+	return !rule.status || !INACTIVE_STATUSES.includes(rule.status);
+}
+
+// r[impl dashboard.coverage.filter-verb]
+// Filters for custom verbs look like `verb:bench`
+const VERB_FILTER_PREFIX = "verb:";
+
+function customRefs(rule: { customRefs: ApiVerbRefs[] }, verb: string) {
+	return rule.customRefs.find((c) => c.verb === verb)?.refs ?? [];
+}
+
 // r[impl dashboard.coverage.table]
 // r[impl dashboard.coverage.filter-type]
 // r[impl dashboard.coverage.filter-level]
@@ -12,6 +38,8 @@ import { getStatClass } from "../utils";
 // r[impl dashboard.coverage.ref-links]
 export function CoverageView({
 	data,
+	config,
+	version,
 	search,
 	level,
 	onLevelChange,
@@ -21,6 +49,7 @@ export function CoverageView({
 	onSelectFile,
 }: CoverageViewProps) {
 	const [levelOpen, setLevelOpen] = useState(false);
+	const history = useHistory(version);
 
 	// Close dropdowns when clicking outside
 	useEffect(() => {
@@ -38,36 +67,44 @@ export function CoverageView({
 		[data],
 	);
 
-	// Infer level from rule html if not explicitly set
-	const inferLevel = useCallback((rule: { level?: string; html?: string }) => {
-		if (rule.level) return rule.level.toLowerCase();
-		if (!rule.html) return null;
-		const text = rule.html.toUpperCase();
-		if (
-			text.includes("MUST") ||
-			text.includes("SHALL") ||
-			text.includes("REQUIRED")
-		)
-			return "must";
-		if (text.includes("SHOULD") || text.includes("RECOMMENDED"))
-			return "should";
-		if (text.includes("MAY") || text.includes("OPTIONAL")) return "may";
-		return null;
-	}, []);
+	// Custom verbs declared by the specs shown here
+	const verbs = useMemo(() => {
+		const names = new Set(data.specs.map((s) => s.name));
+		const seen = new Map<string, ApiVerb>();
+		for (const spec of config.specs) {
+			if (!names.has(spec.name)) continue;
+			for (const verb of spec.verbs) {
+				if (!seen.has(verb.name)) seen.set(verb.name, verb);
+			}
+		}
+		return [...seen.values()];
+	}, [config, data]);
+
+	// The daemon fills in the level, inferring it from RFC 2119 keywords
+	// when the rule doesn't set one
+	const ruleLevel = useCallback(
+		(rule: { level?: string }) => rule.level?.toLowerCase() ?? null,
+		[],
+	);
 
 	const filteredRules = useMemo(() => {
 		let rules = allRules;
 
 		// Filter by level
 		if (level !== "all") {
-			rules = rules.filter((r) => inferLevel(r) === level);
+			rules = rules.filter((r) => ruleLevel(r) === level);
 		}
 
 		// Filter by coverage
 		if (filter === "impl") {
-			rules = rules.filter((r) => r.implRefs.length === 0);
+			rules = rules.filter((r) => isActive(r) && r.implRefs.length === 0);
 		} else if (filter === "verify") {
-			rules = rules.filter((r) => r.verifyRefs.length === 0);
+			rules = rules.filter((r) => isActive(r) && r.verifyRefs.length === 0);
+		} else if (filter?.startsWith(VERB_FILTER_PREFIX)) {
+			const verb = filter.slice(VERB_FILTER_PREFIX.length);
+			rules = rules.filter(
+				(r) => isActive(r) && customRefs(r, verb).length === 0,
+			);
 		}
 
 		// Filter by search
@@ -80,24 +117,40 @@ export function CoverageView({
 		}
 
 		return rules;
-	}, [allRules, search, level, filter, inferLevel]);
+	}, [allRules, search, level, filter, ruleLevel]);
 
 	const stats = useMemo(() => {
-		let rules = allRules;
+		let rules = allRules.filter(isActive);
 		if (level !== "all") {
-			rules = rules.filter((r) => inferLevel(r) === level);
+			rules = rules.filter((r) => ruleLevel(r) === level);
+		} else {
+			// The headline leaves out each impl's excluded levels
+			const excluded = new Map(
+				data.specs.map((s) => [s.name, s.excludeLevels ?? []]),
+			);
+			rules = rules.filter((r) => {
+				const l = ruleLevel(r);
+				return l === null || !excluded.get(r.spec)?.includes(l);
+			});
 		}
 		const total = rules.length;
 		const impl = rules.filter((r) => r.implRefs.length > 0).length;
 		const verify = rules.filter((r) => r.verifyRefs.length > 0).length;
+		const byVerb = verbs.map((verb) => {
+			const count = rules.filter(
+				(r) => customRefs(r, verb.name).length > 0,
+			).length;
+			return { verb, pct: total ? (count / total) * 100 : 0 };
+		});
 		return {
 			total,
 			impl,
 			verify,
 			implPct: total ? (impl / total) * 100 : 0,
 			verifyPct: total ? (verify / total) * 100 : 0,
+			byVerb,
 		};
-	}, [allRules, level, inferLevel]);
+	}, [allRules, data, level, ruleLevel, verbs]);
 
 	const mdIcon = html`<svg
     class="rule-icon"
@@ -133,6 +186,23 @@ export function CoverageView({
           >${stats.verifyPct.toFixed(1)}%</span
         >
       </div>
+      ${stats.byVerb.map(({ verb, pct }) => {
+				const verbFilter = `${VERB_FILTER_PREFIX}${verb.name}`;
+				return html`
+          <div
+            key=${verb.name}
+            class="stat clickable"
+            title=${verb.countsAs ? `Counts toward ${verb.countsAs} coverage` : ""}
+            onClick=${() => onFilterChange(filter === verbFilter ? null : verbFilter)}
+          >
+            <span class="stat-label"
+              >${verb.name} ${filter === verbFilter ? "(filtered)" : ""}</span
+            >
+            <span class="stat-value ${getStatClass(pct)}">${pct.toFixed(1)}%</span>
+          </div>
+        `;
+			})}
+      <${HistoryChart} history=${history} />
 
       <div class="custom-dropdown ${levelOpen ? "open" : ""}" id="level-dropdown">
         <div
@@ -197,6 +267,22 @@ export function CoverageView({
                       <div class="rule-id-row">
                         ${mdIcon}
                         <span class="rule-id">${rule.id}</span>
+                        ${
+													rule.status &&
+													!isActive(rule) &&
+													html`<span class=${`rule-status rule-status-${rule.status}`}>
+                          ${rule.status}
+                        </span>`
+												}
+                        ${
+													rule.verifyStatus &&
+													html`<span
+                          class=${`verify-status verify-status-${rule.verifyStatus}`}
+                          title="Outcome of the tests behind the verify references"
+                        >
+                          ${VERIFY_STATUS_LABELS[rule.verifyStatus]}
+                        </span>`
+												}
                       </div>
                       ${
 												rule.html &&
@@ -208,7 +294,9 @@ export function CoverageView({
                     </td>
                     <td class="rule-refs" onClick=${(e: Event) => e.stopPropagation()}>
                       ${
-												rule.implRefs.length > 0 || rule.verifyRefs.length > 0
+												rule.implRefs.length > 0 ||
+												rule.verifyRefs.length > 0 ||
+												rule.customRefs.length > 0
 													? html`
                             ${rule.implRefs.map(
 															(r) => html`
@@ -232,6 +320,26 @@ export function CoverageView({
                                 />
                               `,
 														)}
+                            ${verbs
+															.filter((verb) => !verb.countsAs)
+															.flatMap((verb) =>
+																customRefs(rule, verb.name).map(
+																	(r) => html`
+                                    <div
+                                      key=${`${verb.name}:${r.file}:${r.line}`}
+                                      class="ref-custom"
+                                    >
+                                      <span class="ref-verb">${verb.name}</span>
+                                      <${FileRef}
+                                        file=${r.file}
+                                        line=${r.line}
+                                        type="source"
+                                        onSelectFile=${onSelectFile}
+                                      />
+                                    </div>
+                                  `,
+																),
+															)}
                           `
 													: html`<span style="color: var(--fg-dim)">—</span>`
 											}
@@ -1,7 +1,7 @@
 import { useCallback, useEffect, useMemo, useRef, useState } from "preact/hooks";
 import { render } from "preact";
-import { EDITORS } from "../config";
-import { useSpec } from "../hooks";
+import { EDITORS, READ_ONLY } from "../config";
+import { fetchJson, useSpec } from "../hooks";
 import { CoverageArc, html, showRefsPopup } from "../main";
 import type { OutlineEntry, SpecViewProps, FileContent } from "../types";
 import { MarkdownEditor } from "../components/MarkdownEditor";
@@ -121,6 +121,7 @@ interface AggregatedCoverage {
   total: number;
   implCount: number;
   verifyCount: number;
+  staleCount: number;
 }
 
 function aggregateCoverage(node: OutlineTreeNode): AggregatedCoverage {
@@ -128,6 +129,7 @@ function aggregateCoverage(node: OutlineTreeNode): AggregatedCoverage {
   let total = node.entry.aggregated.total;
   let implCount = node.entry.aggregated.implCount;
   let verifyCount = node.entry.aggregated.verifyCount;
+  let staleCount = node.entry.aggregated.staleCount;
 
   // Add children's aggregated stats
   for (const child of node.children) {
@@ -135,9 +137,10 @@ function aggregateCoverage(node: OutlineTreeNode): AggregatedCoverage {
     total += childStats.total;
     implCount += childStats.implCount;
     verifyCount += childStats.verifyCount;
+    staleCount += childStats.staleCount;
   }
 
-  return { total, implCount, verifyCount };
+  return { total, implCount, verifyCount, staleCount };
 }
 
 // Recursive outline tree renderer
@@ -197,6 +200,12 @@ function OutlineTree({
                   title="Tests: ${coverage.verifyCount}/${coverage.total}"
                   hideNumber
                 />
+                ${coverage.staleCount > 0 &&
+                html`
+                  <span class="toc-stale" title="Stale: ${coverage.staleCount} changed since annotated">
+                    ●
+                  </span>
+                `}
               </span>
             `}
           </a>
@@ -386,8 +395,9 @@ export function SpecView({
   }, [processedContent]);
 
   // Add pencil edit buttons to paragraphs with data-source-file/data-source-line
+  // (not in a static export, whose readers don't have the sources checked out)
   useEffect(() => {
-    if (!processedContent || !contentRef.current || !config) return;
+    if (!processedContent || !contentRef.current || !config || READ_ONLY) return;
 
     const elements = contentRef.current.querySelectorAll("[data-source-file][data-source-line]");
 
@@ -787,9 +797,8 @@ export function SpecView({
           const params = new URLSearchParams({ path: file });
           if (specName) params.append("spec", specName);
           if (selectedImpl) params.append("impl", selectedImpl);
-          fetch(`/api/file?${params}`)
-            .then((res) => res.json())
-            .then((data: FileContent) => {
+          fetchJson<FileContent>(`/api/file?${params}`)
+            .then((data) => {
               console.log("Setting preview modal");
               // r[impl code-unit.nested.smallest]
               // Find the smallest code unit containing this line.
@@ -820,7 +829,7 @@ export function SpecView({
       // r[impl dashboard.editing.activation.click]
       // Handle Edit badge clicks - mount inline editor
       const editBadge = target.closest("button.req-badge.req-edit") as HTMLElement | null;
-      if (editBadge) {
+      if (editBadge && !READ_ONLY) {
         e.preventDefault();
         const sourceFile = editBadge.dataset.sourceFile;
         const byteRange = editBadge.dataset.br;
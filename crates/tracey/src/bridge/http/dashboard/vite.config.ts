@@ -3,6 +3,8 @@ import preact from "@preact/preset-vite";
 
 export default defineConfig({
   plugins: [preact()],
+  // Relative asset URLs, so a static export works under any path
+  base: "./",
   build: {
     outDir: "dist",
     emptyOutDir: true,
@@ -7,7 +7,7 @@
 //! r[impl daemon.bridge.http]
 
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock};
 use std::time::Duration;
 
 use axum::{
@@ -30,6 +30,7 @@ use tower_http::cors::{Any, CorsLayer};
 use tracing::{debug, error, info, warn};
 
 use crate::daemon::{DaemonClient, new_client};
+use crate::output::MatrixFormat;
 use tracey_api::*;
 
 /// Message sent to WebSocket clients when data changes.
@@ -122,15 +123,18 @@ pub async fn run(
         .route("/api/reverse", get(api_reverse))
         .route("/api/version", get(api_version))
         .route("/api/spec", get(api_spec))
+        .route("/api/history", get(api_history))
         .route("/api/file", get(api_file))
         .route("/api/search", get(api_search))
         .route("/api/status", get(api_status))
         .route("/api/validate", get(api_validate))
         .route("/api/uncovered", get(api_uncovered))
         .route("/api/untested", get(api_untested))
+        .route("/api/tree", get(api_tree))
         .route("/api/unmapped", get(api_unmapped))
         .route("/api/rule", get(api_rule))
         .route("/api/reload", get(api_reload))
+        .route("/api/export/matrix", get(api_export_matrix))
         .route("/api/health", get(api_health));
 
     // In dev mode, proxy to Vite; otherwise serve embedded assets
@@ -173,13 +177,22 @@ pub async fn run(
 }
 
 // Embedded dashboard assets (colocated in src/bridge/http/dashboard/)
-static INDEX_HTML: &str = include_str!("dashboard/dist/index.html");
-static INDEX_CSS: &str = include_str!("dashboard/dist/assets/index.css");
-static INDEX_JS: &str = include_str!("dashboard/dist/assets/index.js");
+pub(crate) static INDEX_HTML: &str = include_str!("dashboard/dist/index.html");
+pub(crate) static INDEX_CSS: &str = include_str!("dashboard/dist/assets/index.css");
+pub(crate) static INDEX_JS: &str = include_str!("dashboard/dist/assets/index.js");
+
+/// `INDEX_HTML` with root-absolute asset URLs, for serving the page at any
+/// route. The dashboard is built with relative asset URLs so that a static
+/// export also works when published under a subpath.
+pub(crate) fn index_html_at_root() -> &'static str {
+    static HTML: LazyLock<String> =
+        LazyLock::new(|| INDEX_HTML.replace("\"./assets/", "\"/assets/"));
+    &HTML
+}
 
 /// SPA fallback - serve index.html for all non-API routes.
 async fn spa_fallback() -> Html<&'static str> {
-    Html(INDEX_HTML)
+    Html(index_html_at_root())
 }
 
 /// Serve static assets from embedded files.
@@ -233,13 +246,14 @@ struct FileQuery {
     impl_name: Option<String>,
 }
 
-/// Query parameters for uncovered/untested endpoints.
+/// Query parameters for uncovered/untested/tree endpoints.
 #[derive(Debug, Clone, Deserialize)]
 struct CoverageQuery {
     spec: Option<String>,
     #[serde(rename = "impl")]
     impl_name: Option<String>,
     prefix: Option<String>,
+    level: Option<String>,
 }
 
 /// Query parameters for unmapped endpoint.
@@ -251,6 +265,15 @@ struct UnmappedQuery {
     path: Option<String>,
 }
 
+/// Query parameters for the matrix export endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct MatrixQuery {
+    format: Option<String>,
+    spec: Option<String>,
+    #[serde(rename = "impl")]
+    impl_name: Option<String>,
+}
+
 /// Query parameters for rule endpoint.
 #[derive(Debug, Clone, Deserialize)]
 struct RuleQuery {
@@ -408,6 +431,28 @@ async fn api_spec(State(state): State<Arc<AppState>>, Query(query): Query<SpecQu
     }
 }
 
+/// GET /api/history - Get coverage history for a spec/impl.
+///
+/// r[impl dashboard.api.history]
+async fn api_history(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ImplQuery>,
+) -> Response {
+    let client = state.client.lock().await;
+
+    let config = match rpc(client.config().await) {
+        Ok(c) => c,
+        Err(e) => return e,
+    };
+
+    let (spec, impl_name) = resolve_spec_impl(query.spec, query.impl_name, &config);
+
+    match rpc(client.history(spec, impl_name).await) {
+        Ok(history) => Json(history).into_response(),
+        Err(e) => e,
+    }
+}
+
 /// GET /api/file - Get file content with syntax highlighting.
 async fn api_file(State(state): State<Arc<AppState>>, Query(query): Query<FileQuery>) -> Response {
     let client = state.client.lock().await;
@@ -504,6 +549,7 @@ async fn api_uncovered(
         spec: Some(spec),
         impl_name: Some(impl_name),
         prefix: query.prefix,
+        level: query.level,
     };
 
     match rpc(client.uncovered(req).await) {
@@ -530,6 +576,7 @@ async fn api_untested(
         spec: Some(spec),
         impl_name: Some(impl_name),
         prefix: query.prefix,
+        level: query.level,
     };
 
     match rpc(client.untested(req).await) {
@@ -538,6 +585,35 @@ async fn api_untested(
     }
 }
 
+/// GET /api/tree - Get the requirement ID tree with rolled-up coverage.
+///
+/// r[impl dashboard.api.tree]
+async fn api_tree(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<CoverageQuery>,
+) -> Response {
+    let client = state.client.lock().await;
+
+    let config = match rpc(client.config().await) {
+        Ok(c) => c,
+        Err(e) => return e,
+    };
+
+    let (spec, impl_name) = resolve_spec_impl(query.spec, query.impl_name, &config);
+
+    let req = tracey_proto::RuleTreeRequest {
+        spec: Some(spec),
+        impl_name: Some(impl_name),
+        prefix: query.prefix,
+        level: query.level,
+    };
+
+    match rpc(client.rule_tree(req).await) {
+        Ok(data) => Json(data).into_response(),
+        Err(e) => e,
+    }
+}
+
 /// GET /api/unmapped - Get unmapped code.
 async fn api_unmapped(
     State(state): State<Arc<AppState>>,
@@ -584,6 +660,68 @@ async fn api_reload(State(state): State<Arc<AppState>>) -> Response {
     }
 }
 
+/// GET /api/export/matrix - Download the traceability matrix as CSV or SpreadsheetML.
+///
+/// r[impl dashboard.api.matrix]
+async fn api_export_matrix(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<MatrixQuery>,
+) -> Response {
+    let format: MatrixFormat = match query.format.as_deref().unwrap_or("csv").parse() {
+        Ok(f) => f,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiError {
+                    error: e.to_string(),
+                    code: "invalid_format".to_string(),
+                }),
+            )
+                .into_response();
+        }
+    };
+
+    let client = state.client.lock().await;
+    let config = match rpc(client.config().await) {
+        Ok(c) => c,
+        Err(e) => return e,
+    };
+
+    let mut suites = Vec::new();
+    for spec in &config.specs {
+        if query.spec.as_ref().is_some_and(|s| s != &spec.name) {
+            continue;
+        }
+        for impl_name in &spec.implementations {
+            if query.impl_name.as_ref().is_some_and(|i| i != impl_name) {
+                continue;
+            }
+            match rpc(client.forward(spec.name.clone(), impl_name.clone()).await) {
+                Ok(Some(forward)) => {
+                    suites.push(((spec.name.clone(), impl_name.clone()), forward));
+                }
+                Ok(None) => {}
+                Err(e) => return e,
+            }
+        }
+    }
+
+    let body = format.render(suites.iter().map(|(key, forward)| (key, forward)));
+    let disposition = format!(
+        "attachment; filename=\"tracey-matrix.{}\"",
+        format.extension()
+    );
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response()
+}
+
 // ============================================================================
 // WebSocket for live updates
 // ============================================================================
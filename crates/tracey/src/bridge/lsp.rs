@@ -24,9 +24,10 @@ fn rpc<T, E: std::fmt::Debug>(res: Result<T, roam_stream::CallError<E>>) -> Resu
 
 // Semantic token types for requirement references
 const SEMANTIC_TOKEN_TYPES: &[SemanticTokenType] = &[
-    SemanticTokenType::NAMESPACE, // 0: prefix (e.g., "r")
-    SemanticTokenType::KEYWORD,   // 1: verb (impl, verify, depends, related)
-    SemanticTokenType::VARIABLE,  // 2: requirement ID
+    SemanticTokenType::NAMESPACE,         // 0: prefix (e.g., "r")
+    SemanticTokenType::KEYWORD,           // 1: verb (impl, verify, depends, related)
+    SemanticTokenType::VARIABLE,          // 2: requirement ID
+    SemanticTokenType::new("customVerb"), // 3: verb declared in config (bench, fuzz, ...)
 ];
 
 const SEMANTIC_TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[
@@ -34,6 +35,20 @@ const SEMANTIC_TOKEN_MODIFIERS: &[SemanticTokenModifier] = &[
     SemanticTokenModifier::DECLARATION, // 1: for valid references
 ];
 
+// Commands run by `workspace/executeCommand`, used by code actions and code lenses
+const CMD_CREATE_REQUIREMENT: &str = "tracey.createRequirement";
+const CMD_SHOW_REFERENCES: &str = "tracey.showReferences";
+const CMD_OPEN_DASHBOARD: &str = "tracey.openDashboard";
+
+/// Where `tracey web` serves the dashboard by default
+const DEFAULT_DASHBOARD_URL: &str = "http://localhost:3000";
+
+/// Where the config file lives, relative to the project root
+const CONFIG_PATH: &str = ".config/tracey/config.styx";
+
+/// Most choices offered in a single `window/showMessageRequest`
+const MAX_CHOICES: usize = 8;
+
 /// Run the LSP bridge over stdio.
 ///
 /// This function starts an LSP server that connects to the tracey daemon
@@ -67,6 +82,9 @@ async fn run_lsp_server(project_root: PathBuf) -> Result<()> {
             daemon_client,
             files_with_diagnostics: std::collections::HashSet::new(),
             project_root: project_root.clone(),
+            pull_diagnostics: false,
+            diagnostic_refresh: false,
+            dashboard_url: DEFAULT_DASHBOARD_URL.to_string(),
         }),
     });
     Server::new(stdin, stdout, socket).serve(service).await;
@@ -89,6 +107,13 @@ struct LspState {
     files_with_diagnostics: std::collections::HashSet<String>,
     /// Project root path
     project_root: PathBuf,
+    /// The client pulls diagnostics (`textDocument/diagnostic`), so they
+    /// aren't pushed
+    pull_diagnostics: bool,
+    /// The client accepts `workspace/diagnostic/refresh` requests
+    diagnostic_refresh: bool,
+    /// Base URL of the dashboard, from the `dashboardUrl` initialization option
+    dashboard_url: String,
 }
 
 impl LspState {
@@ -114,6 +139,29 @@ impl Backend {
         self.state.lock().await
     }
 
+    /// Push diagnostics for a document, unless the client pulls them.
+    async fn push_diagnostics(&self, uri: Url) {
+        if !self.state().await.pull_diagnostics {
+            self.publish_diagnostics(uri).await;
+        }
+    }
+
+    /// Push diagnostics for the workspace, or ask a client that pulls them
+    /// to pull again.
+    ///
+    /// r[impl lsp.diagnostics.push-fallback]
+    async fn push_workspace_diagnostics(&self) {
+        let (pull, refresh) = {
+            let state = self.state().await;
+            (state.pull_diagnostics, state.diagnostic_refresh)
+        };
+        if !pull {
+            self.publish_workspace_diagnostics().await;
+        } else if refresh {
+            let _ = self.client.workspace_diagnostic_refresh().await;
+        }
+    }
+
     /// Get path and content for a document, for daemon calls.
     async fn get_path_and_content(&self, uri: &Url) -> Option<(String, String)> {
         let state = self.state().await;
@@ -146,28 +194,7 @@ impl Backend {
         // Convert daemon diagnostics to LSP diagnostics
         let diagnostics: Vec<Diagnostic> = daemon_diagnostics
             .into_iter()
-            .map(|d| Diagnostic {
-                range: Range {
-                    start: Position {
-                        line: d.start_line,
-                        character: d.start_char,
-                    },
-                    end: Position {
-                        line: d.end_line,
-                        character: d.end_char,
-                    },
-                },
-                severity: Some(match d.severity.as_str() {
-                    "error" => DiagnosticSeverity::ERROR,
-                    "warning" => DiagnosticSeverity::WARNING,
-                    "info" => DiagnosticSeverity::INFORMATION,
-                    _ => DiagnosticSeverity::HINT,
-                }),
-                code: Some(NumberOrString::String(d.code)),
-                source: Some("tracey".into()),
-                message: d.message,
-                ..Default::default()
-            })
+            .map(to_lsp_diagnostic)
             .collect();
 
         // Track files with non-empty diagnostics for clearing later
@@ -257,28 +284,11 @@ impl Backend {
         // Lock is now released
 
         // Publish config error diagnostic on config file
-        let config_path = project_root.join(".config/tracey/config.styx");
+        let config_path = project_root.join(CONFIG_PATH);
         if let Ok(uri) = Url::from_file_path(&config_path) {
             if let Some(error_msg) = config_error {
-                let diagnostic = Diagnostic {
-                    range: Range {
-                        start: Position {
-                            line: 0,
-                            character: 0,
-                        },
-                        end: Position {
-                            line: 0,
-                            character: 0,
-                        },
-                    },
-                    severity: Some(DiagnosticSeverity::ERROR),
-                    code: Some(NumberOrString::String("config-error".into())),
-                    source: Some("tracey".into()),
-                    message: error_msg,
-                    ..Default::default()
-                };
                 self.client
-                    .publish_diagnostics(uri, vec![diagnostic], None)
+                    .publish_diagnostics(uri, vec![config_error_diagnostic(error_msg)], None)
                     .await;
             } else {
                 // Clear config diagnostics if no error
@@ -305,42 +315,429 @@ impl Backend {
             let diagnostics: Vec<Diagnostic> = file_diag
                 .diagnostics
                 .into_iter()
-                .map(|d| Diagnostic {
+                .map(to_lsp_diagnostic)
+                .collect();
+
+            self.client
+                .publish_diagnostics(uri, diagnostics, None)
+                .await;
+        }
+    }
+
+    /// Ask the user to pick one of `items`, showing at most [`MAX_CHOICES`].
+    /// A single item is picked without asking.
+    async fn choose<T>(
+        &self,
+        message: String,
+        mut items: Vec<T>,
+        label: impl Fn(&T) -> String,
+    ) -> Option<T> {
+        items.truncate(MAX_CHOICES);
+        if items.len() <= 1 {
+            return items.pop();
+        }
+        let labels: Vec<String> = items.iter().map(label).collect();
+        let actions = labels
+            .iter()
+            .map(|title| MessageActionItem {
+                title: title.clone(),
+                properties: HashMap::new(),
+            })
+            .collect();
+        let chosen = self
+            .client
+            .show_message_request(MessageType::INFO, message, Some(actions))
+            .await
+            .ok()??;
+        let idx = labels.iter().position(|l| *l == chosen.title)?;
+        Some(items.swap_remove(idx))
+    }
+
+    /// Insert a stub definition for `req_id` into the spec file and section
+    /// the user picks.
+    ///
+    /// r[impl lsp.commands.create-requirement]
+    async fn create_requirement(&self, req_id: String) {
+        let (stubs, project_root) = {
+            let state = self.state().await;
+            let stubs = state
+                .daemon_client
+                .lsp_requirement_stubs(req_id.clone())
+                .await;
+            (rpc(stubs), state.project_root.clone())
+        };
+        let Ok(stubs) = stubs else {
+            return;
+        };
+        if stubs.is_empty() {
+            self.client
+                .show_message(
+                    MessageType::WARNING,
+                    format!("No spec section to define '{}' in", req_id),
+                )
+                .await;
+            return;
+        }
+
+        let message = format!("Where should '{}' be defined?", req_id);
+        let label = |s: &RequirementStub| match &s.section_title {
+            Some(title) => format!("{} › {}", s.edit.path, title),
+            None => s.edit.path.clone(),
+        };
+        let Some(stub) = self.choose(message, stubs, label).await else {
+            return;
+        };
+
+        let Ok(uri) = Url::from_file_path(project_root.join(&stub.edit.path)) else {
+            return;
+        };
+        let position = Position {
+            line: stub.edit.start_line,
+            character: stub.edit.start_char,
+        };
+        let edit = WorkspaceEdit {
+            changes: Some(HashMap::from([(
+                uri.clone(),
+                vec![TextEdit {
                     range: Range {
-                        start: Position {
-                            line: d.start_line,
-                            character: d.start_char,
-                        },
-                        end: Position {
-                            line: d.end_line,
-                            character: d.end_char,
-                        },
+                        start: position,
+                        end: position,
                     },
-                    severity: Some(match d.severity.as_str() {
-                        "error" => DiagnosticSeverity::ERROR,
-                        "warning" => DiagnosticSeverity::WARNING,
-                        "info" => DiagnosticSeverity::INFORMATION,
-                        _ => DiagnosticSeverity::HINT,
+                    new_text: stub.edit.new_text,
+                }],
+            )])),
+            ..Default::default()
+        };
+        let applied = self
+            .client
+            .apply_edit(edit)
+            .await
+            .is_ok_and(|response| response.applied);
+        if applied {
+            let _ = self
+                .client
+                .show_document(ShowDocumentParams {
+                    uri,
+                    external: Some(false),
+                    take_focus: Some(true),
+                    selection: Some(Range {
+                        start: position,
+                        end: position,
                     }),
-                    code: Some(NumberOrString::String(d.code)),
-                    source: Some("tracey".into()),
-                    message: d.message,
-                    ..Default::default()
                 })
-                .collect();
+                .await;
+        }
+    }
 
+    /// Let the user pick one of the references to `req_id` and open it.
+    /// Also returns every reference, for clients that show them themselves.
+    ///
+    /// r[impl lsp.commands.show-references]
+    async fn show_references(&self, req_id: String) -> Option<serde_json::Value> {
+        let (info, project_root) = {
+            let state = self.state().await;
+            let info = rpc(state.daemon_client.rule(req_id.clone()).await);
+            (info, state.project_root.clone())
+        };
+        let info = info.ok()??;
+
+        let mut refs: Vec<(&str, ApiCodeRef)> = Vec::new();
+        for coverage in info.coverage {
+            let impl_refs = coverage.impl_refs.into_iter().map(|r| ("impl", r));
+            let verify_refs = coverage.verify_refs.into_iter().map(|r| ("verify", r));
+            for (kind, r) in impl_refs.chain(verify_refs) {
+                if !refs
+                    .iter()
+                    .any(|(_, seen)| seen.file == r.file && seen.line == r.line)
+                {
+                    refs.push((kind, r));
+                }
+            }
+        }
+        let choices: Vec<(String, Location)> = refs
+            .iter()
+            .filter_map(|(kind, r)| {
+                let uri = Url::from_file_path(project_root.join(&r.file)).ok()?;
+                let position = Position {
+                    line: r.line.saturating_sub(1) as u32,
+                    character: 0,
+                };
+                let location = Location {
+                    uri,
+                    range: Range {
+                        start: position,
+                        end: position,
+                    },
+                };
+                Some((format!("{} {}:{}", kind, r.file, r.line), location))
+            })
+            .collect();
+        if choices.is_empty() {
             self.client
-                .publish_diagnostics(uri, diagnostics, None)
+                .show_message(MessageType::INFO, format!("No references to '{}'", req_id))
+                .await;
+            return None;
+        }
+        let locations: Vec<Location> = choices.iter().map(|(_, l)| l.clone()).collect();
+
+        let message = format!("References to '{}'", req_id);
+        if let Some((_, location)) = self
+            .choose(message, choices, |(label, _)| label.clone())
+            .await
+        {
+            let _ = self
+                .client
+                .show_document(ShowDocumentParams {
+                    uri: location.uri,
+                    external: Some(false),
+                    take_focus: Some(true),
+                    selection: Some(location.range),
+                })
+                .await;
+        }
+
+        serde_json::to_value(locations).ok()
+    }
+
+    /// Open `req_id` in the dashboard, in the browser.
+    ///
+    /// r[impl lsp.commands.open-dashboard]
+    async fn open_dashboard(&self, req_id: String) {
+        let (info, dashboard_url) = {
+            let state = self.state().await;
+            (
+                rpc(state.daemon_client.rule(req_id.clone()).await),
+                state.dashboard_url.clone(),
+            )
+        };
+        let Some(coverage) = info
+            .ok()
+            .flatten()
+            .and_then(|i| i.coverage.into_iter().next())
+        else {
+            return;
+        };
+
+        let url = format!(
+            "{}/{}/{}/spec#r--{}",
+            dashboard_url, coverage.spec, coverage.impl_name, req_id
+        );
+        let Ok(uri) = Url::parse(&url) else {
+            return;
+        };
+        let opened = self
+            .client
+            .show_document(ShowDocumentParams {
+                uri,
+                external: Some(true),
+                take_focus: Some(true),
+                selection: None,
+            })
+            .await
+            .unwrap_or(false);
+        if !opened {
+            // Not every client can open URLs; show it so it can be copied
+            self.client
+                .show_message(
+                    MessageType::INFO,
+                    format!("Open {} (start the dashboard with `tracey web`)", url),
+                )
                 .await;
         }
     }
 }
 
+/// Convert a daemon diagnostic to an LSP one
+fn to_lsp_diagnostic(d: LspDiagnostic) -> Diagnostic {
+    Diagnostic {
+        range: Range {
+            start: Position {
+                line: d.start_line,
+                character: d.start_char,
+            },
+            end: Position {
+                line: d.end_line,
+                character: d.end_char,
+            },
+        },
+        severity: Some(match d.severity.as_str() {
+            "error" => DiagnosticSeverity::ERROR,
+            "warning" => DiagnosticSeverity::WARNING,
+            "info" => DiagnosticSeverity::INFORMATION,
+            _ => DiagnosticSeverity::HINT,
+        }),
+        code: Some(NumberOrString::String(d.code)),
+        source: Some("tracey".into()),
+        message: d.message,
+        ..Default::default()
+    }
+}
+
+/// The diagnostic shown at the top of a config file that doesn't parse
+fn config_error_diagnostic(message: String) -> Diagnostic {
+    Diagnostic {
+        range: Range::default(),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(NumberOrString::String("config-error".into())),
+        source: Some("tracey".into()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Result ID for pulled diagnostics: the engine version, which changes
+/// whenever the dashboard data does, plus a hash of the other input the
+/// diagnostics depend on (document content, config error)
+fn diagnostics_result_id(version: u64, input: &str) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    input.hash(&mut hasher);
+    format!("{}-{:016x}", version, hasher.finish())
+}
+
+/// Prefix of the result IDs `workspace/diagnostic` hands out. Document pulls
+/// hash the document content into theirs, so the two never compare equal.
+const WORKSPACE_RESULT_ID_PREFIX: &str = "workspace-";
+
+/// Report for a document pull: unchanged when the client's previous result
+/// still stands, otherwise the diagnostics from `items`, which is only
+/// called then. `items` gives `None` when they couldn't be computed.
+async fn document_report<F>(
+    version: u64,
+    content: &str,
+    previous_result_id: Option<&str>,
+    items: impl FnOnce() -> F,
+) -> DocumentDiagnosticReportResult
+where
+    F: std::future::Future<Output = Option<Vec<Diagnostic>>>,
+{
+    let result_id = diagnostics_result_id(version, content);
+    if previous_result_id == Some(result_id.as_str()) {
+        return DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Unchanged(
+            RelatedUnchangedDocumentDiagnosticReport {
+                related_documents: None,
+                unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                    result_id,
+                },
+            },
+        ));
+    }
+
+    let Some(items) = items().await else {
+        return empty_document_report();
+    };
+    full_document_report(Some(result_id), items)
+}
+
+/// Full report without a result ID, for when the daemon can't be asked, so
+/// the next pull computes diagnostics again
+fn empty_document_report() -> DocumentDiagnosticReportResult {
+    full_document_report(None, Vec::new())
+}
+
+/// Full report carrying `items`
+fn full_document_report(
+    result_id: Option<String>,
+    items: Vec<Diagnostic>,
+) -> DocumentDiagnosticReportResult {
+    DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(
+        RelatedFullDocumentDiagnosticReport {
+            related_documents: None,
+            full_document_diagnostic_report: FullDocumentDiagnosticReport { result_id, items },
+        },
+    ))
+}
+
+/// Reports for a workspace pull when nothing moved since the last one, or
+/// `None` when a full report is needed. Only the result IDs this handler
+/// produced are compared; those from document pulls say nothing about it.
+fn unchanged_workspace_reports(
+    result_id: &str,
+    previous: &HashMap<Url, String>,
+) -> Option<Vec<WorkspaceDocumentDiagnosticReport>> {
+    let ours: Vec<(&Url, &String)> = previous
+        .iter()
+        .filter(|(_, id)| id.starts_with(WORKSPACE_RESULT_ID_PREFIX))
+        .collect();
+    if ours.is_empty() || ours.iter().any(|(_, id)| *id != result_id) {
+        return None;
+    }
+    Some(
+        ours.into_iter()
+            .map(|(uri, _)| {
+                WorkspaceDocumentDiagnosticReport::Unchanged(
+                    WorkspaceUnchangedDocumentDiagnosticReport {
+                        uri: uri.clone(),
+                        version: None,
+                        unchanged_document_diagnostic_report: UnchangedDocumentDiagnosticReport {
+                            result_id: result_id.to_string(),
+                        },
+                    },
+                )
+            })
+            .collect(),
+    )
+}
+
+/// A call hierarchy item for a requirement, located at its definition
+fn call_hierarchy_item(
+    project_root: &std::path::Path,
+    symbol: LspSymbol,
+) -> Option<CallHierarchyItem> {
+    let uri = Url::from_file_path(project_root.join(symbol.path.as_deref()?)).ok()?;
+    let range = Range {
+        start: Position {
+            line: symbol.start_line,
+            character: symbol.start_char,
+        },
+        end: Position {
+            line: symbol.end_line,
+            character: symbol.end_char,
+        },
+    };
+    Some(CallHierarchyItem {
+        name: symbol.name,
+        kind: SymbolKind::CONSTANT,
+        tags: None,
+        detail: symbol.container,
+        uri,
+        range,
+        selection_range: range,
+        data: None,
+    })
+}
+
 #[tower_lsp::async_trait]
 impl LanguageServer for Backend {
     /// r[impl lsp.lifecycle.initialize]
     /// r[impl lsp.completions.trigger]
-    async fn initialize(&self, _: InitializeParams) -> LspResult<InitializeResult> {
+    async fn initialize(&self, params: InitializeParams) -> LspResult<InitializeResult> {
+        {
+            let mut state = self.state().await;
+            state.pull_diagnostics = params
+                .capabilities
+                .text_document
+                .as_ref()
+                .is_some_and(|t| t.diagnostic.is_some());
+            state.diagnostic_refresh = params
+                .capabilities
+                .workspace
+                .as_ref()
+                .and_then(|w| w.diagnostic.as_ref())
+                .and_then(|d| d.refresh_support)
+                .unwrap_or(false);
+            // r[impl lsp.config.dashboard-url]
+            if let Some(url) = params
+                .initialization_options
+                .as_ref()
+                .and_then(|o| o.get("dashboardUrl"))
+                .and_then(|u| u.as_str())
+            {
+                state.dashboard_url = url.trim_end_matches('/').to_string();
+            }
+        }
+
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 text_document_sync: Some(TextDocumentSyncCapability::Kind(
@@ -358,10 +755,30 @@ impl LanguageServer for Backend {
                 document_symbol_provider: Some(OneOf::Left(true)),
                 workspace_symbol_provider: Some(OneOf::Left(true)),
                 code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+                execute_command_provider: Some(ExecuteCommandOptions {
+                    commands: [
+                        CMD_CREATE_REQUIREMENT,
+                        CMD_SHOW_REFERENCES,
+                        CMD_OPEN_DASHBOARD,
+                    ]
+                    .map(String::from)
+                    .to_vec(),
+                    work_done_progress_options: Default::default(),
+                }),
                 code_lens_provider: Some(CodeLensOptions {
                     resolve_provider: Some(false),
                 }),
                 inlay_hint_provider: Some(OneOf::Left(true)),
+                call_hierarchy_provider: Some(CallHierarchyServerCapability::Simple(true)),
+                diagnostic_provider: Some(DiagnosticServerCapabilities::Options(
+                    DiagnosticOptions {
+                        identifier: Some("tracey".to_string()),
+                        // Covering a requirement in one file changes another's
+                        inter_file_dependencies: true,
+                        workspace_diagnostics: true,
+                        work_done_progress_options: Default::default(),
+                    },
+                )),
                 rename_provider: Some(OneOf::Right(RenameOptions {
                     prepare_provider: Some(true),
                     work_done_progress_options: Default::default(),
@@ -390,7 +807,7 @@ impl LanguageServer for Backend {
             .await;
 
         // Publish workspace-wide diagnostics for all files on startup
-        self.publish_workspace_diagnostics().await;
+        self.push_workspace_diagnostics().await;
     }
 
     async fn shutdown(&self) -> LspResult<()> {
@@ -402,7 +819,7 @@ impl LanguageServer for Backend {
         let content = params.text_document.text.clone();
         self.state().await.document_opened(&uri, content.clone());
         self.notify_vfs_open(&uri, &content).await;
-        self.publish_diagnostics(uri).await;
+        self.push_diagnostics(uri).await;
     }
 
     /// r[impl lsp.diagnostics.on-change]
@@ -412,18 +829,18 @@ impl LanguageServer for Backend {
             let content = change.text.clone();
             self.state().await.document_changed(&uri, content.clone());
             self.notify_vfs_change(&uri, &content).await;
-            self.publish_diagnostics(uri).await;
+            self.push_diagnostics(uri).await;
         }
     }
 
     /// r[impl lsp.diagnostics.on-save]
     async fn did_save(&self, params: DidSaveTextDocumentParams) {
         let uri = params.text_document.uri.clone();
-        self.publish_diagnostics(uri).await;
+        self.push_diagnostics(uri).await;
 
         // Also refresh workspace-wide diagnostics, since saving one file
         // can affect diagnostics in other files (e.g., covering a requirement)
-        self.publish_workspace_diagnostics().await;
+        self.push_workspace_diagnostics().await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
@@ -435,6 +852,114 @@ impl LanguageServer for Backend {
         // call will update diagnostics based on current file state on disk.
     }
 
+    /// r[impl lsp.diagnostics.pull]
+    async fn diagnostic(
+        &self,
+        params: DocumentDiagnosticParams,
+    ) -> LspResult<DocumentDiagnosticReportResult> {
+        let Some((path, content)) = self.get_path_and_content(&params.text_document.uri).await
+        else {
+            return Ok(empty_document_report());
+        };
+
+        let state = self.state().await;
+        let Ok(version) = rpc(state.daemon_client.version().await) else {
+            return Ok(empty_document_report());
+        };
+        let req = LspDocumentRequest {
+            path,
+            content: content.clone(),
+        };
+        let report = document_report(
+            version,
+            &content,
+            params.previous_result_id.as_deref(),
+            || async {
+                let diagnostics = rpc(state.daemon_client.lsp_diagnostics(req).await).ok()?;
+                Some(diagnostics.into_iter().map(to_lsp_diagnostic).collect())
+            },
+        )
+        .await;
+        Ok(report)
+    }
+
+    /// r[impl lsp.diagnostics.pull]
+    async fn workspace_diagnostic(
+        &self,
+        params: WorkspaceDiagnosticParams,
+    ) -> LspResult<WorkspaceDiagnosticReportResult> {
+        let report = |items: Vec<WorkspaceDocumentDiagnosticReport>| {
+            WorkspaceDiagnosticReportResult::Report(WorkspaceDiagnosticReport { items })
+        };
+
+        let state = self.state().await;
+        let project_root = state.project_root.clone();
+        let Ok(version) = rpc(state.daemon_client.version().await) else {
+            return Ok(report(Vec::new()));
+        };
+        let config_error = rpc(state.daemon_client.health().await)
+            .ok()
+            .and_then(|h| h.config_error);
+        let result_id = format!(
+            "{}{}",
+            WORKSPACE_RESULT_ID_PREFIX,
+            diagnostics_result_id(version, config_error.as_deref().unwrap_or(""))
+        );
+
+        // Nothing moved since the last pull: every report still stands
+        let previous: HashMap<Url, String> = params
+            .previous_result_ids
+            .into_iter()
+            .map(|p| (p.uri, p.value))
+            .collect();
+        if let Some(items) = unchanged_workspace_reports(&result_id, &previous) {
+            return Ok(report(items));
+        }
+
+        let Ok(all_diagnostics) = rpc(state.daemon_client.lsp_workspace_diagnostics().await) else {
+            return Ok(report(Vec::new()));
+        };
+        drop(state);
+
+        let mut by_uri: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
+        for file_diag in all_diagnostics {
+            let Ok(uri) = Url::from_file_path(project_root.join(&file_diag.path)) else {
+                continue;
+            };
+            by_uri
+                .entry(uri)
+                .or_default()
+                .extend(file_diag.diagnostics.into_iter().map(to_lsp_diagnostic));
+        }
+        if let Some(error_msg) = config_error
+            && let Ok(uri) = Url::from_file_path(project_root.join(CONFIG_PATH))
+        {
+            by_uri
+                .entry(uri)
+                .or_default()
+                .push(config_error_diagnostic(error_msg));
+        }
+        // Files reported on before that are clean now get an empty report
+        for uri in previous.into_keys() {
+            by_uri.entry(uri).or_default();
+        }
+
+        let items = by_uri
+            .into_iter()
+            .map(|(uri, items)| {
+                WorkspaceDocumentDiagnosticReport::Full(WorkspaceFullDocumentDiagnosticReport {
+                    uri,
+                    version: None,
+                    full_document_diagnostic_report: FullDocumentDiagnosticReport {
+                        result_id: Some(result_id.clone()),
+                        items,
+                    },
+                })
+            })
+            .collect();
+        Ok(report(items))
+    }
+
     /// r[impl lsp.completions.verb]
     /// r[impl lsp.completions.req-id]
     /// r[impl lsp.completions.req-id-fuzzy]
@@ -507,7 +1032,25 @@ impl LanguageServer for Backend {
         let project_root = self.state().await.project_root.clone();
 
         // Format hover with spec info
-        let mut markdown = format!("## {}\n\n{}", info.rule_id, info.raw);
+        let mut markdown = if info.matched_rules.is_empty() {
+            format!("## {}\n\n{}", info.rule_id, info.raw)
+        } else {
+            // r[impl lsp.hover.wildcard]
+            let count = info.matched_rules.len();
+            let mut markdown = format!(
+                "## {}\n\nMatches {} requirement{}:",
+                info.rule_id,
+                count,
+                if count == 1 { "" } else { "s" }
+            );
+            for m in &info.matched_rules {
+                markdown.push_str(&format!(
+                    "\n- `{}` ({} impl, {} verify)",
+                    m.rule_id, m.impl_count, m.verify_count
+                ));
+            }
+            markdown
+        };
         markdown.push_str(&format!("\n\n**Spec:** {}", info.spec_name));
         if let Some(url) = &info.spec_url {
             markdown.push_str(&format!(" ([source]({}))", url));
@@ -541,7 +1084,8 @@ impl LanguageServer for Backend {
         }
 
         // Summary counts
-        if info.impl_refs.is_empty() && info.verify_refs.is_empty() {
+        if info.impl_refs.is_empty() && info.verify_refs.is_empty() && info.matched_rules.is_empty()
+        {
             markdown.push_str("\n\n*No implementations or verifications*");
         }
 
@@ -713,6 +1257,84 @@ impl LanguageServer for Backend {
         Ok(Some(lsp_locations))
     }
 
+    /// r[impl lsp.call-hierarchy.prepare]
+    async fn prepare_call_hierarchy(
+        &self,
+        params: CallHierarchyPrepareParams,
+    ) -> LspResult<Option<Vec<CallHierarchyItem>>> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let Some((path, content)) = self.get_path_and_content(uri).await else {
+            return Ok(None);
+        };
+
+        let state = self.state().await;
+        let project_root = state.project_root.clone();
+        let req = LspPositionRequest {
+            path,
+            content,
+            line: position.line,
+            character: position.character,
+        };
+
+        let Ok(symbols) = rpc(state.daemon_client.lsp_prepare_call_hierarchy(req).await) else {
+            return Ok(None);
+        };
+
+        let items: Vec<CallHierarchyItem> = symbols
+            .into_iter()
+            .filter_map(|s| call_hierarchy_item(&project_root, s))
+            .collect();
+        Ok((!items.is_empty()).then_some(items))
+    }
+
+    /// r[impl lsp.call-hierarchy.incoming]
+    async fn incoming_calls(
+        &self,
+        params: CallHierarchyIncomingCallsParams,
+    ) -> LspResult<Option<Vec<CallHierarchyIncomingCall>>> {
+        let state = self.state().await;
+        let project_root = state.project_root.clone();
+
+        let Ok(symbols) = rpc(state.daemon_client.lsp_dependents(params.item.name).await) else {
+            return Ok(None);
+        };
+
+        let calls = symbols
+            .into_iter()
+            .filter_map(|s| call_hierarchy_item(&project_root, s))
+            .map(|from| CallHierarchyIncomingCall {
+                from,
+                from_ranges: Vec::new(),
+            })
+            .collect();
+        Ok(Some(calls))
+    }
+
+    /// r[impl lsp.call-hierarchy.outgoing]
+    async fn outgoing_calls(
+        &self,
+        params: CallHierarchyOutgoingCallsParams,
+    ) -> LspResult<Option<Vec<CallHierarchyOutgoingCall>>> {
+        let state = self.state().await;
+        let project_root = state.project_root.clone();
+
+        let Ok(symbols) = rpc(state.daemon_client.lsp_dependencies(params.item.name).await) else {
+            return Ok(None);
+        };
+
+        let calls = symbols
+            .into_iter()
+            .filter_map(|s| call_hierarchy_item(&project_root, s))
+            .map(|to| CallHierarchyOutgoingCall {
+                to,
+                from_ranges: Vec::new(),
+            })
+            .collect();
+        Ok(Some(calls))
+    }
+
     async fn document_highlight(
         &self,
         params: DocumentHighlightParams,
@@ -834,11 +1456,17 @@ impl LanguageServer for Backend {
             .into_iter()
             .filter_map(|s| {
                 // Try to construct a URI for the symbol
-                let uri = Url::from_file_path(project_root.join("docs/spec")).ok()?;
+                let path = s.path.as_deref().unwrap_or("docs/spec");
+                let uri = Url::from_file_path(project_root.join(path)).ok()?;
+                let kind = if s.kind == "prefix" {
+                    SymbolKind::NAMESPACE
+                } else {
+                    SymbolKind::CONSTANT
+                };
                 #[allow(deprecated)]
                 Some(SymbolInformation {
                     name: s.name,
-                    kind: SymbolKind::CONSTANT,
+                    kind,
                     tags: None,
                     deprecated: None,
                     location: Location {
@@ -854,7 +1482,7 @@ impl LanguageServer for Backend {
                             },
                         },
                     },
-                    container_name: Some(s.kind),
+                    container_name: s.container.or(Some(s.kind)),
                 })
             })
             .collect();
@@ -886,23 +1514,46 @@ impl LanguageServer for Backend {
             return Ok(None);
         }
 
+        let project_root = state.project_root.clone();
         let lsp_actions: Vec<CodeActionOrCommand> = actions
             .into_iter()
             .map(|a| {
+                let edit = a.edit.and_then(|edit| {
+                    let uri = Url::from_file_path(project_root.join(&edit.path)).ok()?;
+                    let text_edit = TextEdit {
+                        range: Range {
+                            start: Position {
+                                line: edit.start_line,
+                                character: edit.start_char,
+                            },
+                            end: Position {
+                                line: edit.end_line,
+                                character: edit.end_char,
+                            },
+                        },
+                        new_text: edit.new_text,
+                    };
+                    Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri, vec![text_edit])])),
+                        ..Default::default()
+                    })
+                });
+                let command = (!a.command.is_empty()).then(|| Command {
+                    title: String::new(),
+                    command: a.command,
+                    arguments: Some(
+                        a.arguments
+                            .into_iter()
+                            .map(serde_json::Value::String)
+                            .collect(),
+                    ),
+                });
                 CodeActionOrCommand::CodeAction(CodeAction {
                     title: a.title,
                     kind: Some(a.kind.into()),
                     is_preferred: Some(a.is_preferred),
-                    command: Some(Command {
-                        title: String::new(),
-                        command: a.command,
-                        arguments: Some(
-                            a.arguments
-                                .into_iter()
-                                .map(serde_json::Value::String)
-                                .collect(),
-                        ),
-                    }),
+                    edit,
+                    command,
                     ..Default::default()
                 })
             })
@@ -911,6 +1562,37 @@ impl LanguageServer for Backend {
         Ok(Some(lsp_actions))
     }
 
+    /// r[impl lsp.commands.execute]
+    async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> LspResult<Option<serde_json::Value>> {
+        // Every command takes the requirement ID as its first argument
+        let Some(req_id) = params
+            .arguments
+            .first()
+            .and_then(|a| a.as_str())
+            .map(String::from)
+        else {
+            return Err(tower_lsp::jsonrpc::Error::invalid_params(
+                "Expected a requirement ID",
+            ));
+        };
+
+        match params.command.as_str() {
+            CMD_CREATE_REQUIREMENT => self.create_requirement(req_id).await,
+            CMD_SHOW_REFERENCES => return Ok(self.show_references(req_id).await),
+            CMD_OPEN_DASHBOARD => self.open_dashboard(req_id).await,
+            _ => {
+                return Err(tower_lsp::jsonrpc::Error::invalid_params(format!(
+                    "Unknown command: {}",
+                    params.command
+                )));
+            }
+        }
+        Ok(None)
+    }
+
     async fn code_lens(&self, params: CodeLensParams) -> LspResult<Option<Vec<CodeLens>>> {
         let uri = &params.text_document.uri;
 
@@ -1147,3 +1829,95 @@ impl LanguageServer for Backend {
         })))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_unchanged(report: &DocumentDiagnosticReportResult) -> bool {
+        matches!(
+            report,
+            DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Unchanged(_))
+        )
+    }
+
+    fn full_result_id(report: &DocumentDiagnosticReportResult) -> Option<String> {
+        match report {
+            DocumentDiagnosticReportResult::Report(DocumentDiagnosticReport::Full(full)) => {
+                full.full_document_diagnostic_report.result_id.clone()
+            }
+            _ => None,
+        }
+    }
+
+    // r[verify lsp.diagnostics.pull]
+    #[tokio::test]
+    async fn test_document_report_unchanged_until_version_or_content_moves() {
+        let content = "/// r[impl auth.login]\nfn login() {}";
+        let first = document_report(1, content, None, || async { Some(Vec::new()) }).await;
+        let result_id = full_result_id(&first).expect("full report carries a result ID");
+
+        let mut recomputed = false;
+        let again = document_report(1, content, Some(&result_id), || {
+            recomputed = true;
+            async { Some(Vec::new()) }
+        })
+        .await;
+        assert!(is_unchanged(&again));
+        assert!(!recomputed, "an unchanged document isn't diagnosed again");
+
+        let newer =
+            document_report(2, content, Some(&result_id), || async { Some(Vec::new()) }).await;
+        assert!(!is_unchanged(&newer), "a new data version invalidates it");
+
+        let edited = document_report(1, "fn login() {}", Some(&result_id), || async {
+            Some(Vec::new())
+        })
+        .await;
+        assert!(
+            !is_unchanged(&edited),
+            "editing the document invalidates it"
+        );
+
+        let failed = document_report(1, content, None, || async { None }).await;
+        assert_eq!(full_result_id(&failed), None, "failures aren't cached");
+    }
+
+    // r[verify lsp.diagnostics.pull]
+    #[test]
+    fn test_workspace_report_ignores_document_pull_ids() {
+        let result_id = format!(
+            "{}{}",
+            WORKSPACE_RESULT_ID_PREFIX,
+            diagnostics_result_id(3, "")
+        );
+        let lib = Url::parse("file:///project/src/lib.rs").unwrap();
+        let open = Url::parse("file:///project/src/open.rs").unwrap();
+
+        // A document pull's ID for an open file doesn't force a full report
+        let previous = HashMap::from([
+            (lib.clone(), result_id.clone()),
+            (open.clone(), diagnostics_result_id(3, "fn open() {}")),
+        ]);
+        let unchanged = unchanged_workspace_reports(&result_id, &previous)
+            .expect("nothing moved since the last workspace pull");
+        assert_eq!(unchanged.len(), 1);
+        assert!(matches!(
+            &unchanged[0],
+            WorkspaceDocumentDiagnosticReport::Unchanged(r) if r.uri == lib
+        ));
+
+        // An older workspace ID does
+        let stale = format!(
+            "{}{}",
+            WORKSPACE_RESULT_ID_PREFIX,
+            diagnostics_result_id(2, "")
+        );
+        let previous = HashMap::from([(lib, stale), (open.clone(), result_id.clone())]);
+        assert!(unchanged_workspace_reports(&result_id, &previous).is_none());
+
+        // So does having no workspace IDs at all
+        let previous = HashMap::from([(open, diagnostics_result_id(3, "fn open() {}"))]);
+        assert!(unchanged_workspace_reports(&result_id, &previous).is_none());
+    }
+}
@@ -32,6 +32,15 @@ fn rpc<T, E: std::fmt::Debug>(res: Result<T, roam_stream::CallError<E>>) -> Resu
     res.map_err(|e| format!("RPC error: {:?}", e))
 }
 
+/// Percentage of `total`, or 0 when there's nothing to count
+fn percent(count: usize, total: usize) -> f64 {
+    if total > 0 {
+        count as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
 /// Format config error as a warning banner to prepend to responses
 fn format_config_error_banner(error: &str) -> String {
     format!(
@@ -55,7 +64,7 @@ pub struct StatusTool {}
 /// Get rules without implementation references
 #[mcp_tool(
     name = "tracey_uncovered",
-    description = "List rules that have no implementation references ([impl ...] comments). Optionally filter by spec/impl or rule ID prefix."
+    description = "List rules that have no implementation references ([impl ...] comments). Optionally filter by spec/impl, rule ID prefix, or requirement level (must, should, may)."
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct UncoveredTool {
@@ -63,12 +72,15 @@ pub struct UncoveredTool {
     pub spec_impl: Option<String>,
     #[serde(default)]
     pub prefix: Option<String>,
+    /// Only list rules at this level: must, should or may
+    #[serde(default)]
+    pub level: Option<String>,
 }
 
 /// Get rules without verification references
 #[mcp_tool(
     name = "tracey_untested",
-    description = "List rules that have implementation but no verification references ([verify ...] comments). These rules are implemented but not tested."
+    description = "List rules that have implementation but no verification references ([verify ...] comments). These rules are implemented but not tested. Optionally filter by requirement level (must, should, may)."
 )]
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 pub struct UntestedTool {
@@ -76,6 +88,26 @@ pub struct UntestedTool {
     pub spec_impl: Option<String>,
     #[serde(default)]
     pub prefix: Option<String>,
+    /// Only list rules at this level: must, should or may
+    #[serde(default)]
+    pub level: Option<String>,
+}
+
+/// Get the requirement ID tree with coverage per prefix
+#[mcp_tool(
+    name = "tracey_tree",
+    description = "Show requirements as a tree of dotted ID prefixes (channel > channel.id > channel.id.allocation), with implementation and verification counts rolled up at each prefix. Pass a prefix to zoom into a subtree, or a level (must, should, may) to only count those requirements."
+)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct TreeTool {
+    #[serde(default)]
+    pub spec_impl: Option<String>,
+    /// Only show the subtree under this ID prefix
+    #[serde(default)]
+    pub prefix: Option<String>,
+    /// Only count rules at this level: must, should or may
+    #[serde(default)]
+    pub level: Option<String>,
 }
 
 /// Get code units without rule references
@@ -91,6 +123,19 @@ pub struct UnmappedTool {
     pub path: Option<String>,
 }
 
+/// Get requirements and code changed since a git ref
+#[mcp_tool(
+    name = "tracey_diff",
+    description = "Scope coverage to a branch: list requirements added or edited in spec markdown since a git ref (e.g. \"origin/main\"), and changed code units that have no requirement references."
+)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema)]
+pub struct DiffTool {
+    #[serde(default)]
+    pub spec_impl: Option<String>,
+    /// Git ref to compare the working tree against
+    pub since: String,
+}
+
 /// Get details about a specific rule
 #[mcp_tool(
     name = "tracey_rule",
@@ -170,7 +215,9 @@ tool_box!(
         StatusTool,
         UncoveredTool,
         UntestedTool,
+        TreeTool,
         UnmappedTool,
+        DiffTool,
         RuleTool,
         ConfigTool,
         ReloadTool,
@@ -207,16 +254,8 @@ impl TraceyHandler {
             Ok(status) => {
                 let mut output = String::new();
                 for impl_status in &status.impls {
-                    let impl_pct = if impl_status.total_rules > 0 {
-                        impl_status.covered_rules as f64 / impl_status.total_rules as f64 * 100.0
-                    } else {
-                        0.0
-                    };
-                    let verify_pct = if impl_status.total_rules > 0 {
-                        impl_status.verified_rules as f64 / impl_status.total_rules as f64 * 100.0
-                    } else {
-                        0.0
-                    };
+                    let impl_pct = percent(impl_status.covered_rules, impl_status.total_rules);
+                    let verify_pct = percent(impl_status.verified_rules, impl_status.total_rules);
                     output.push_str(&format!(
                         "{}/{}: impl {:.0}%, verify {:.0}% ({}/{} rules)\n",
                         impl_status.spec,
@@ -226,6 +265,17 @@ impl TraceyHandler {
                         impl_status.covered_rules,
                         impl_status.total_rules
                     ));
+                    // r[impl level.breakdown]
+                    for level in &impl_status.by_level {
+                        output.push_str(&format!(
+                            "  {}: impl {:.0}%, verify {:.0}% ({}/{} rules)\n",
+                            level.level.to_uppercase(),
+                            percent(level.covered_rules, level.total_rules),
+                            percent(level.verified_rules, level.total_rules),
+                            level.covered_rules,
+                            level.total_rules
+                        ));
+                    }
                 }
                 if output.is_empty() {
                     "No specs configured".to_string()
@@ -243,7 +293,17 @@ impl TraceyHandler {
 
     /// r[impl mcp.tool.uncovered]
     /// r[impl mcp.response.hints]
-    async fn handle_uncovered(&self, spec_impl: Option<&str>, prefix: Option<&str>) -> String {
+    /// r[impl level.filter]
+    async fn handle_uncovered(
+        &self,
+        spec_impl: Option<&str>,
+        prefix: Option<&str>,
+        level: Option<&str>,
+    ) -> String {
+        let level = match level.map(crate::level::parse).transpose() {
+            Ok(level) => level,
+            Err(e) => return format!("Error: {}", e),
+        };
         let client = self.client.lock().await;
         let (spec, impl_name) = parse_spec_impl(spec_impl);
 
@@ -251,6 +311,7 @@ impl TraceyHandler {
             spec,
             impl_name,
             prefix: prefix.map(String::from),
+            level: level.map(String::from),
         };
 
         match rpc(client.uncovered(req).await) {
@@ -263,6 +324,10 @@ impl TraceyHandler {
                     response.total_rules
                 );
 
+                format_rollup(&mut output, &response.rollup, "implemented", |c| {
+                    c.impl_count
+                });
+
                 for section in &response.by_section {
                     if !section.rules.is_empty() {
                         output.push_str(&format!("## {}\n", section.section));
@@ -273,9 +338,12 @@ impl TraceyHandler {
                     }
                 }
 
+                format_stale(&mut output, &response.stale);
+
                 output.push_str("---\n");
                 output.push_str("→ Use tracey_rule to see details about a specific rule\n");
                 output.push_str("→ Use prefix parameter to filter by rule ID prefix\n");
+                output.push_str("→ Use level parameter to filter by requirement level\n");
 
                 output
             }
@@ -285,7 +353,17 @@ impl TraceyHandler {
 
     /// r[impl mcp.tool.untested]
     /// r[impl mcp.response.hints]
-    async fn handle_untested(&self, spec_impl: Option<&str>, prefix: Option<&str>) -> String {
+    /// r[impl level.filter]
+    async fn handle_untested(
+        &self,
+        spec_impl: Option<&str>,
+        prefix: Option<&str>,
+        level: Option<&str>,
+    ) -> String {
+        let level = match level.map(crate::level::parse).transpose() {
+            Ok(level) => level,
+            Err(e) => return format!("Error: {}", e),
+        };
         let client = self.client.lock().await;
         let (spec, impl_name) = parse_spec_impl(spec_impl);
 
@@ -293,6 +371,7 @@ impl TraceyHandler {
             spec,
             impl_name,
             prefix: prefix.map(String::from),
+            level: level.map(String::from),
         };
 
         match rpc(client.untested(req).await) {
@@ -305,6 +384,10 @@ impl TraceyHandler {
                     response.total_rules
                 );
 
+                format_rollup(&mut output, &response.rollup, "verified", |c| {
+                    c.verify_count
+                });
+
                 for section in &response.by_section {
                     if !section.rules.is_empty() {
                         output.push_str(&format!("## {}\n", section.section));
@@ -315,9 +398,83 @@ impl TraceyHandler {
                     }
                 }
 
+                format_stale(&mut output, &response.stale);
+
                 output.push_str("---\n");
                 output.push_str("→ Use tracey_rule to see details about a specific rule\n");
                 output.push_str("→ Use prefix parameter to filter by rule ID prefix\n");
+                output.push_str("→ Use level parameter to filter by requirement level\n");
+
+                output
+            }
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    /// r[impl mcp.tool.tree]
+    /// r[impl mcp.response.hints]
+    async fn handle_tree(
+        &self,
+        spec_impl: Option<&str>,
+        prefix: Option<&str>,
+        level: Option<&str>,
+    ) -> String {
+        let level = match level.map(crate::level::parse).transpose() {
+            Ok(level) => level,
+            Err(e) => return format!("Error: {}", e),
+        };
+        let client = self.client.lock().await;
+        let (spec, impl_name) = parse_spec_impl(spec_impl);
+
+        let req = RuleTreeRequest {
+            spec,
+            impl_name,
+            prefix: prefix.map(String::from),
+            level: level.map(String::from),
+        };
+
+        match rpc(client.rule_tree(req).await) {
+            Ok(response) => {
+                let mut output = format!("{}/{}", response.spec, response.impl_name);
+                if let Some(prefix) = prefix {
+                    output.push_str(&format!(" under '{}'", prefix));
+                }
+                output.push_str(":\n\n");
+
+                if response.entries.is_empty() {
+                    output.push_str("No matching requirements\n");
+                }
+
+                // Indent relative to the shallowest entry, which is where a
+                // prefix filter lands
+                let min_depth = response.entries.iter().map(|e| e.depth).min().unwrap_or(1);
+                for entry in &response.entries {
+                    let depth = entry.depth - min_depth;
+                    let label = if depth == 0 {
+                        &entry.prefix
+                    } else {
+                        &entry.segment
+                    };
+                    let cov = &entry.aggregated;
+                    let pct = percent(cov.impl_count, cov.total);
+                    output.push_str(&format!(
+                        "{}{:<30} {:>3.0}% {} impl {}/{}, verify {}/{}\n",
+                        "  ".repeat(depth),
+                        label,
+                        pct,
+                        coverage_bar(pct),
+                        cov.impl_count,
+                        cov.total,
+                        cov.verify_count,
+                        cov.total
+                    ));
+                }
+
+                output.push_str("\n---\n");
+                output.push_str("→ Use prefix parameter to zoom into a subtree\n");
+                output.push_str(
+                    "→ Use tracey_uncovered with the same prefix to list uncovered rules\n",
+                );
 
                 output
             }
@@ -537,6 +694,70 @@ impl TraceyHandler {
         output
     }
 
+    /// r[impl mcp.tool.diff]
+    /// r[impl mcp.response.hints]
+    async fn handle_diff(&self, spec_impl: Option<&str>, since: &str) -> String {
+        let client = self.client.lock().await;
+        let (spec, impl_name) = parse_spec_impl(spec_impl);
+
+        let req = DiffScopeRequest {
+            spec,
+            impl_name,
+            since: since.to_string(),
+        };
+
+        match rpc(client.diff_scope(req).await) {
+            Ok(response) => {
+                let mut output = format!(
+                    "{}/{} since {}: {}\n\n",
+                    response.spec,
+                    response.impl_name,
+                    response.since,
+                    if response.passing {
+                        "passing"
+                    } else {
+                        "failing"
+                    }
+                );
+
+                output.push_str("## Changed requirements\n");
+                if response.changed_rules.is_empty() {
+                    output.push_str("  (none)\n");
+                }
+                for rule in &response.changed_rules {
+                    let change = if rule.added { "added" } else { "edited" };
+                    let coverage = if rule.covered { "" } else { ", no impl" };
+                    output.push_str(&format!("  - {} ({}{})\n", rule.id, change, coverage));
+                }
+                output.push('\n');
+
+                output.push_str("## Unannotated code touched by the diff\n");
+                if response.unannotated_units.is_empty() {
+                    output.push_str("  (none)\n");
+                }
+                for unit in &response.unannotated_units {
+                    output.push_str(&format!(
+                        "  - {}:{}-{} {} {}{}\n",
+                        unit.path,
+                        unit.start_line,
+                        unit.end_line,
+                        unit.kind,
+                        unit.name.as_deref().unwrap_or("<anonymous>"),
+                        if unit.added { " (new)" } else { "" }
+                    ));
+                }
+                output.push('\n');
+
+                output.push_str("---\n");
+                output.push_str("→ Use tracey_rule to see details about a specific rule\n");
+                output.push_str("→ Add [impl ...] comments to new functions in annotated files\n");
+
+                output
+            }
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
     async fn handle_config_exclude(&self, spec_impl: Option<&str>, pattern: &str) -> String {
         let client = self.client.lock().await;
         let (spec, impl_name) = parse_spec_impl(spec_impl);
@@ -599,18 +820,34 @@ impl ServerHandler for TraceyHandler {
             "tracey_uncovered" => {
                 let spec_impl = args.get("spec_impl").and_then(|v| v.as_str());
                 let prefix = args.get("prefix").and_then(|v| v.as_str());
-                self.handle_uncovered(spec_impl, prefix).await
+                let level = args.get("level").and_then(|v| v.as_str());
+                self.handle_uncovered(spec_impl, prefix, level).await
             }
             "tracey_untested" => {
                 let spec_impl = args.get("spec_impl").and_then(|v| v.as_str());
                 let prefix = args.get("prefix").and_then(|v| v.as_str());
-                self.handle_untested(spec_impl, prefix).await
+                let level = args.get("level").and_then(|v| v.as_str());
+                self.handle_untested(spec_impl, prefix, level).await
+            }
+            "tracey_tree" => {
+                let spec_impl = args.get("spec_impl").and_then(|v| v.as_str());
+                let prefix = args.get("prefix").and_then(|v| v.as_str());
+                let level = args.get("level").and_then(|v| v.as_str());
+                self.handle_tree(spec_impl, prefix, level).await
             }
             "tracey_unmapped" => {
                 let spec_impl = args.get("spec_impl").and_then(|v| v.as_str());
                 let path = args.get("path").and_then(|v| v.as_str());
                 self.handle_unmapped(spec_impl, path).await
             }
+            "tracey_diff" => {
+                let spec_impl = args.get("spec_impl").and_then(|v| v.as_str());
+                let since = args.get("since").and_then(|v| v.as_str());
+                match since {
+                    Some(since) => self.handle_diff(spec_impl, since).await,
+                    None => "Error: since is required".to_string(),
+                }
+            }
             "tracey_rule" => {
                 let rule_id = args.get("rule_id").and_then(|v| v.as_str());
                 match rule_id {
@@ -674,6 +911,57 @@ fn parse_spec_impl(spec_impl: Option<&str>) -> (Option<String>, Option<String>)
     }
 }
 
+/// Append stale rules (annotated against an older version) as their own section.
+///
+/// r[impl ref.version.stale]
+fn format_stale(output: &mut String, stale: &[StaleRuleRef]) {
+    if stale.is_empty() {
+        return;
+    }
+    output.push_str("## Stale (requirement changed since it was annotated)\n");
+    for rule in stale {
+        output.push_str(&format!("  - {} (now +{})", rule.id, rule.version));
+        for stale_ref in &rule.stale_refs {
+            output.push_str(&format!(" {}:{}", stale_ref.file, stale_ref.line));
+        }
+        output.push('\n');
+    }
+    output.push('\n');
+}
+
+/// Format the per-subtree roll-up returned for a prefix query.
+///
+/// r[impl tree.rollup.query]
+fn format_rollup(
+    output: &mut String,
+    rollup: &[ApiRuleTreeEntry],
+    what: &str,
+    count: impl Fn(&OutlineCoverage) -> usize,
+) {
+    if rollup.is_empty() {
+        return;
+    }
+    output.push_str("## Roll-up by ID prefix\n");
+    for entry in rollup {
+        let covered = count(&entry.aggregated);
+        output.push_str(&format!(
+            "  {}: {}/{} {} ({:.0}%)\n",
+            entry.prefix,
+            covered,
+            entry.aggregated.total,
+            what,
+            percent(covered, entry.aggregated.total)
+        ));
+    }
+    output.push('\n');
+}
+
+/// Ten-character progress bar for a percentage
+fn coverage_bar(pct: f64) -> String {
+    let filled = ((pct / 10.0).round() as usize).min(10);
+    "█".repeat(filled) + &"░".repeat(10 - filled)
+}
+
 /// Format a validation result for display.
 fn format_validation_result(result: &tracey_proto::ValidationResult) -> String {
     if result.errors.is_empty() {
@@ -682,9 +970,14 @@ fn format_validation_result(result: &tracey_proto::ValidationResult) -> String {
             result.spec, result.impl_name
         )
     } else {
+        let mark = if result.error_count == 0 {
+            "⚠"
+        } else {
+            "✗"
+        };
         let mut output = format!(
-            "✗ {}/{}: {} error(s) found\n",
-            result.spec, result.impl_name, result.error_count
+            "{} {}/{}: {} error(s), {} warning(s) found\n",
+            mark, result.spec, result.impl_name, result.error_count, result.warning_count
         );
 
         for error in &result.errors {
@@ -748,7 +1041,7 @@ pub async fn run(root: Option<PathBuf>, _config_path: PathBuf) -> Result<()> {
         protocol_version: LATEST_PROTOCOL_VERSION.into(),
         instructions: Some(
             "Tracey is a spec coverage tool. Use the MCP tools to query coverage status, \
-             uncovered rules, untested rules, unmapped code, and rule details."
+             uncovered rules, untested rules, the requirement ID tree, unmapped code, and rule details."
                 .into(),
         ),
         meta: None,
@@ -0,0 +1,633 @@
+//! Extraction cache shared across rebuilds.
+//!
+//! r[impl daemon.state.extraction-cache]
+//!
+//! The daemon rebuilds `DashboardData` on every watcher batch and VFS change.
+//! Most of that work (tree-sitter parsing of source files, marq rendering of
+//! spec markdown, indexing files for search) only depends on file content, so
+//! [`BuildCache`] keeps those results keyed by path and content hash and hands
+//! them back on the next rebuild. The search index is kept too, and only the
+//! files whose content changed are re-indexed.
+//!
+//! r[impl daemon.state.persistent-cache]
+//!
+//! The daemon also saves the per-file results, the requirements parsed from
+//! each spec file and the rendered specs to `.tracey/cache.json` so that a
+//! cold start after an idle shutdown only re-parses files that changed in the
+//! meantime.
+
+use eyre::{Result, WrapErr};
+use facet::Facet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use tracey_core::code_units::CodeUnit;
+use tracey_core::{CustomVerb, ExtractOptions, Reqs};
+use tracing::{debug, info};
+
+use crate::ExtractedRule;
+use crate::data::{ApiSpecData, ImplKey, simple_hash};
+use crate::search::{self, RuleEntry, SearchIndex};
+
+/// Bump whenever the on-disk format or the extraction logic changes, so that
+/// caches written by other tracey versions are discarded.
+pub const CACHE_SCHEMA_VERSION: u32 = 4;
+
+/// File name of the persisted cache inside the `.tracey` directory.
+pub const CACHE_FILE_NAME: &str = "cache.json";
+
+/// Hit/miss counters for the last build.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Files whose cached results were reused
+    pub hits: usize,
+    /// Files that had to be parsed or rendered
+    pub misses: usize,
+}
+
+/// Per-file parse results for a source file.
+#[derive(Default)]
+struct CachedSource {
+    hash: u64,
+    reqs: Option<Reqs>,
+    code_units: Option<Vec<CodeUnit>>,
+}
+
+/// The search index, with what it currently holds.
+struct CachedSearch {
+    index: Arc<dyn SearchIndex>,
+    /// Indexed source file path -> content hash
+    files: HashMap<PathBuf, u64>,
+    /// Hash of the indexed rules
+    rules: u64,
+}
+
+/// Content-addressed results from previous builds.
+///
+/// Entries that are not used during a build (see [`BuildCache::begin`] and
+/// [`BuildCache::finish`]) are evicted, so deleted files don't accumulate.
+#[derive(Default)]
+pub struct BuildCache {
+    /// Source file path -> references and code units
+    sources: HashMap<PathBuf, CachedSource>,
+    /// Spec markdown path -> requirements defined in it
+    spec_files: HashMap<PathBuf, (u64, Vec<ExtractedRule>)>,
+    /// Rendered spec per implementation (`None` if it has no content), keyed
+    /// by a hash of its inputs
+    rendered: HashMap<ImplKey, (u64, Option<ApiSpecData>)>,
+    /// Search index, updated in place as files change
+    search: Option<CachedSearch>,
+    /// Options the cached references were extracted with
+    extract_options: ExtractOptions,
+    touched_sources: HashSet<PathBuf>,
+    touched_spec_files: HashSet<PathBuf>,
+    touched_rendered: HashSet<ImplKey>,
+    stats: CacheStats,
+}
+
+impl BuildCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a build: reset the stats and the set of used entries.
+    pub fn begin(&mut self) {
+        self.touched_sources.clear();
+        self.touched_spec_files.clear();
+        self.touched_rendered.clear();
+        self.stats = CacheStats::default();
+    }
+
+    /// Finish a build: evict every entry that wasn't used since [`Self::begin`].
+    pub fn finish(&mut self) {
+        self.sources
+            .retain(|path, _| self.touched_sources.contains(path));
+        self.spec_files
+            .retain(|path, _| self.touched_spec_files.contains(path));
+        self.rendered
+            .retain(|key, _| self.touched_rendered.contains(key));
+    }
+
+    /// Set the options references are extracted with, dropping the cached
+    /// references if they changed.
+    pub fn set_extract_options(&mut self, options: &ExtractOptions) {
+        if self.extract_options != *options {
+            for source in self.sources.values_mut() {
+                source.reqs = None;
+            }
+            self.extract_options = options.clone();
+        }
+    }
+
+    /// Reset the stats without starting a build, for patching the previous
+    /// build's data (see [`crate::data::patch_dashboard_data`]).
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+
+    /// Whether `path` was read as a source file by a previous build.
+    pub fn has_source(&self, path: &Path) -> bool {
+        self.sources.contains_key(path)
+    }
+
+    /// Stats for the current (or last) build.
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Number of cached source files.
+    pub fn source_count(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Look up the entry for `path`, resetting it if the content changed.
+    fn source_entry(&mut self, path: &Path, content: &str) -> &mut CachedSource {
+        let hash = simple_hash(content);
+        self.touched_sources.insert(path.to_path_buf());
+        let entry = self.sources.entry(path.to_path_buf()).or_default();
+        if entry.hash != hash {
+            *entry = CachedSource {
+                hash,
+                ..Default::default()
+            };
+        }
+        entry
+    }
+
+    /// Requirement references in a source file, parsed only if its content changed.
+    pub fn source_reqs(&mut self, path: &Path, content: &str) -> Reqs {
+        let entry = self.source_entry(path, content);
+        if let Some(reqs) = entry.reqs.clone() {
+            self.stats.hits += 1;
+            return reqs;
+        }
+        let reqs = Reqs::extract_from_content_with(path, content, &self.extract_options);
+        if let Some(entry) = self.sources.get_mut(path) {
+            entry.reqs = Some(reqs.clone());
+        }
+        self.stats.misses += 1;
+        reqs
+    }
+
+    /// Code units in a source file, parsed only if its content changed.
+    pub fn source_code_units(&mut self, path: &Path, content: &str) -> Vec<CodeUnit> {
+        let entry = self.source_entry(path, content);
+        if let Some(units) = entry.code_units.clone() {
+            self.stats.hits += 1;
+            return units;
+        }
+        let units = tracey_core::code_units::extract(path, content).units;
+        entry.code_units = Some(units.clone());
+        self.stats.misses += 1;
+        units
+    }
+
+    /// Requirements previously extracted from a spec file with this content hash.
+    pub(crate) fn spec_file_rules(&mut self, path: &Path, hash: u64) -> Option<Vec<ExtractedRule>> {
+        self.touched_spec_files.insert(path.to_path_buf());
+        match self.spec_files.get(path) {
+            Some((cached, rules)) if *cached == hash => {
+                self.stats.hits += 1;
+                Some(rules.clone())
+            }
+            _ => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn insert_spec_file_rules(
+        &mut self,
+        path: &Path,
+        hash: u64,
+        rules: Vec<ExtractedRule>,
+    ) {
+        self.spec_files.insert(path.to_path_buf(), (hash, rules));
+    }
+
+    /// Rendered spec for an implementation, if its inputs hash to `hash`.
+    pub(crate) fn rendered_spec(
+        &mut self,
+        key: &ImplKey,
+        hash: u64,
+    ) -> Option<Option<ApiSpecData>> {
+        self.touched_rendered.insert(key.clone());
+        match self.rendered.get(key) {
+            Some((cached, data)) if *cached == hash => {
+                self.stats.hits += 1;
+                Some(data.clone())
+            }
+            _ => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn insert_rendered_spec(
+        &mut self,
+        key: &ImplKey,
+        hash: u64,
+        data: Option<ApiSpecData>,
+    ) {
+        self.rendered.insert(key.clone(), (hash, data));
+    }
+
+    /// Search index over `files` and `rules`.
+    ///
+    /// The index from the previous build is updated in place: only files
+    /// whose content changed are re-indexed, and rules only if any changed.
+    pub(crate) fn search_index(
+        &mut self,
+        project_root: &Path,
+        files: &BTreeMap<PathBuf, String>,
+        rules: &[RuleEntry],
+    ) -> Arc<dyn SearchIndex> {
+        let mut rules_key = String::new();
+        for rule in rules {
+            rules_key.push_str(&format!("{}:{:x}\n", rule.id, simple_hash(&rule.raw)));
+        }
+        let rules_hash = simple_hash(&rules_key);
+
+        let Some(search) = &mut self.search else {
+            let index: Arc<dyn SearchIndex> =
+                Arc::from(search::build_index(project_root, files, rules));
+            self.search = Some(CachedSearch {
+                index: Arc::clone(&index),
+                files: files
+                    .iter()
+                    .map(|(path, content)| (path.clone(), simple_hash(content)))
+                    .collect(),
+                rules: rules_hash,
+            });
+            return index;
+        };
+
+        let removed: Vec<PathBuf> = search
+            .files
+            .keys()
+            .filter(|path| !files.contains_key(*path))
+            .cloned()
+            .collect();
+        search.files.retain(|path, _| files.contains_key(path));
+        let mut changed: Vec<(&Path, Option<&str>)> =
+            removed.iter().map(|path| (path.as_path(), None)).collect();
+        for (path, content) in files {
+            let hash = simple_hash(content);
+            if search.files.insert(path.clone(), hash) != Some(hash) {
+                changed.push((path.as_path(), Some(content.as_str())));
+            }
+        }
+        let rules_changed = search.rules != rules_hash;
+        if !changed.is_empty() || rules_changed {
+            search
+                .index
+                .update(project_root, &changed, rules_changed.then_some(rules));
+            search.rules = rules_hash;
+        }
+        Arc::clone(&search.index)
+    }
+
+    /// Re-index individual source files in the search index, if there is one.
+    pub(crate) fn update_search_files(&mut self, project_root: &Path, files: &[(&Path, &str)]) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        let mut changed = Vec::new();
+        for &(path, content) in files {
+            let hash = simple_hash(content);
+            if search.files.insert(path.to_path_buf(), hash) != Some(hash) {
+                changed.push((path, Some(content)));
+            }
+        }
+        if !changed.is_empty() {
+            search.index.update(project_root, &changed, None);
+        }
+    }
+}
+
+/// On-disk form of a [`BuildCache`].
+#[derive(Facet)]
+struct DiskCache {
+    schema: u32,
+    tracey_version: String,
+    /// `ExtractOptions::json_keys` the cached references were extracted with
+    json_keys: Vec<String>,
+    /// `ExtractOptions::custom_verbs` the cached references were extracted with
+    custom_verbs: Vec<CustomVerb>,
+    sources: Vec<DiskSource>,
+    spec_files: Vec<DiskSpecFile>,
+    rendered: Vec<DiskRendered>,
+}
+
+#[derive(Facet)]
+struct DiskSource {
+    path: PathBuf,
+    /// Modification time in nanoseconds since the Unix epoch
+    mtime: u64,
+    size: u64,
+    hash: u64,
+    reqs: Option<Reqs>,
+    code_units: Option<Vec<CodeUnit>>,
+}
+
+#[derive(Facet)]
+struct DiskSpecFile {
+    path: PathBuf,
+    /// Modification time in nanoseconds since the Unix epoch
+    mtime: u64,
+    size: u64,
+    hash: u64,
+    rules: Vec<ExtractedRule>,
+}
+
+#[derive(Facet)]
+struct DiskRendered {
+    spec: String,
+    impl_name: String,
+    hash: u64,
+    data: Option<ApiSpecData>,
+}
+
+/// Modification time (ns since the Unix epoch) and size of a file on disk.
+fn file_stamp(path: &Path) -> Option<(u64, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    let mtime = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos() as u64;
+    Some((mtime, metadata.len()))
+}
+
+impl BuildCache {
+    /// Load a cache saved by [`Self::save`].
+    ///
+    /// A missing, unreadable or outdated cache file yields an empty cache:
+    /// the cache only ever saves work, so it never fails a build. Source
+    /// entries whose file changed size or modification time are dropped.
+    pub fn load(path: &Path) -> Self {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                debug!("No extraction cache at {}: {}", path.display(), e);
+                return Self::new();
+            }
+        };
+        let disk: DiskCache = match facet_json::from_str(&content) {
+            Ok(disk) => disk,
+            Err(e) => {
+                info!("Ignoring unreadable cache {}: {}", path.display(), e);
+                return Self::new();
+            }
+        };
+        if disk.schema != CACHE_SCHEMA_VERSION || disk.tracey_version != env!("CARGO_PKG_VERSION") {
+            info!(
+                "Ignoring cache {} from another tracey version",
+                path.display()
+            );
+            return Self::new();
+        }
+
+        let mut cache = Self::new();
+        cache.extract_options.json_keys = disk.json_keys;
+        cache.extract_options.custom_verbs = disk.custom_verbs;
+        for source in disk.sources {
+            if file_stamp(&source.path) != Some((source.mtime, source.size)) {
+                continue;
+            }
+            cache.sources.insert(
+                source.path,
+                CachedSource {
+                    hash: source.hash,
+                    reqs: source.reqs,
+                    code_units: source.code_units,
+                },
+            );
+        }
+        for spec_file in disk.spec_files {
+            if file_stamp(&spec_file.path) != Some((spec_file.mtime, spec_file.size)) {
+                continue;
+            }
+            cache
+                .spec_files
+                .insert(spec_file.path, (spec_file.hash, spec_file.rules));
+        }
+        for rendered in disk.rendered {
+            cache.rendered.insert(
+                (rendered.spec, rendered.impl_name),
+                (rendered.hash, rendered.data),
+            );
+        }
+        info!(
+            "Loaded extraction cache with {} file(s) and {} spec file(s) from {}",
+            cache.sources.len(),
+            cache.spec_files.len(),
+            path.display()
+        );
+        cache
+    }
+
+    /// Save the cache so the next daemon start can skip unchanged files.
+    ///
+    /// The file is written to a temporary path and renamed into place, so a
+    /// crash mid-write never leaves a truncated cache behind.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let sources = self
+            .sources
+            .iter()
+            .filter_map(|(path, source)| {
+                let (mtime, size) = file_stamp(path)?;
+                Some(DiskSource {
+                    path: path.clone(),
+                    mtime,
+                    size,
+                    hash: source.hash,
+                    reqs: source.reqs.clone(),
+                    code_units: source.code_units.clone(),
+                })
+            })
+            .collect();
+        let spec_files = self
+            .spec_files
+            .iter()
+            .filter_map(|(path, (hash, rules))| {
+                let (mtime, size) = file_stamp(path)?;
+                Some(DiskSpecFile {
+                    path: path.clone(),
+                    mtime,
+                    size,
+                    hash: *hash,
+                    rules: rules.clone(),
+                })
+            })
+            .collect();
+        let rendered = self
+            .rendered
+            .iter()
+            .map(|((spec, impl_name), (hash, data))| DiskRendered {
+                spec: spec.clone(),
+                impl_name: impl_name.clone(),
+                hash: *hash,
+                data: data.clone(),
+            })
+            .collect();
+        let disk = DiskCache {
+            schema: CACHE_SCHEMA_VERSION,
+            tracey_version: env!("CARGO_PKG_VERSION").to_string(),
+            json_keys: self.extract_options.json_keys.clone(),
+            custom_verbs: self.extract_options.custom_verbs.clone(),
+            sources,
+            spec_files,
+            rendered,
+        };
+
+        let json = facet_json::to_string(&disk)
+            .map_err(|e| eyre::eyre!("Failed to serialize cache: {}", e))?;
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)
+            .wrap_err_with(|| format!("Failed to write {}", tmp.display()))?;
+        std::fs::rename(&tmp, path)
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_reqs_reparsed_only_on_change() {
+        let mut cache = BuildCache::new();
+        let path = Path::new("src/lib.rs");
+
+        cache.begin();
+        let reqs = cache.source_reqs(path, "// r[impl auth.login]\nfn login() {}\n");
+        assert_eq!(reqs.len(), 1);
+        assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 1 });
+
+        cache.begin();
+        let reqs = cache.source_reqs(path, "// r[impl auth.login]\nfn login() {}\n");
+        assert_eq!(reqs.len(), 1);
+        assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 0 });
+
+        cache.begin();
+        let reqs = cache.source_reqs(path, "// r[verify auth.login]\nfn login() {}\n");
+        assert_eq!(reqs.references[0].verb, tracey_core::RefVerb::Verify);
+        assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 1 });
+    }
+
+    #[test]
+    fn test_source_reqs_reparsed_when_extract_options_change() {
+        let mut cache = BuildCache::new();
+        let path = Path::new("settings.json");
+        let content = r#"{"requirements": "r[impl config.retries]"}"#;
+
+        cache.begin();
+        assert!(cache.source_reqs(path, content).is_empty());
+
+        cache.begin();
+        cache.set_extract_options(&ExtractOptions {
+            json_keys: vec!["requirements".to_string()],
+            ..Default::default()
+        });
+        let reqs = cache.source_reqs(path, content);
+        assert_eq!(reqs.references[0].req_id, "config.retries");
+        assert_eq!(cache.stats(), CacheStats { hits: 0, misses: 1 });
+    }
+
+    #[test]
+    fn test_finish_evicts_unused_entries() {
+        let mut cache = BuildCache::new();
+
+        cache.begin();
+        cache.source_reqs(Path::new("a.rs"), "// r[impl a]");
+        cache.source_reqs(Path::new("b.rs"), "// r[impl b]");
+        cache.finish();
+        assert_eq!(cache.source_count(), 2);
+
+        // b.rs was deleted
+        cache.begin();
+        cache.source_reqs(Path::new("a.rs"), "// r[impl a]");
+        cache.finish();
+        assert_eq!(cache.source_count(), 1);
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("lib.rs");
+        let content = "// r[impl auth.login]\nfn login() {}\n";
+        std::fs::write(&source, content).unwrap();
+        let cache_path = dir.path().join(CACHE_FILE_NAME);
+
+        let mut cache = BuildCache::new();
+        cache.begin();
+        cache.source_reqs(&source, content);
+        cache.save(&cache_path).unwrap();
+
+        let mut loaded = BuildCache::load(&cache_path);
+        assert_eq!(loaded.source_count(), 1);
+        loaded.begin();
+        let reqs = loaded.source_reqs(&source, content);
+        assert_eq!(reqs.references[0].req_id, "auth.login");
+        assert_eq!(loaded.stats(), CacheStats { hits: 1, misses: 0 });
+    }
+
+    #[tokio::test]
+    async fn test_save_and_load_keeps_spec_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let spec = dir.path().join("spec.md");
+        let content = "# Auth\n\nr[auth.login]\nUsers MUST log in.\n";
+        std::fs::write(&spec, content).unwrap();
+        let cache_path = dir.path().join(CACHE_FILE_NAME);
+        let hash = simple_hash(content);
+
+        let rules = crate::extract_rules_from_markdown(&spec, content)
+            .await
+            .unwrap();
+        let mut cache = BuildCache::new();
+        cache.begin();
+        cache.insert_spec_file_rules(&spec, hash, rules);
+        cache.save(&cache_path).unwrap();
+
+        let mut loaded = BuildCache::load(&cache_path);
+        loaded.begin();
+        let rules = loaded
+            .spec_file_rules(&spec, hash)
+            .expect("spec rules should survive a restart");
+        assert_eq!(rules[0].def.id, "auth.login");
+        assert_eq!(loaded.stats(), CacheStats { hits: 1, misses: 0 });
+    }
+
+    #[test]
+    fn test_load_drops_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("lib.rs");
+        std::fs::write(&source, "// r[impl a]").unwrap();
+        let cache_path = dir.path().join(CACHE_FILE_NAME);
+
+        let mut cache = BuildCache::new();
+        cache.source_reqs(&source, "// r[impl a]");
+        cache.save(&cache_path).unwrap();
+
+        std::fs::write(&source, "// r[impl a]\n// r[impl b]").unwrap();
+        assert_eq!(BuildCache::load(&cache_path).source_count(), 0);
+    }
+
+    #[test]
+    fn test_load_ignores_garbage() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache_path = dir.path().join(CACHE_FILE_NAME);
+        std::fs::write(&cache_path, "not json").unwrap();
+        assert_eq!(BuildCache::load(&cache_path).source_count(), 0);
+        assert_eq!(
+            BuildCache::load(&dir.path().join("missing.json")).source_count(),
+            0
+        );
+    }
+}
@@ -0,0 +1,371 @@
+//! Daemon-free validation and coverage check for CI.
+//!
+//! r[impl cli.check]
+//! r[impl cli.check.no-daemon]
+//!
+//! Builds the dashboard data once in-process, runs the same validation as the
+//! daemon's `validate` RPC, and enforces per-impl coverage thresholds. Nothing
+//! is written to disk: no socket, no `.tracey` directory, no logs.
+
+use eyre::{Result, bail};
+use owo_colors::OwoColorize;
+use std::path::{Path, PathBuf};
+use tracey_api::ValidationResult;
+use tracey_proto::DiffScopeResponse;
+
+use crate::config::Config;
+use crate::data::{DashboardData, ImplKey, build_dashboard_data};
+use crate::diff::{GitDiff, diff_scope, is_unannotated_addition, is_uncovered_addition};
+use crate::level;
+use crate::lock::{DriftedRule, Lock};
+
+/// Options for `tracey check`
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// Only check this spec (default: all specs)
+    pub spec: Option<String>,
+    /// Only check this implementation (default: all impls)
+    pub impl_name: Option<String>,
+    /// Coverage threshold overriding any per-impl `threshold` from the config
+    pub threshold: Option<f64>,
+    /// Lock file to check requirement text against (see `tracey lock`)
+    pub lock_path: Option<PathBuf>,
+    /// Also check what changed since this git ref (see `tracey check --since`)
+    pub since: Option<String>,
+    /// Requirement levels to leave out of the headline percentage, on top of
+    /// each impl's `exclude_levels`
+    pub exclude_levels: Vec<String>,
+}
+
+/// Check result for a single spec/impl pair
+#[derive(Debug, Clone)]
+pub struct ImplCheck {
+    pub spec: String,
+    pub impl_name: String,
+    /// Validation errors, as reported by the daemon's `validate`
+    pub validation: ValidationResult,
+    pub total_rules: usize,
+    pub covered_rules: usize,
+    pub coverage_percent: f64,
+    /// Threshold this impl was checked against (0-100)
+    pub threshold: f64,
+    /// Whether coverage met the threshold with no invalid references
+    pub coverage_passing: bool,
+    /// Levels left out of `coverage_percent`
+    pub excluded_levels: Vec<String>,
+    /// Coverage of each requirement level that has rules, strongest first
+    pub levels: Vec<LevelCheck>,
+    /// Referenced requirements whose text changed since the last `tracey lock`
+    pub drifted: Vec<DriftedRule>,
+    /// Requirements and code changed since `--since`, if given
+    pub diff: Option<DiffScopeResponse>,
+}
+
+/// Coverage of the requirements at one level
+#[derive(Debug, Clone)]
+pub struct LevelCheck {
+    pub level: &'static str,
+    pub total_rules: usize,
+    pub covered_rules: usize,
+    pub coverage_percent: f64,
+    /// Threshold from the impl's `level_thresholds`, if set
+    pub threshold: Option<f64>,
+}
+
+impl LevelCheck {
+    pub fn is_passing(&self) -> bool {
+        self.threshold.is_none_or(|t| self.coverage_percent >= t)
+    }
+}
+
+impl ImplCheck {
+    pub fn is_passing(&self) -> bool {
+        self.coverage_passing
+            && self.levels.iter().all(|l| l.is_passing())
+            && self.validation.error_count == 0
+            && self.drifted.is_empty()
+            && self.diff.as_ref().is_none_or(|d| d.passing)
+    }
+}
+
+/// Result of a full `tracey check` run
+#[derive(Debug, Clone, Default)]
+pub struct CheckOutcome {
+    pub impls: Vec<ImplCheck>,
+}
+
+impl CheckOutcome {
+    pub fn is_passing(&self) -> bool {
+        self.impls.iter().all(|i| i.is_passing())
+    }
+}
+
+/// Build the data in-process and check every selected spec/impl pair.
+pub async fn check(
+    project_root: &Path,
+    config: &Config,
+    options: &CheckOptions,
+) -> Result<CheckOutcome> {
+    let data = build_dashboard_data(project_root, config, 1, true).await?;
+    let mut outcome = check_data(&data, project_root, config, options)?;
+
+    // r[impl cli.check.since]
+    if let Some(since) = &options.since {
+        let diff = GitDiff::load(project_root, since)?;
+        for check in &mut outcome.impls {
+            let key: ImplKey = (check.spec.clone(), check.impl_name.clone());
+            check.diff = Some(diff_scope(&data, &key, &diff).await);
+        }
+    }
+
+    Ok(outcome)
+}
+
+/// Check already-built dashboard data.
+pub fn check_data(
+    data: &DashboardData,
+    project_root: &Path,
+    config: &Config,
+    options: &CheckOptions,
+) -> Result<CheckOutcome> {
+    let mut outcome = CheckOutcome::default();
+    let lock = match &options.lock_path {
+        Some(path) => Lock::load(path)?,
+        None => Lock::default(),
+    };
+
+    for spec_config in &config.specs {
+        if options
+            .spec
+            .as_ref()
+            .is_some_and(|s| s != &spec_config.name)
+        {
+            continue;
+        }
+
+        for impl_config in &spec_config.impls {
+            if options
+                .impl_name
+                .as_ref()
+                .is_some_and(|i| i != &impl_config.name)
+            {
+                continue;
+            }
+
+            let key: ImplKey = (spec_config.name.clone(), impl_config.name.clone());
+            let excluded_levels = excluded_levels(&impl_config.exclude_levels, options)?;
+            // r[impl level.headline]
+            let Some(report) =
+                data.coverage_report_where(&key, |r| level::in_headline(r, &excluded_levels))
+            else {
+                continue;
+            };
+
+            // r[impl cli.check.threshold]
+            let threshold = options.threshold.or(impl_config.threshold).unwrap_or(0.0);
+
+            // r[impl level.thresholds]
+            let levels = level::LEVELS
+                .iter()
+                .filter_map(|&level| {
+                    let report =
+                        data.coverage_report_where(&key, |r| level::matches(r, Some(level)))?;
+                    (report.total_rules > 0).then(|| LevelCheck {
+                        level,
+                        total_rules: report.total_rules,
+                        covered_rules: report.covered_rules.len(),
+                        coverage_percent: report.coverage_percent(),
+                        threshold: impl_config.level_thresholds.get(level),
+                    })
+                })
+                .collect();
+
+            outcome.impls.push(ImplCheck {
+                spec: key.0.clone(),
+                impl_name: key.1.clone(),
+                validation: crate::validation::validate(data, project_root, &key.0, &key.1),
+                total_rules: report.total_rules,
+                covered_rules: report.covered_rules.len(),
+                coverage_percent: report.coverage_percent(),
+                threshold,
+                coverage_passing: report.is_passing(threshold),
+                excluded_levels,
+                levels,
+                // r[impl cli.check.lock]
+                drifted: lock.drift(data, &key.0),
+                diff: None,
+            });
+        }
+    }
+
+    if outcome.impls.is_empty() {
+        bail!(
+            "No spec/impl pairs matched (spec: {}, impl: {})",
+            options.spec.as_deref().unwrap_or("*"),
+            options.impl_name.as_deref().unwrap_or("*")
+        );
+    }
+
+    Ok(outcome)
+}
+
+/// Levels excluded from the headline percentage, normalized and deduplicated.
+fn excluded_levels(configured: &[String], options: &CheckOptions) -> Result<Vec<String>> {
+    let mut excluded = Vec::new();
+    for name in configured.iter().chain(&options.exclude_levels) {
+        let level = level::parse(name)?.to_string();
+        if !excluded.contains(&level) {
+            excluded.push(level);
+        }
+    }
+    Ok(excluded)
+}
+
+/// Print a human-readable summary of the check to stderr.
+pub fn print_summary(outcome: &CheckOutcome) {
+    for check in &outcome.impls {
+        let label = format!("{}/{}", check.spec, check.impl_name);
+        let mark = if check.is_passing() {
+            "✓".green().to_string()
+        } else {
+            "✗".red().to_string()
+        };
+
+        let excluding = if check.excluded_levels.is_empty() {
+            String::new()
+        } else {
+            format!(" excluding {}", check.excluded_levels.join(", "))
+        };
+        eprintln!(
+            "{} {}: {:.1}% covered ({}/{} rules{}, threshold {:.0}%), {} error(s), {} warning(s), {} changed requirement(s)",
+            mark,
+            label.bold(),
+            check.coverage_percent,
+            check.covered_rules,
+            check.total_rules,
+            excluding,
+            check.threshold,
+            check.validation.error_count,
+            check.validation.warning_count,
+            check.drifted.len()
+        );
+
+        if !check.coverage_passing && check.coverage_percent < check.threshold {
+            eprintln!(
+                "    {} coverage {:.1}% is below threshold {:.0}%",
+                "error:".red(),
+                check.coverage_percent,
+                check.threshold
+            );
+        }
+
+        for level in &check.levels {
+            let threshold = level
+                .threshold
+                .map(|t| format!(", threshold {:.0}%", t))
+                .unwrap_or_default();
+            eprintln!(
+                "    {}: {:.1}% covered ({}/{} rules{})",
+                level.level.to_uppercase(),
+                level.coverage_percent,
+                level.covered_rules,
+                level.total_rules,
+                threshold
+            );
+            if !level.is_passing() {
+                eprintln!(
+                    "    {} {} coverage {:.1}% is below threshold {:.0}%",
+                    "error:".red(),
+                    level.level.to_uppercase(),
+                    level.coverage_percent,
+                    level.threshold.unwrap_or_default()
+                );
+            }
+        }
+
+        for error in &check.validation.errors {
+            let location = match (&error.file, error.line) {
+                (Some(file), Some(line)) => format!("{}:{}: ", file, line),
+                (Some(file), None) => format!("{}: ", file),
+                _ => String::new(),
+            };
+            let severity = if error.code.is_warning() {
+                "warning:".yellow().to_string()
+            } else {
+                "error:".red().to_string()
+            };
+            eprintln!(
+                "    {} {}[{:?}] {}",
+                severity, location, error.code, error.message
+            );
+        }
+
+        for drifted in &check.drifted {
+            eprintln!(
+                "    {} requirement {} changed since it was locked; re-confirm its references and run `tracey lock`",
+                "error:".red(),
+                drifted.rule_id.yellow()
+            );
+        }
+
+        if let Some(diff) = &check.diff {
+            print_diff(diff);
+        }
+    }
+
+    let failed = outcome.impls.iter().filter(|i| !i.is_passing()).count();
+    if failed == 0 {
+        eprintln!("{}: all checks passed", "Success".green());
+    } else {
+        eprintln!(
+            "{}: {} of {} spec/impl pair(s) failed",
+            "Failed".red(),
+            failed,
+            outcome.impls.len()
+        );
+    }
+}
+
+/// Print the requirements and code changed since `--since`.
+fn print_diff(diff: &DiffScopeResponse) {
+    eprintln!(
+        "    since {}: {} changed requirement(s), {} unannotated code unit(s) touched",
+        diff.since.bold(),
+        diff.changed_rules.len(),
+        diff.unannotated_units.len()
+    );
+
+    for rule in &diff.changed_rules {
+        let location = match (&rule.source_file, rule.source_line) {
+            (Some(file), Some(line)) => format!(" ({}:{})", file, line),
+            _ => String::new(),
+        };
+        if is_uncovered_addition(rule) {
+            eprintln!(
+                "    {} new requirement {}{} has no impl references",
+                "error:".red(),
+                rule.id.yellow(),
+                location
+            );
+        } else {
+            let change = if rule.added { "added" } else { "edited" };
+            eprintln!("    {} {}{}", change, rule.id.yellow(), location);
+        }
+    }
+
+    for unit in &diff.unannotated_units {
+        let name = unit.name.as_deref().unwrap_or("<anonymous>");
+        let location = format!("{}:{}-{}", unit.path, unit.start_line, unit.end_line);
+        if is_unannotated_addition(unit) {
+            eprintln!(
+                "    {} new {} {} at {} has no requirement references",
+                "error:".red(),
+                unit.kind,
+                name.yellow(),
+                location
+            );
+        } else {
+            eprintln!("    unannotated {} {} at {}", unit.kind, name, location);
+        }
+    }
+}
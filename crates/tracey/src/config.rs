@@ -7,3 +7,70 @@
 
 // Re-export from tracey-config crate so build.rs can access the types
 pub use tracey_config::*;
+
+/// Options for extracting references from the files this config includes
+pub fn extract_options(config: &Config) -> tracey_core::ExtractOptions {
+    let mut options = tracey_core::ExtractOptions::default();
+    if !config.json_keys.is_empty() {
+        options.json_keys = config.json_keys.clone();
+    }
+    for spec in &config.specs {
+        for verb in &spec.verbs {
+            options.custom_verbs.push(tracey_core::CustomVerb {
+                prefix: spec.prefix.clone(),
+                name: verb.name.clone(),
+                counts_as: counts_as(verb).unwrap_or(tracey_core::RefVerb::Related),
+            });
+        }
+    }
+    options
+}
+
+/// The built-in verb whose coverage a custom verb counts toward, if any
+fn counts_as(verb: &Verb) -> Option<tracey_core::RefVerb> {
+    match verb.counts_as.as_deref()? {
+        "impl" => Some(tracey_core::RefVerb::Impl),
+        "verify" => Some(tracey_core::RefVerb::Verify),
+        _ => None,
+    }
+}
+
+/// Check the custom verbs a spec declares
+pub fn validate_verbs(spec: &SpecConfig) -> eyre::Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    for verb in &spec.verbs {
+        let name = verb.name.as_str();
+        let well_formed = name.starts_with(|c: char| c.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+        if !well_formed {
+            eyre::bail!(
+                "Spec '{}' declares invalid verb '{}': verbs are lowercase letters, digits and '-'",
+                spec.name,
+                name
+            );
+        }
+        if tracey_core::RefVerb::parse(name).is_some() {
+            eyre::bail!(
+                "Spec '{}' declares verb '{}', which is already built in",
+                spec.name,
+                name
+            );
+        }
+        if !seen.insert(name) {
+            eyre::bail!("Spec '{}' declares verb '{}' twice", spec.name, name);
+        }
+        if let Some(counts_as) = &verb.counts_as
+            && !matches!(counts_as.as_str(), "impl" | "verify")
+        {
+            eyre::bail!(
+                "Verb '{}' in spec '{}' has counts_as '{}': expected 'impl' or 'verify'",
+                name,
+                spec.name,
+                counts_as
+            );
+        }
+    }
+    Ok(())
+}
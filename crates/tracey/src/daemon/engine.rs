@@ -4,19 +4,24 @@
 //! r[impl daemon.state.blocking-rebuild]
 //! r[impl server.state.shared]
 //! r[impl server.state.version]
+//! r[impl daemon.state.extraction-cache]
 //!
 //! The engine owns the `DashboardData`, file watcher, and VFS overlay.
 //! It provides blocking rebuild semantics - all requests wait during rebuild.
+//! A `BuildCache` carried across rebuilds means only changed files are re-parsed,
+//! and changes to individual source files patch the current data in place of a
+//! full rebuild.
 
 use eyre::Result;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{RwLock, watch};
+use tokio::sync::{Mutex, RwLock, watch};
 use tracing::{debug, error, info, warn};
 
+use crate::cache::BuildCache;
 use crate::config::Config;
-use crate::data::{DashboardData, FileOverlay, build_dashboard_data_with_overlay};
+use crate::data::{DashboardData, FileOverlay, build_dashboard_data_cached, patch_dashboard_data};
 
 /// The core tracey engine.
 ///
@@ -42,11 +47,27 @@ pub struct Engine {
     version: Arc<std::sync::atomic::AtomicU64>,
     /// Current config error (if config file has errors)
     config_error: Arc<RwLock<Option<String>>>,
+    /// Per-file extraction results reused across rebuilds
+    cache: Arc<Mutex<BuildCache>>,
+    /// Where the extraction cache is persisted, if anywhere
+    cache_path: Option<PathBuf>,
 }
 
 impl Engine {
     /// Create a new engine for the given project root.
     pub async fn new(project_root: PathBuf, config_path: PathBuf) -> Result<Self> {
+        Self::with_cache_file(project_root, config_path, None).await
+    }
+
+    /// Create a new engine that starts from, and saves to, a persisted
+    /// extraction cache.
+    ///
+    /// r[impl daemon.state.persistent-cache]
+    pub async fn with_cache_file(
+        project_root: PathBuf,
+        config_path: PathBuf,
+        cache_path: Option<PathBuf>,
+    ) -> Result<Self> {
         // Check for deprecated config files first
         let deprecated_error = Self::check_deprecated_configs(&project_root);
 
@@ -82,14 +103,24 @@ impl Engine {
 
         // Build initial data
         let overlay = FileOverlay::new();
+        let mut cache = match &cache_path {
+            Some(path) => BuildCache::load(path),
+            None => BuildCache::new(),
+        };
         let data =
-            build_dashboard_data_with_overlay(&project_root, &config, 1, false, &overlay).await?;
+            build_dashboard_data_cached(&project_root, &config, 1, false, &overlay, &mut cache)
+                .await?;
         let data = Arc::new(data);
+        let stats = cache.stats();
+        info!(
+            "Initial build: {} parsed, {} reused from cache",
+            stats.misses, stats.hits
+        );
 
         // Create watch channel for broadcasting updates
         let (update_tx, update_rx) = watch::channel(Arc::clone(&data));
 
-        Ok(Self {
+        let engine = Self {
             data: Arc::new(RwLock::new(data)),
             update_tx,
             update_rx,
@@ -99,7 +130,25 @@ impl Engine {
             config: Arc::new(RwLock::new(config)),
             version: Arc::new(std::sync::atomic::AtomicU64::new(1)),
             config_error: Arc::new(RwLock::new(config_error)),
-        })
+            cache: Arc::new(Mutex::new(cache)),
+            cache_path,
+        };
+        engine.save_cache().await;
+        Ok(engine)
+    }
+
+    /// Persist the extraction cache, if this engine has a cache file.
+    ///
+    /// Failures are logged: a missing cache only costs a slower next start.
+    pub async fn save_cache(&self) {
+        let Some(path) = &self.cache_path else {
+            return;
+        };
+        let cache = self.cache.lock().await;
+        match cache.save(path) {
+            Ok(()) => debug!("Saved extraction cache to {}", path.display()),
+            Err(e) => warn!("Failed to save extraction cache: {:#}", e),
+        }
     }
 
     /// Get the current dashboard data.
@@ -129,7 +178,7 @@ impl Engine {
         debug!("VFS: opened {}", path.display());
         // Trigger rebuild
         drop(vfs);
-        if let Err(e) = self.rebuild().await {
+        if let Err(e) = self.update_files(&[path]).await {
             error!("Rebuild failed after vfs_open: {}", e);
         }
     }
@@ -143,7 +192,7 @@ impl Engine {
         debug!("VFS: changed {}", path.display());
         // Trigger rebuild
         drop(vfs);
-        if let Err(e) = self.rebuild().await {
+        if let Err(e) = self.update_files(&[path]).await {
             error!("Rebuild failed after vfs_change: {}", e);
         }
     }
@@ -157,7 +206,7 @@ impl Engine {
         debug!("VFS: closed {}", path.display());
         // Trigger rebuild
         drop(vfs);
-        if let Err(e) = self.rebuild().await {
+        if let Err(e) = self.update_files(&[path]).await {
             error!("Rebuild failed after vfs_close: {}", e);
         }
     }
@@ -224,15 +273,20 @@ impl Engine {
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
             + 1;
 
-        // Build new data (this is the expensive part)
-        let new_data = build_dashboard_data_with_overlay(
+        // Build new data (this is the expensive part, minus whatever the cache
+        // already has). Holding the cache lock also serializes rebuilds.
+        let mut cache = self.cache.lock().await;
+        let new_data = build_dashboard_data_cached(
             &self.project_root,
             &config,
             new_version,
             true,
             &overlay,
+            &mut cache,
         )
         .await?;
+        let stats = cache.stats();
+        drop(cache);
         let new_data = Arc::new(new_data);
 
         // Acquire write lock and update (blocks all reads)
@@ -252,8 +306,81 @@ impl Engine {
 
         let elapsed = start.elapsed();
         info!(
-            "Rebuild completed in {:?} (version {})",
-            elapsed, new_version
+            "Rebuild completed in {:?} (version {}, {} parsed, {} reused)",
+            elapsed, new_version, stats.misses, stats.hits
+        );
+
+        Ok((new_version, elapsed))
+    }
+
+    /// Update the dashboard data for changes to the given files.
+    ///
+    /// r[impl daemon.state.incremental-rebuild]
+    ///
+    /// The current data is patched for just those files where possible (see
+    /// [`patch_dashboard_data`]); anything else, such as a spec file or the
+    /// config changing, falls back to a full [`Self::rebuild`].
+    pub async fn update_files(&self, changed: &[PathBuf]) -> Result<(u64, Duration)> {
+        let start = Instant::now();
+
+        let config_path = self
+            .config_path
+            .canonicalize()
+            .unwrap_or_else(|_| self.config_path.clone());
+        if changed
+            .iter()
+            .any(|p| p.canonicalize().is_ok_and(|p| p == config_path))
+        {
+            return self.rebuild().await;
+        }
+
+        let config = self.config.read().await.clone();
+        let overlay = self.vfs.read().await.clone();
+
+        // Holding the cache lock serializes this with rebuilds, so the data
+        // being patched is the latest
+        let mut cache = self.cache.lock().await;
+        let current = self.data().await;
+        let new_version = self
+            .version
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+            + 1;
+        let Some(new_data) = patch_dashboard_data(
+            &current,
+            &self.project_root,
+            &config,
+            new_version,
+            &overlay,
+            changed,
+            &mut cache,
+        )
+        .await?
+        else {
+            drop(cache);
+            debug!("Changed files can't be patched in, rebuilding");
+            return self.rebuild().await;
+        };
+        let stats = cache.stats();
+        drop(cache);
+        let new_data = Arc::new(new_data);
+
+        // Acquire write lock and update (blocks all reads)
+        {
+            let mut data = self.data.write().await;
+            *data = Arc::clone(&new_data);
+        }
+
+        // Broadcast to subscribers
+        let _ = self.update_tx.send(new_data);
+
+        let elapsed = start.elapsed();
+        info!(
+            "Patched {} changed file(s) in {:?} (version {}, {} parsed, {} reused)",
+            changed.len(),
+            elapsed,
+            new_version,
+            stats.misses,
+            stats.hits
         );
 
         Ok((new_version, elapsed))
@@ -272,7 +399,6 @@ impl Engine {
     }
 
     /// Get the current config.
-    #[allow(dead_code)]
     pub async fn config(&self) -> Config {
         self.config.read().await.clone()
     }
@@ -128,7 +128,7 @@ pub async fn run(project_root: PathBuf, config_path: PathBuf) -> Result<()> {
     info!("Starting tracey daemon for {}", project_root.display());
 
     // Ensure .tracey directory exists
-    ensure_tracey_dir(&project_root)?;
+    let tracey_dir = ensure_tracey_dir(&project_root)?;
 
     // Get local IPC endpoint
     let endpoint = local_endpoint(&project_root);
@@ -145,9 +145,13 @@ pub async fn run(project_root: PathBuf, config_path: PathBuf) -> Result<()> {
 
     // Create engine
     let engine = Arc::new(
-        Engine::new(project_root.clone(), config_path.clone())
-            .await
-            .wrap_err("Failed to initialize engine")?,
+        Engine::with_cache_file(
+            project_root.clone(),
+            config_path.clone(),
+            Some(tracey_dir.join(crate::cache::CACHE_FILE_NAME)),
+        )
+        .await
+        .wrap_err("Failed to initialize engine")?,
     );
 
     // r[impl daemon.state.file-watcher]
@@ -333,7 +337,11 @@ pub async fn run(project_root: PathBuf, config_path: PathBuf) -> Result<()> {
                         );
                     }
 
-                    if let Err(e) = engine_for_rebuild.rebuild().await {
+                    let changed: Vec<PathBuf> = relative_paths
+                        .iter()
+                        .map(|p| project_root_for_rebuild.join(p))
+                        .collect();
+                    if let Err(e) = engine_for_rebuild.update_files(&changed).await {
                         error!("Rebuild failed: {}", e);
                     }
                 }
@@ -373,6 +381,7 @@ pub async fn run(project_root: PathBuf, config_path: PathBuf) -> Result<()> {
             _ = shutdown_rx.changed() => {
                 if *shutdown_rx.borrow() {
                     info!("Shutdown signal received");
+                    engine.save_cache().await;
                     let _ = roam_local::remove_endpoint(&endpoint);
                     return Ok(());
                 }
@@ -447,6 +456,7 @@ pub async fn run(project_root: PathBuf, config_path: PathBuf) -> Result<()> {
 
                     if idle_secs >= DEFAULT_IDLE_TIMEOUT_SECS {
                         info!("No connections for {} seconds, shutting down", idle_secs);
+                        engine.save_cache().await;
                         // Clean up endpoint
                         let _ = roam_local::remove_endpoint(&endpoint);
                         return Ok(());
@@ -9,8 +9,11 @@ use tracey_proto::*;
 
 use super::engine::Engine;
 use super::watcher::WatcherState;
-use crate::server::QueryEngine;
+use crate::data::DashboardData;
+use crate::server::{CoverageStats, QueryEngine};
+use crate::validation::{closest_rule_ids, did_you_mean, is_valid_rule_id, shared_segments};
 use roam::Tx;
+use tracey_core::split_version;
 
 // Re-export the generated dispatcher from tracey-proto
 pub use tracey_proto::TraceyDaemonDispatcher;
@@ -185,6 +188,74 @@ fn arborium_language(path: &str) -> Option<&'static str> {
     }
 }
 
+/// Read and syntax-highlight a source file of a spec/impl pair, with its code units.
+pub(crate) fn render_file(
+    data: &DashboardData,
+    project_root: &Path,
+    highlighter: &mut arborium::Highlighter,
+    req: FileRequest,
+) -> Option<ApiFileData> {
+    let impl_key = (req.spec, req.impl_name);
+
+    // Get the code units map for this impl
+    let code_units_by_file = data.code_units_by_impl.get(&impl_key)?;
+
+    // Resolve the file path - it may be relative or absolute
+    let file_path = PathBuf::from(&req.path);
+    let full_path = if file_path.is_absolute() {
+        file_path
+    } else {
+        project_root.join(&file_path)
+    };
+    // Canonicalize to handle cross-workspace paths like ../marq/...
+    let full_path = full_path.canonicalize().unwrap_or(full_path);
+
+    // Look up code units for this file
+    let units = code_units_by_file.get(&full_path)?;
+
+    // Read file content
+    let content = match std::fs::read_to_string(&full_path) {
+        Ok(c) => c,
+        Err(_) => return None,
+    };
+
+    // Get relative path for display
+    let relative = full_path
+        .strip_prefix(project_root)
+        .unwrap_or(&full_path)
+        .display()
+        .to_string();
+
+    // Syntax highlight the content
+    let html = if let Some(lang) = arborium_language(&relative) {
+        match highlighter.highlight(lang, &content) {
+            Ok(highlighted) => highlighted,
+            Err(_) => html_escape(&content),
+        }
+    } else {
+        html_escape(&content)
+    };
+
+    // Convert code units to API format
+    let api_units: Vec<ApiCodeUnit> = units
+        .iter()
+        .map(|u| ApiCodeUnit {
+            kind: format!("{:?}", u.kind).to_lowercase(),
+            name: u.name.clone(),
+            start_line: u.start_line,
+            end_line: u.end_line,
+            rule_refs: u.req_refs.clone(),
+        })
+        .collect();
+
+    Some(ApiFileData {
+        path: relative,
+        content,
+        html,
+        units: api_units,
+    })
+}
+
 /// Implementation of the TraceyDaemon trait.
 impl TraceyDaemon for TraceyService {
     /// Get coverage status for all specs/impls
@@ -196,19 +267,36 @@ impl TraceyDaemon for TraceyService {
         StatusResponse {
             impls: stats
                 .into_iter()
-                .map(|(spec, impl_name, s)| ImplStatus {
-                    spec,
-                    impl_name,
-                    total_rules: s.total_rules,
-                    covered_rules: s.impl_covered,
-                    verified_rules: s.verify_covered,
+                .map(|(spec, impl_name, s)| {
+                    // r[impl level.breakdown]
+                    let by_level = data
+                        .forward_by_impl
+                        .get(&(spec.clone(), impl_name.clone()))
+                        .map(|f| CoverageStats::by_level(&f.rules))
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|(level, l)| LevelStatus {
+                            level: level.to_string(),
+                            total_rules: l.total_rules,
+                            covered_rules: l.impl_covered,
+                            verified_rules: l.verify_covered,
+                        })
+                        .collect();
+                    ImplStatus {
+                        spec,
+                        impl_name,
+                        total_rules: s.total_rules,
+                        covered_rules: s.impl_covered,
+                        verified_rules: s.verify_covered,
+                        by_level,
+                    }
                 })
                 .collect(),
         }
     }
 
     /// Get uncovered rules
-    async fn uncovered(&self, req: UncoveredRequest) -> UncoveredResponse {
+    async fn uncovered(&self, req: UncoveredRequest) -> Result<UncoveredResponse, String> {
         let data = self.inner.engine.data().await;
         let query = QueryEngine::new(&data);
 
@@ -216,76 +304,109 @@ impl TraceyDaemon for TraceyService {
         let (spec, impl_name) =
             self.resolve_spec_impl(req.spec.as_deref(), req.impl_name.as_deref(), &data.config);
 
-        if let Some(result) = query.uncovered(&spec, &impl_name, req.prefix.as_deref()) {
-            UncoveredResponse {
-                spec: result.spec,
-                impl_name: result.impl_name,
-                total_rules: result.stats.total_rules,
-                uncovered_count: result.total_uncovered,
-                by_section: result
-                    .by_section
-                    .into_iter()
-                    .map(|(section, rules)| SectionRules {
-                        section,
-                        rules: rules
-                            .into_iter()
-                            .map(|r| tracey_proto::RuleRef {
-                                id: r.id,
-                                text: None, // RuleRef in server.rs doesn't have text
-                            })
-                            .collect(),
-                    })
-                    .collect(),
-            }
-        } else {
-            UncoveredResponse {
+        let level = req.level.as_deref().map(parse_level).transpose()?;
+        let Some(result) = query.uncovered(&spec, &impl_name, req.prefix.as_deref(), level) else {
+            return Ok(UncoveredResponse {
                 spec,
                 impl_name,
                 total_rules: 0,
                 uncovered_count: 0,
                 by_section: vec![],
-            }
-        }
+                stale: vec![],
+                rollup: vec![],
+            });
+        };
+        Ok(UncoveredResponse {
+            spec: result.spec,
+            impl_name: result.impl_name,
+            total_rules: result.stats.total_rules,
+            uncovered_count: result.total_uncovered,
+            by_section: result
+                .by_section
+                .into_iter()
+                .map(|(section, rules)| SectionRules {
+                    section,
+                    rules: rules
+                        .into_iter()
+                        .map(|r| tracey_proto::RuleRef {
+                            id: r.id,
+                            text: None, // RuleRef in server.rs doesn't have text
+                        })
+                        .collect(),
+                })
+                .collect(),
+            stale: result.stale.into_iter().map(stale_rule_ref).collect(),
+            rollup: result.rollup,
+        })
     }
 
     /// Get untested rules
-    async fn untested(&self, req: UntestedRequest) -> UntestedResponse {
+    async fn untested(&self, req: UntestedRequest) -> Result<UntestedResponse, String> {
         let data = self.inner.engine.data().await;
         let query = QueryEngine::new(&data);
 
         let (spec, impl_name) =
             self.resolve_spec_impl(req.spec.as_deref(), req.impl_name.as_deref(), &data.config);
 
-        if let Some(result) = query.untested(&spec, &impl_name, req.prefix.as_deref()) {
-            UntestedResponse {
-                spec: result.spec,
-                impl_name: result.impl_name,
-                total_rules: result.stats.total_rules,
-                untested_count: result.total_untested,
-                by_section: result
-                    .by_section
-                    .into_iter()
-                    .map(|(section, rules)| SectionRules {
-                        section,
-                        rules: rules
-                            .into_iter()
-                            .map(|r| tracey_proto::RuleRef {
-                                id: r.id,
-                                text: None,
-                            })
-                            .collect(),
-                    })
-                    .collect(),
-            }
-        } else {
-            UntestedResponse {
+        let level = req.level.as_deref().map(parse_level).transpose()?;
+        let Some(result) = query.untested(&spec, &impl_name, req.prefix.as_deref(), level) else {
+            return Ok(UntestedResponse {
                 spec,
                 impl_name,
                 total_rules: 0,
                 untested_count: 0,
                 by_section: vec![],
-            }
-        }
+                stale: vec![],
+                rollup: vec![],
+            });
+        };
+        Ok(UntestedResponse {
+            spec: result.spec,
+            impl_name: result.impl_name,
+            total_rules: result.stats.total_rules,
+            untested_count: result.total_untested,
+            by_section: result
+                .by_section
+                .into_iter()
+                .map(|(section, rules)| SectionRules {
+                    section,
+                    rules: rules
+                        .into_iter()
+                        .map(|r| tracey_proto::RuleRef {
+                            id: r.id,
+                            text: None,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            stale: result.stale.into_iter().map(stale_rule_ref).collect(),
+            rollup: result.rollup,
+        })
+    }
+
+    /// Get the requirement ID tree
+    async fn rule_tree(&self, req: RuleTreeRequest) -> Result<RuleTreeResponse, String> {
+        let data = self.inner.engine.data().await;
+        let query = QueryEngine::new(&data);
+
+        let (spec, impl_name) =
+            self.resolve_spec_impl(req.spec.as_deref(), req.impl_name.as_deref(), &data.config);
+
+        let level = req.level.as_deref().map(parse_level).transpose()?;
+        Ok(
+            match query.rule_tree(&spec, &impl_name, req.prefix.as_deref(), level) {
+                Some(result) => RuleTreeResponse {
+                    spec: result.spec,
+                    impl_name: result.impl_name,
+                    entries: result.entries,
+                },
+                None => RuleTreeResponse {
+                    spec,
+                    impl_name,
+                    entries: vec![],
+                },
+            },
+        )
     }
 
     /// Get unmapped code
@@ -351,6 +472,25 @@ impl TraceyDaemon for TraceyService {
         }
     }
 
+    /// Get requirements and unannotated code changed since a git ref
+    ///
+    /// r[impl diff.daemon]
+    async fn diff_scope(&self, req: DiffScopeRequest) -> Result<DiffScopeResponse, String> {
+        let data = self.inner.engine.data().await;
+        let project_root = self.inner.engine.project_root();
+
+        let (spec, impl_name) =
+            self.resolve_spec_impl(req.spec.as_deref(), req.impl_name.as_deref(), &data.config);
+        let key = (spec, impl_name);
+        if !data.forward_by_impl.contains_key(&key) {
+            return Err(format!("Unknown spec/impl: {}/{}", key.0, key.1));
+        }
+
+        let diff =
+            crate::diff::GitDiff::load(project_root, &req.since).map_err(|e| format!("{e:#}"))?;
+        Ok(crate::diff::diff_scope(&data, &key, &diff).await)
+    }
+
     /// Get details for a specific rule
     async fn rule(&self, rule_id: String) -> Option<RuleInfo> {
         let data = self.inner.engine.data().await;
@@ -370,6 +510,7 @@ impl TraceyDaemon for TraceyService {
                     impl_name: c.impl_name,
                     impl_refs: c.impl_refs,
                     verify_refs: c.verify_refs,
+                    verify_status: c.verify_status,
                 })
                 .collect(),
         })
@@ -547,68 +688,13 @@ impl TraceyDaemon for TraceyService {
     /// Get file with syntax highlighting
     async fn file(&self, req: FileRequest) -> Option<ApiFileData> {
         let data = self.inner.engine.data().await;
-        let project_root = self.inner.engine.project_root();
-
-        let impl_key = (req.spec, req.impl_name);
-
-        // Get the code units map for this impl
-        let code_units_by_file = data.code_units_by_impl.get(&impl_key)?;
-
-        // Resolve the file path - it may be relative or absolute
-        let file_path = PathBuf::from(&req.path);
-        let full_path = if file_path.is_absolute() {
-            file_path
-        } else {
-            project_root.join(&file_path)
-        };
-        // Canonicalize to handle cross-workspace paths like ../marq/...
-        let full_path = full_path.canonicalize().unwrap_or(full_path);
-
-        // Look up code units for this file
-        let units = code_units_by_file.get(&full_path)?;
-
-        // Read file content
-        let content = match std::fs::read_to_string(&full_path) {
-            Ok(c) => c,
-            Err(_) => return None,
-        };
-
-        // Get relative path for display
-        let relative = full_path
-            .strip_prefix(project_root)
-            .unwrap_or(&full_path)
-            .display()
-            .to_string();
-
-        // Syntax highlight the content
-        let html = if let Some(lang) = arborium_language(&relative) {
-            let mut hl = self.inner.highlighter.lock().unwrap();
-            match hl.highlight(lang, &content) {
-                Ok(highlighted) => highlighted,
-                Err(_) => html_escape(&content),
-            }
-        } else {
-            html_escape(&content)
-        };
-
-        // Convert code units to API format
-        let api_units: Vec<ApiCodeUnit> = units
-            .iter()
-            .map(|u| ApiCodeUnit {
-                kind: format!("{:?}", u.kind).to_lowercase(),
-                name: u.name.clone(),
-                start_line: u.start_line,
-                end_line: u.end_line,
-                rule_refs: u.req_refs.clone(),
-            })
-            .collect();
-
-        Some(ApiFileData {
-            path: relative,
-            content,
-            html,
-            units: api_units,
-        })
+        let mut highlighter = self.inner.highlighter.lock().unwrap();
+        render_file(
+            &data,
+            self.inner.engine.project_root(),
+            &mut highlighter,
+            req,
+        )
     }
 
     /// Get rendered spec content
@@ -617,6 +703,18 @@ impl TraceyDaemon for TraceyService {
         data.specs_content_by_impl.get(&(spec, impl_name)).cloned()
     }
 
+    /// Get coverage history recorded for a spec/impl pair
+    ///
+    /// r[impl history.api]
+    async fn history(&self, spec: String, impl_name: String) -> ApiHistory {
+        let path = crate::history::history_path(self.inner.engine.project_root());
+        let history = crate::history::History::load(&path).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring coverage history: {:#}", e);
+            Default::default()
+        });
+        history.for_impl(&spec, &impl_name)
+    }
+
     /// Search rules and files
     async fn search(&self, query: String, limit: u32) -> Vec<SearchResult> {
         let data = self.inner.engine.data().await;
@@ -737,178 +835,7 @@ impl TraceyDaemon for TraceyService {
         let (spec, impl_name) =
             self.resolve_spec_impl(req.spec.as_deref(), req.impl_name.as_deref(), &data.config);
 
-        let mut errors = Vec::new();
-
-        // Get all rules for this spec/impl
-        if let Some(forward_data) = data.forward_by_impl.get(&(spec.clone(), impl_name.clone())) {
-            // Build a map of rule IDs for quick lookup
-            let rule_ids: std::collections::HashSet<_> =
-                forward_data.rules.iter().map(|r| r.id.as_str()).collect();
-
-            // r[impl config.multi-spec.unique-within-spec]
-            // Check for duplicate rule IDs (within this spec)
-            let mut seen_ids: std::collections::HashMap<&str, (&Option<String>, Option<usize>)> =
-                std::collections::HashMap::new();
-            for rule in &forward_data.rules {
-                if let Some((prev_file, prev_line)) = seen_ids.get(rule.id.as_str()) {
-                    errors.push(ValidationError {
-                        code: ValidationErrorCode::DuplicateRequirement,
-                        message: format!(
-                            "Duplicate rule ID '{}' (first defined at {}:{})",
-                            rule.id,
-                            prev_file.as_deref().unwrap_or("?"),
-                            prev_line.unwrap_or(0)
-                        ),
-                        file: rule.source_file.clone(),
-                        line: rule.source_line,
-                        column: rule.source_column,
-                        related_rules: vec![rule.id.clone()],
-                    });
-                } else {
-                    seen_ids.insert(&rule.id, (&rule.source_file, rule.source_line));
-                }
-            }
-
-            // Check each rule
-            for rule in &forward_data.rules {
-                // Check naming convention (dot-separated segments)
-                if !is_valid_rule_id(&rule.id) {
-                    errors.push(ValidationError {
-                        code: ValidationErrorCode::InvalidNaming,
-                        message: format!(
-                            "Rule ID '{}' doesn't follow naming convention (use dot-separated lowercase segments)",
-                            rule.id
-                        ),
-                        file: rule.source_file.clone(),
-                        line: rule.source_line,
-                        column: rule.source_column,
-                        related_rules: vec![],
-                    });
-                }
-
-                // r[impl config.impl.test_include.verify-only]
-                // Check that impl references are not in test files
-                for impl_ref in &rule.impl_refs {
-                    let ref_path = project_root.join(&impl_ref.file);
-                    if data.test_files.contains(&ref_path) {
-                        errors.push(ValidationError {
-                            code: ValidationErrorCode::ImplInTestFile,
-                            message: format!(
-                                "Test file contains impl annotation for '{}' - test files may only contain verify annotations",
-                                rule.id
-                            ),
-                            file: Some(impl_ref.file.clone()),
-                            line: Some(impl_ref.line),
-                            column: None,
-                            related_rules: vec![rule.id.clone()],
-                        });
-                    }
-                }
-
-                // Check depends references exist
-                for dep_ref in &rule.depends_refs {
-                    // Extract rule ID from the file path (this is a simplification)
-                    // In a full implementation, we'd track what rule ID each depends ref points to
-                    // For now, we just note that depends references exist
-                    let _ = dep_ref;
-                }
-            }
-
-            // r[impl ref.prefix.unknown]
-            // Check for references with unknown prefixes
-            // This requires checking the reverse data for any files that have
-            // references to rules not in the rule_ids set
-            if let Some(reverse_data) = data.reverse_by_impl.get(&(spec.clone(), impl_name.clone()))
-            {
-                // Get all prefixes from the config
-                let known_prefixes: std::collections::HashSet<&str> = data
-                    .config
-                    .specs
-                    .iter()
-                    .map(|s| s.prefix.as_str())
-                    .collect();
-
-                // r[impl ref.prefix.filter]
-                // Find the prefix for the current spec being validated
-                let current_spec_prefix: Option<&str> = data
-                    .config
-                    .specs
-                    .iter()
-                    .find(|s| s.name == spec)
-                    .map(|s| s.prefix.as_str());
-
-                // Check files for unknown references
-                for file_entry in &reverse_data.files {
-                    let file_path = project_root.join(&file_entry.path);
-                    if let Ok(content) = std::fs::read_to_string(&file_path) {
-                        let reqs = tracey_core::Reqs::extract_from_content(&file_path, &content);
-                        for reference in &reqs.references {
-                            // Check if prefix is known
-                            if !known_prefixes.contains(reference.prefix.as_str()) {
-                                let available: Vec<_> = known_prefixes.iter().copied().collect();
-                                errors.push(ValidationError {
-                                    code: ValidationErrorCode::UnknownPrefix,
-                                    message: format!(
-                                        "Unknown prefix '{}' - available prefixes: {}",
-                                        reference.prefix,
-                                        available.join(", ")
-                                    ),
-                                    file: Some(file_entry.path.clone()),
-                                    line: Some(reference.line),
-                                    column: None,
-                                    related_rules: vec![],
-                                });
-                            }
-                            // r[impl ref.prefix.filter]
-                            // Only validate references whose prefix matches the current spec
-                            // Skip references that belong to a different spec (different prefix)
-                            else if current_spec_prefix == Some(reference.prefix.as_str()) {
-                                // Check if rule ID exists (for matching prefix only)
-                                if !rule_ids.contains(reference.req_id.as_str()) {
-                                    errors.push(ValidationError {
-                                        code: ValidationErrorCode::UnknownRequirement,
-                                        message: format!(
-                                            "Reference to unknown rule '{}'",
-                                            reference.req_id
-                                        ),
-                                        file: Some(file_entry.path.clone()),
-                                        line: Some(reference.line),
-                                        column: None,
-                                        related_rules: vec![],
-                                    });
-                                }
-                            }
-                            // References with different known prefixes are intentionally skipped
-                            // They belong to a different spec and will be validated when that spec is checked
-                        }
-                    }
-                }
-            }
-
-            // Check for circular dependencies
-            // Build dependency graph and detect cycles
-            let cycles = detect_circular_dependencies(forward_data);
-            for cycle in cycles {
-                errors.push(ValidationError {
-                    code: ValidationErrorCode::CircularDependency,
-                    message: format!("Circular dependency detected: {}", cycle.join(" → ")),
-                    file: None,
-                    line: None,
-                    column: None,
-                    related_rules: cycle,
-                });
-            }
-        }
-
-        let error_count = errors.len();
-
-        ValidationResult {
-            spec,
-            impl_name,
-            errors,
-            warning_count: 0,
-            error_count,
-        }
+        crate::validation::validate(&data, project_root, &spec, &impl_name)
     }
 
     // =========================================================================
@@ -920,11 +847,64 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.hover.prefix]
     async fn lsp_hover(&self, req: LspPositionRequest) -> Option<HoverInfo> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         // Find the rule at cursor position (works for both spec and source files)
-        let rule_at_pos =
-            find_rule_at_position(&path, &req.content, req.line, req.character).await?;
+        let rule_at_pos = find_rule_at_position(
+            &config,
+            project_root,
+            &path,
+            &req.content,
+            req.line,
+            req.character,
+        )
+        .await?;
+
+        // Calculate the range of the reference
+        let (start_line, start_char, end_line, end_char) = span_to_range(
+            &req.content,
+            rule_at_pos.span_offset,
+            rule_at_pos.span_length,
+        );
+
+        // r[impl lsp.hover.wildcard]
+        if tracey_core::is_wildcard(&rule_at_pos.req_id) {
+            let matched = find_rules_in_data(&data, &rule_at_pos.req_id);
+            let &(spec_name, _) = matched.first()?;
+            let spec_url = data
+                .config
+                .specs
+                .iter()
+                .find(|s| &s.name == spec_name)
+                .and_then(|s| s.source_url.clone());
+            let matched_rules: Vec<HoverMatch> = matched
+                .iter()
+                .map(|(_, rule)| HoverMatch {
+                    rule_id: rule.id.clone(),
+                    impl_count: rule.impl_refs.len(),
+                    verify_count: rule.verify_refs.len(),
+                })
+                .collect();
+
+            return Some(HoverInfo {
+                rule_id: rule_at_pos.req_id,
+                raw: String::new(),
+                spec_name: spec_name.clone(),
+                spec_url,
+                source_file: None,
+                impl_count: matched_rules.iter().map(|m| m.impl_count).sum(),
+                verify_count: matched_rules.iter().map(|m| m.verify_count).sum(),
+                impl_refs: vec![],
+                verify_refs: vec![],
+                matched_rules,
+                range_start_line: start_line,
+                range_start_char: start_char,
+                range_end_line: end_line,
+                range_end_char: end_char,
+            });
+        }
 
         // Look up the rule in our data
         let (spec_name, rule) = find_rule_in_data(&data, &rule_at_pos.req_id)?;
@@ -954,13 +934,6 @@ impl TraceyDaemon for TraceyService {
         let impl_count = impl_refs.len();
         let verify_count = verify_refs.len();
 
-        // Calculate the range of the reference
-        let (start_line, start_char, end_line, end_char) = span_to_range(
-            &req.content,
-            rule_at_pos.span_offset,
-            rule_at_pos.span_length,
-        );
-
         Some(HoverInfo {
             rule_id: rule.id.clone(),
             raw: rule.raw.clone(),
@@ -971,6 +944,7 @@ impl TraceyDaemon for TraceyService {
             verify_count,
             impl_refs,
             verify_refs,
+            matched_rules: vec![],
             range_start_line: start_line,
             range_start_char: start_char,
             range_end_line: end_line,
@@ -983,29 +957,40 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.goto.ref-to-def]
     async fn lsp_definition(&self, req: LspPositionRequest) -> Vec<LspLocation> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         // Find the rule at cursor position (works for both spec and source files)
-        let Some(rule_at_pos) =
-            find_rule_at_position(&path, &req.content, req.line, req.character).await
+        let Some(rule_at_pos) = find_rule_at_position(
+            &config,
+            project_root,
+            &path,
+            &req.content,
+            req.line,
+            req.character,
+        )
+        .await
         else {
             return vec![];
         };
 
-        let Some((_, rule)) = find_rule_in_data(&data, &rule_at_pos.req_id) else {
-            return vec![];
-        };
-
-        // Return the definition location (where the rule is defined in the spec)
-        if let (Some(file), Some(line)) = (&rule.source_file, rule.source_line) {
-            vec![LspLocation {
-                path: file.clone(),
-                line: line.saturating_sub(1) as u32, // Convert to 0-indexed
-                character: rule.source_column.unwrap_or(0) as u32,
-            }]
-        } else {
-            vec![]
-        }
+        // Return the definition location (where the rule is defined in the spec),
+        // or one per requirement a wildcard matches
+        // r[impl lsp.goto.wildcard]
+        find_rules_in_data(&data, &rule_at_pos.req_id)
+            .into_iter()
+            .filter_map(|(_, rule)| {
+                let (Some(file), Some(line)) = (&rule.source_file, rule.source_line) else {
+                    return None;
+                };
+                Some(LspLocation {
+                    path: file.clone(),
+                    line: line.saturating_sub(1) as u32, // Convert to 0-indexed
+                    character: rule.source_column.unwrap_or(0) as u32,
+                })
+            })
+            .collect()
     }
 
     /// Get implementation locations for a reference at a position
@@ -1015,28 +1000,44 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.impl.multiple]
     async fn lsp_implementation(&self, req: LspPositionRequest) -> Vec<LspLocation> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         // Find the rule at cursor position (works for both spec and source files)
-        let Some(rule_at_pos) =
-            find_rule_at_position(&path, &req.content, req.line, req.character).await
+        let Some(rule_at_pos) = find_rule_at_position(
+            &config,
+            project_root,
+            &path,
+            &req.content,
+            req.line,
+            req.character,
+        )
+        .await
         else {
             return vec![];
         };
 
-        let Some((_, rule)) = find_rule_in_data(&data, &rule_at_pos.req_id) else {
-            return vec![];
-        };
-
-        // Return all impl reference locations
-        rule.impl_refs
-            .iter()
-            .map(|r| LspLocation {
-                path: r.file.clone(),
-                line: r.line.saturating_sub(1) as u32,
-                character: 0,
-            })
-            .collect()
+        // Return all impl reference locations, across every requirement a
+        // wildcard matches
+        // r[impl lsp.impl.wildcard]
+        let mut locations: Vec<LspLocation> = Vec::new();
+        for (_, rule) in find_rules_in_data(&data, &rule_at_pos.req_id) {
+            for r in &rule.impl_refs {
+                let location = LspLocation {
+                    path: r.file.clone(),
+                    line: r.line.saturating_sub(1) as u32,
+                    character: 0,
+                };
+                if !locations
+                    .iter()
+                    .any(|l| l.path == location.path && l.line == location.line)
+                {
+                    locations.push(location);
+                }
+            }
+        }
+        locations
     }
 
     /// Get all references to a requirement
@@ -1046,11 +1047,20 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.references.include-type]
     async fn lsp_references(&self, req: LspReferencesRequest) -> Vec<LspLocation> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         // Find the rule at cursor position (works for both spec and source files)
-        let Some(rule_at_pos) =
-            find_rule_at_position(&path, &req.content, req.line, req.character).await
+        let Some(rule_at_pos) = find_rule_at_position(
+            &config,
+            project_root,
+            &path,
+            &req.content,
+            req.line,
+            req.character,
+        )
+        .await
         else {
             return vec![];
         };
@@ -1131,20 +1141,34 @@ impl TraceyDaemon for TraceyService {
                 // If we haven't closed the bracket and there's no space yet, suggest verbs
                 if !after_bracket.contains(']') {
                     if !after_bracket.contains(' ') {
-                        // Suggest verbs
-                        for (verb, desc) in [
-                            ("impl ", "Implementation of a requirement"),
-                            ("verify ", "Test/verification of a requirement"),
-                            ("depends ", "Dependency on another requirement"),
-                            ("related ", "Related requirement"),
-                        ] {
+                        // Suggest verbs, including the ones this spec declares
+                        let mut verbs: Vec<(String, String)> = [
+                            ("impl", "Implementation of a requirement"),
+                            ("verify", "Test/verification of a requirement"),
+                            ("depends", "Dependency on another requirement"),
+                            ("related", "Related requirement"),
+                        ]
+                        .into_iter()
+                        .map(|(verb, desc)| (verb.to_string(), desc.to_string()))
+                        .collect();
+                        // r[impl lsp.completions.custom-verb]
+                        for verb in &prefix.verbs {
+                            let desc = match &verb.counts_as {
+                                Some(counts_as) => {
+                                    format!("Custom verb (counts toward {} coverage)", counts_as)
+                                }
+                                None => "Custom verb".to_string(),
+                            };
+                            verbs.push((verb.name.clone(), desc));
+                        }
+                        for (verb, desc) in verbs {
                             if verb.starts_with(after_bracket) || after_bracket.is_empty() {
                                 completions.push(LspCompletionItem {
-                                    label: verb.trim().to_string(),
+                                    label: verb.clone(),
                                     kind: "verb".to_string(),
-                                    detail: Some(desc.to_string()),
+                                    detail: Some(desc),
                                     documentation: None,
-                                    insert_text: Some(verb.to_string()),
+                                    insert_text: Some(format!("{} ", verb)),
                                 });
                             }
                         }
@@ -1186,12 +1210,14 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.diagnostics.impl-in-test]
     async fn lsp_diagnostics(&self, req: LspDocumentRequest) -> Vec<LspDiagnostic> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         let mut diagnostics = Vec::new();
 
         // For markdown spec files, show coverage diagnostics for definitions
-        if path.extension().is_some_and(|ext| ext == "md") {
+        if crate::is_spec_file(&config, project_root, &path) {
             let options = marq::RenderOptions::default();
             if let Ok(doc) = marq::render(&req.content, &options).await {
                 for def in &doc.reqs {
@@ -1199,12 +1225,30 @@ impl TraceyDaemon for TraceyService {
                     let (start_line, start_char, end_line, end_char) =
                         span_to_range(&req.content, def.marker_span.offset, def.marker_span.length);
 
-                    // Look up the rule to check coverage
-                    if let Some((_, rule)) = find_rule_in_data(&data, &def.id) {
+                    // Look up the rule to check coverage; draft, deprecated
+                    // and removed rules aren't expected to be covered
+                    if let Some((_, rule)) = find_rule_in_data(&data, split_version(&def.id).0)
+                        && rule.counts_toward_coverage()
+                    {
                         let impl_count = rule.impl_refs.len();
                         let verify_count = rule.verify_refs.len();
 
-                        if impl_count == 0 {
+                        // r[impl lsp.diagnostics.stale]
+                        if rule.is_stale() {
+                            diagnostics.push(LspDiagnostic {
+                                severity: "hint".to_string(),
+                                code: "stale".to_string(),
+                                message: format!(
+                                    "Requirement changed (now +{}); {} reference(s) still point at an older version",
+                                    rule.version,
+                                    rule.stale_refs.len()
+                                ),
+                                start_line,
+                                start_char,
+                                end_line,
+                                end_char,
+                            });
+                        } else if impl_count == 0 {
                             diagnostics.push(LspDiagnostic {
                                 severity: "hint".to_string(),
                                 code: "uncovered".to_string(),
@@ -1235,16 +1279,16 @@ impl TraceyDaemon for TraceyService {
         }
 
         // For source files, check references
-        let reqs = tracey_core::Reqs::extract_from_content(&path, &req.content);
+        let reqs = extract_document_reqs(&config, &path, &req.content);
 
         // Check if this is a test file
         let is_test = data.test_files.contains(&path);
 
-        // Build set of known rule IDs
-        let known_rules: std::collections::HashSet<_> = data
+        // Build map of known rule IDs to their current version and status
+        let known_rules: std::collections::HashMap<_, _> = data
             .forward_by_impl
             .values()
-            .flat_map(|f| f.rules.iter().map(|r| r.id.as_str()))
+            .flat_map(|f| f.rules.iter().map(|r| (r.id.as_str(), r)))
             .collect();
 
         // Build set of known prefixes
@@ -1274,16 +1318,95 @@ impl TraceyDaemon for TraceyService {
             }
 
             // Check for unknown rule ID (orphaned reference)
-            if !known_rules.contains(reference.req_id.as_str()) {
-                diagnostics.push(LspDiagnostic {
-                    severity: "warning".to_string(),
-                    code: "orphaned".to_string(),
-                    message: format!("Unknown requirement: '{}'", reference.req_id),
-                    start_line,
-                    start_char,
-                    end_line,
-                    end_char,
-                });
+            match known_rules.get(reference.req_id.as_str()) {
+                // r[impl lsp.diagnostics.unmatched-wildcard]
+                None if tracey_core::is_wildcard(&reference.req_id) => {
+                    let matches_any = known_rules.values().any(|rule| {
+                        !rule.is_deprecated()
+                            && !rule.is_removed()
+                            && tracey_core::wildcard_matches(&reference.req_id, &rule.id)
+                    });
+                    if !matches_any {
+                        diagnostics.push(LspDiagnostic {
+                            severity: "error".to_string(),
+                            code: "unmatched-wildcard".to_string(),
+                            message: format!(
+                                "Wildcard '{}' matches no requirements",
+                                reference.req_id
+                            ),
+                            start_line,
+                            start_char,
+                            end_line,
+                            end_char,
+                        });
+                    }
+                }
+                None => {
+                    diagnostics.push(LspDiagnostic {
+                        severity: "warning".to_string(),
+                        code: "orphaned".to_string(),
+                        message: format!(
+                            "Unknown requirement: '{}'{}",
+                            reference.req_id,
+                            did_you_mean(&closest_rule_ids(
+                                &reference.req_id,
+                                rule_ids_with_prefix(&data, &reference.prefix)
+                            ))
+                        ),
+                        start_line,
+                        start_char,
+                        end_line,
+                        end_char,
+                    });
+                }
+                // r[impl lsp.diagnostics.removed]
+                Some(rule) if rule.is_removed() => {
+                    diagnostics.push(LspDiagnostic {
+                        severity: "error".to_string(),
+                        code: "removed".to_string(),
+                        message: format!("Requirement '{}' was removed", reference.req_id),
+                        start_line,
+                        start_char,
+                        end_line,
+                        end_char,
+                    });
+                }
+                // r[impl lsp.diagnostics.stale]
+                Some(rule)
+                    if reference.version < rule.version
+                        && !matches!(
+                            reference.verb,
+                            tracey_core::RefVerb::Define | tracey_core::RefVerb::Related
+                        ) =>
+                {
+                    diagnostics.push(LspDiagnostic {
+                        severity: "warning".to_string(),
+                        code: "stale".to_string(),
+                        message: format!(
+                            "Requirement '{}' changed since this reference was written (now +{})",
+                            reference.req_id, rule.version
+                        ),
+                        start_line,
+                        start_char,
+                        end_line,
+                        end_char,
+                    });
+                }
+                // r[impl lsp.diagnostics.deprecated]
+                Some(rule)
+                    if rule.is_deprecated() && reference.verb == tracey_core::RefVerb::Impl =>
+                {
+                    diagnostics.push(LspDiagnostic {
+                        severity: "warning".to_string(),
+                        code: "deprecated".to_string(),
+                        message: format!("Requirement '{}' is deprecated", reference.req_id),
+                        start_line,
+                        start_char,
+                        end_line,
+                        end_char,
+                    });
+                }
+                Some(_) => {}
             }
 
             // Check for impl in test file
@@ -1399,14 +1522,14 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.symbols.references]
     /// r[impl lsp.symbols.requirements]
     async fn lsp_document_symbols(&self, req: LspDocumentRequest) -> Vec<LspSymbol> {
+        let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
         let mut symbols = Vec::new();
 
         // For spec files (markdown), return requirement definitions
-        if path.extension().is_some_and(|ext| ext == "md") {
-            let data = self.inner.engine.data().await;
-            let project_root = self.inner.engine.project_root();
-
+        if crate::is_spec_file(&config, project_root, &path) {
             // Get relative path for matching
             let relative_path = path
                 .strip_prefix(project_root)
@@ -1429,23 +1552,27 @@ impl TraceyDaemon for TraceyService {
                             start_char: col,
                             end_line: line,
                             end_char: col + rule.id.len() as u32,
+                            path: None,
+                            container: None,
                         });
                     }
                 }
             }
         } else {
             // For implementation files, extract references
-            let reqs = tracey_core::Reqs::extract_from_content(&path, &req.content);
+            let reqs = extract_document_reqs(&config, &path, &req.content);
             for r in &reqs.references {
                 let (start_line, start_char, end_line, end_char) =
                     span_to_range(&req.content, r.span.offset, r.span.length);
                 symbols.push(LspSymbol {
                     name: r.req_id.clone(),
-                    kind: format!("{:?}", r.verb).to_lowercase(),
+                    kind: r.verb_name().to_string(),
                     start_line,
                     start_char,
                     end_line,
                     end_char,
+                    path: None,
+                    container: None,
                 });
             }
         }
@@ -1461,27 +1588,41 @@ impl TraceyDaemon for TraceyService {
         let query_lower = query.to_lowercase();
 
         let mut symbols = Vec::new();
-        for ((_, _), forward_data) in &data.forward_by_impl {
+        for ((spec, impl_name), forward_data) in &data.forward_by_impl {
             for rule in &forward_data.rules {
                 if rule.id.to_lowercase().contains(&query_lower) {
-                    let (line, char) = if let Some(l) = rule.source_line {
-                        (
-                            l.saturating_sub(1) as u32,
-                            rule.source_column.unwrap_or(0) as u32,
-                        )
-                    } else {
-                        (0, 0)
-                    };
+                    let parent = rule.id.rsplit_once('.').map(|(p, _)| p.to_string());
+                    symbols.push(workspace_symbol(rule, &rule.id, "requirement", parent));
+                }
+            }
 
-                    symbols.push(LspSymbol {
-                        name: rule.id.clone(),
-                        kind: "requirement".to_string(),
-                        start_line: line,
-                        start_char: char,
-                        end_line: line,
-                        end_char: char + rule.id.len() as u32,
-                    });
+            // r[impl lsp.workspace-symbols.prefixes]
+            // ID prefixes with requirements under them, located at the first one
+            for entry in crate::data::build_rule_tree(&forward_data.rules) {
+                if entry.aggregated.total == entry.coverage.total
+                    || !entry.prefix.to_lowercase().contains(&query_lower)
+                {
+                    continue;
                 }
+                let child_prefix = format!("{}.", entry.prefix);
+                let Some(first) = forward_data
+                    .rules
+                    .iter()
+                    .find(|r| r.id.starts_with(&child_prefix))
+                else {
+                    continue;
+                };
+                let cov = &entry.aggregated;
+                let summary = format!(
+                    "{}/{}: {}/{} implemented, {}/{} verified",
+                    spec, impl_name, cov.impl_count, cov.total, cov.verify_count, cov.total
+                );
+                symbols.push(workspace_symbol(
+                    first,
+                    &entry.prefix,
+                    "prefix",
+                    Some(summary),
+                ));
             }
         }
 
@@ -1495,6 +1636,8 @@ impl TraceyDaemon for TraceyService {
     async fn lsp_semantic_tokens(&self, req: LspDocumentRequest) -> Vec<LspSemanticToken> {
         let path = PathBuf::from(&req.path);
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
 
         // Build set of known rule IDs
         let known_rules: std::collections::HashSet<_> = data
@@ -1506,7 +1649,7 @@ impl TraceyDaemon for TraceyService {
         let mut tokens = Vec::new();
 
         // For markdown spec files, tokenize requirement definitions
-        if path.extension().is_some_and(|ext| ext == "md") {
+        if crate::is_spec_file(&config, project_root, &path) {
             let options = marq::RenderOptions::default();
             if let Ok(doc) = marq::render(&req.content, &options).await {
                 for def in &doc.reqs {
@@ -1526,7 +1669,7 @@ impl TraceyDaemon for TraceyService {
             }
         } else {
             // For source files, tokenize references in comments
-            let reqs = tracey_core::Reqs::extract_from_content(&path, &req.content);
+            let reqs = extract_document_reqs(&config, &path, &req.content);
 
             for reference in &reqs.references {
                 let (start_line, start_char, _, _) =
@@ -1534,7 +1677,11 @@ impl TraceyDaemon for TraceyService {
 
                 // Token for the entire reference
                 // Token type 0 = namespace (prefix), 1 = keyword (verb), 2 = variable (req_id)
-                let is_valid = known_rules.contains(reference.req_id.as_str());
+                let is_valid = known_rules.contains(reference.req_id.as_str())
+                    || (tracey_core::is_wildcard(&reference.req_id)
+                        && known_rules
+                            .iter()
+                            .any(|id| tracey_core::wildcard_matches(&reference.req_id, id)));
                 let modifier = if reference.verb == tracey_core::RefVerb::Define {
                     1 // DEFINITION modifier
                 } else if is_valid {
@@ -1543,6 +1690,34 @@ impl TraceyDaemon for TraceyService {
                     0
                 };
 
+                // r[impl lsp.semantic-tokens.custom-verb]
+                // Custom verbs get a token of their own, between the prefix and the ID
+                let text = &req.content
+                    [reference.span.offset..reference.span.offset + reference.span.length];
+                if let Some(verb) = &reference.custom_verb
+                    && let Some(verb_start) = text.find('[').map(|i| i + 1)
+                    && text[verb_start..].starts_with(verb.as_str())
+                {
+                    let verb_end = verb_start + verb.len();
+                    for (start, end, token_type) in [
+                        (0, verb_start, 0),        // namespace (prefix)
+                        (verb_start, verb_end, 3), // customVerb
+                        (verb_end, text.len(), 2), // variable (req_id)
+                    ] {
+                        // Segment offsets are in bytes; LSP columns are UTF-16
+                        let (line, segment_start, _, segment_end) =
+                            span_to_range(&req.content, reference.span.offset + start, end - start);
+                        tokens.push(LspSemanticToken {
+                            line,
+                            start_char: segment_start,
+                            length: segment_end - segment_start,
+                            token_type,
+                            modifiers: modifier,
+                        });
+                    }
+                    continue;
+                }
+
                 tokens.push(LspSemanticToken {
                     line: start_line,
                     start_char,
@@ -1563,12 +1738,14 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.codelens.run-test]
     async fn lsp_code_lens(&self, req: LspDocumentRequest) -> Vec<LspCodeLens> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         let mut lenses = Vec::new();
 
         // For markdown spec files, show code lenses for requirement definitions
-        if path.extension().is_some_and(|ext| ext == "md") {
+        if crate::is_spec_file(&config, project_root, &path) {
             let options = marq::RenderOptions::default();
             if let Ok(doc) = marq::render(&req.content, &options).await {
                 for def in &doc.reqs {
@@ -1577,7 +1754,7 @@ impl TraceyDaemon for TraceyService {
                         span_to_range(&req.content, def.marker_span.offset, def.marker_span.length);
 
                     // Look up coverage for this rule
-                    if let Some((_, rule)) = find_rule_in_data(&data, &def.id) {
+                    if let Some((_, rule)) = find_rule_in_data(&data, split_version(&def.id).0) {
                         let impl_count = rule.impl_refs.len();
                         let verify_count = rule.verify_refs.len();
 
@@ -1595,14 +1772,14 @@ impl TraceyDaemon for TraceyService {
                             end_char,
                             title,
                             command: "tracey.showReferences".to_string(),
-                            arguments: vec![def.id.clone()],
+                            arguments: vec![split_version(&def.id).0.to_string()],
                         });
                     }
                 }
             }
         } else {
             // For source files, show code lenses for definition references
-            let reqs = tracey_core::Reqs::extract_from_content(&path, &req.content);
+            let reqs = extract_document_reqs(&config, &path, &req.content);
 
             for reference in &reqs.references {
                 // Only show code lens for definitions
@@ -1647,12 +1824,14 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.inlay.impl-count]
     async fn lsp_inlay_hints(&self, req: InlayHintsRequest) -> Vec<LspInlayHint> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         let mut hints = Vec::new();
 
         // For markdown spec files, show hints for requirement definitions
-        if path.extension().is_some_and(|ext| ext == "md") {
+        if crate::is_spec_file(&config, project_root, &path) {
             let options = marq::RenderOptions::default();
             if let Ok(doc) = marq::render(&req.content, &options).await {
                 for def in &doc.reqs {
@@ -1666,7 +1845,7 @@ impl TraceyDaemon for TraceyService {
                     }
 
                     // Look up the rule to get impl/verify counts
-                    if let Some((_, rule)) = find_rule_in_data(&data, &def.id) {
+                    if let Some((_, rule)) = find_rule_in_data(&data, split_version(&def.id).0) {
                         let impl_count = rule.impl_refs.len();
                         let verify_count = rule.verify_refs.len();
 
@@ -1682,7 +1861,7 @@ impl TraceyDaemon for TraceyService {
             }
         } else {
             // For source files, show hints for references in comments
-            let reqs = tracey_core::Reqs::extract_from_content(&path, &req.content);
+            let reqs = extract_document_reqs(&config, &path, &req.content);
 
             for reference in &reqs.references {
                 let (line, _, _, end_char) =
@@ -1717,11 +1896,20 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.rename.prepare]
     async fn lsp_prepare_rename(&self, req: LspPositionRequest) -> Option<PrepareRenameResult> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         // Find the rule at cursor position (works for both spec and source files)
-        let rule_at_pos =
-            find_rule_at_position(&path, &req.content, req.line, req.character).await?;
+        let rule_at_pos = find_rule_at_position(
+            &config,
+            project_root,
+            &path,
+            &req.content,
+            req.line,
+            req.character,
+        )
+        .await?;
 
         // Check if the rule exists
         find_rule_in_data(&data, &rule_at_pos.req_id)?;
@@ -1749,11 +1937,20 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.rename.validation]
     async fn lsp_rename(&self, req: LspRenameRequest) -> Vec<LspTextEdit> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         // Find the rule at cursor position (works for both spec and source files)
-        let Some(rule_at_pos) =
-            find_rule_at_position(&path, &req.content, req.line, req.character).await
+        let Some(rule_at_pos) = find_rule_at_position(
+            &config,
+            project_root,
+            &path,
+            &req.content,
+            req.line,
+            req.character,
+        )
+        .await
         else {
             return vec![];
         };
@@ -1806,22 +2003,68 @@ impl TraceyDaemon for TraceyService {
     /// r[impl lsp.actions.open-dashboard]
     async fn lsp_code_actions(&self, req: LspPositionRequest) -> Vec<LspCodeAction> {
         let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         let mut actions = Vec::new();
 
         // Check if we're on a rule (works for both spec and source files)
-        if let Some(rule_at_pos) =
-            find_rule_at_position(&path, &req.content, req.line, req.character).await
+        if let Some(rule_at_pos) = find_rule_at_position(
+            &config,
+            project_root,
+            &path,
+            &req.content,
+            req.line,
+            req.character,
+        )
+        .await
         {
             // Check if it's an orphaned reference
             if find_rule_in_data(&data, &rule_at_pos.req_id).is_none() {
+                // r[impl lsp.actions.fix-unknown]
+                // Most unknown IDs are typos: offer the closest known ones first
+                let fixes = rule_at_pos
+                    .prefix
+                    .as_deref()
+                    .filter(|_| !tracey_core::is_wildcard(&rule_at_pos.req_id))
+                    .map(|prefix| {
+                        closest_rule_ids(&rule_at_pos.req_id, rule_ids_with_prefix(&data, prefix))
+                    })
+                    .unwrap_or_default();
+                let reference = &req.content
+                    [rule_at_pos.span_offset..rule_at_pos.span_offset + rule_at_pos.span_length];
+                if let Some(id_offset) = reference.rfind(&rule_at_pos.req_id) {
+                    let (start_line, start_char, end_line, end_char) = span_to_range(
+                        &req.content,
+                        rule_at_pos.span_offset + id_offset,
+                        rule_at_pos.req_id.len(),
+                    );
+                    for (i, id) in fixes.iter().enumerate() {
+                        actions.push(LspCodeAction {
+                            title: format!("Change to '{}'", id),
+                            kind: "quickfix".to_string(),
+                            command: String::new(),
+                            arguments: Vec::new(),
+                            is_preferred: i == 0,
+                            edit: Some(LspTextEdit {
+                                path: req.path.clone(),
+                                start_line,
+                                start_char,
+                                end_line,
+                                end_char,
+                                new_text: id.to_string(),
+                            }),
+                        });
+                    }
+                }
                 actions.push(LspCodeAction {
                     title: format!("Create requirement '{}'", rule_at_pos.req_id),
                     kind: "quickfix".to_string(),
                     command: "tracey.createRequirement".to_string(),
                     arguments: vec![rule_at_pos.req_id.clone()],
-                    is_preferred: true,
+                    is_preferred: actions.is_empty(),
+                    edit: None,
                 });
             } else {
                 // Open dashboard for this requirement
@@ -1831,29 +2074,192 @@ impl TraceyDaemon for TraceyService {
                     command: "tracey.openDashboard".to_string(),
                     arguments: vec![rule_at_pos.req_id],
                     is_preferred: false,
+                    edit: None,
                 });
             }
+        } else {
+            actions.extend(annotation_actions(&data, &path, &req.content, req.line));
         }
 
         actions
     }
 
+    /// Places to define a new requirement, best match first
+    ///
+    /// Every section of a spec file that already has requirements is a
+    /// candidate, ranked by how many leading ID segments its requirements
+    /// share with `req_id`. The stub goes after the section's last requirement,
+    /// just before the next heading.
+    ///
+    /// r[impl lsp.commands.create-requirement]
+    async fn lsp_requirement_stubs(&self, req_id: String) -> Vec<RequirementStub> {
+        let data = self.inner.engine.data().await;
+        let project_root = self.inner.engine.project_root();
+
+        struct Section<'a> {
+            spec: &'a str,
+            prefix: &'a str,
+            file: &'a str,
+            title: Option<&'a str>,
+            last_line: usize,
+            score: usize,
+        }
+
+        let mut sections: Vec<Section> = Vec::new();
+        for spec in &data.config.specs {
+            // Rules are the same in every impl of a spec
+            let Some(forward) = spec.implementations.first().and_then(|impl_name| {
+                data.forward_by_impl
+                    .get(&(spec.name.clone(), impl_name.clone()))
+            }) else {
+                continue;
+            };
+            for rule in &forward.rules {
+                let (Some(file), Some(line)) = (&rule.source_file, rule.source_line) else {
+                    continue;
+                };
+                let title = rule.section_title.as_deref();
+                let score = shared_segments(&rule.id, &req_id);
+                match sections
+                    .iter_mut()
+                    .find(|s| s.spec == spec.name && s.file == file && s.title == title)
+                {
+                    Some(section) => {
+                        section.last_line = section.last_line.max(line);
+                        section.score = section.score.max(score);
+                    }
+                    None => sections.push(Section {
+                        spec: &spec.name,
+                        prefix: &spec.prefix,
+                        file,
+                        title,
+                        last_line: line,
+                        score,
+                    }),
+                }
+            }
+        }
+        sections.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then_with(|| (a.file, a.last_line).cmp(&(b.file, b.last_line)))
+        });
+
+        let mut stubs = Vec::new();
+        for section in sections {
+            let Ok(content) = tokio::fs::read_to_string(project_root.join(section.file)).await
+            else {
+                continue;
+            };
+            let stub = format!(
+                "{}[{}]\nTODO: describe this requirement.\n",
+                section.prefix, req_id
+            );
+            let (line, character, new_text) = match next_heading_line(&content, section.last_line) {
+                Some(line) => (line, 0, format!("{}\n", stub)),
+                // Last section: append to the end of the file
+                None => {
+                    let line = content.lines().count() as u32;
+                    if content.is_empty() || content.ends_with('\n') {
+                        (line, 0, format!("\n{}", stub))
+                    } else {
+                        let last = content.lines().last().unwrap_or("");
+                        (line - 1, last.len() as u32, format!("\n\n{}", stub))
+                    }
+                }
+            };
+            stubs.push(RequirementStub {
+                spec: section.spec.to_string(),
+                section_title: section.title.map(String::from),
+                edit: LspTextEdit {
+                    path: section.file.to_string(),
+                    start_line: line,
+                    start_char: character,
+                    end_line: line,
+                    end_char: character,
+                    new_text,
+                },
+            });
+        }
+        stubs
+    }
+
+    /// The requirement defined or referenced at a position
+    ///
+    /// r[impl lsp.call-hierarchy.prepare]
+    async fn lsp_prepare_call_hierarchy(&self, req: LspPositionRequest) -> Vec<LspSymbol> {
+        let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
+        let path = PathBuf::from(&req.path);
+
+        let Some(rule_at_pos) = find_rule_at_position(
+            &config,
+            project_root,
+            &path,
+            &req.content,
+            req.line,
+            req.character,
+        )
+        .await
+        else {
+            return Vec::new();
+        };
+        requirement_symbols(&data, [rule_at_pos.req_id.as_str()])
+    }
+
+    /// Requirements the given requirement depends on
+    ///
+    /// r[impl lsp.call-hierarchy.outgoing]
+    async fn lsp_dependencies(&self, req_id: String) -> Vec<LspSymbol> {
+        let data = self.inner.engine.data().await;
+        let edges = dependency_edges(&data, self.inner.engine.project_root());
+        let targets = edges
+            .iter()
+            .filter(|(from, _)| *from == req_id)
+            .map(|(_, to)| to.as_str());
+        requirement_symbols(&data, targets)
+    }
+
+    /// Requirements that depend on the given requirement
+    ///
+    /// r[impl lsp.call-hierarchy.incoming]
+    async fn lsp_dependents(&self, req_id: String) -> Vec<LspSymbol> {
+        let data = self.inner.engine.data().await;
+        let edges = dependency_edges(&data, self.inner.engine.project_root());
+        let sources = edges
+            .iter()
+            .filter(|(_, to)| *to == req_id)
+            .map(|(from, _)| from.as_str());
+        requirement_symbols(&data, sources)
+    }
+
     /// Get document highlight ranges (same requirement references)
     ///
     /// r[impl lsp.highlight.full-range]
     /// r[impl lsp.highlight.consistent]
     async fn lsp_document_highlight(&self, req: LspPositionRequest) -> Vec<LspLocation> {
+        let data = self.inner.engine.data().await;
+        let config = self.inner.engine.config().await;
+        let project_root = self.inner.engine.project_root();
         let path = PathBuf::from(&req.path);
 
         // Find the rule at cursor position (works for both spec and source files)
-        let Some(rule_at_pos) =
-            find_rule_at_position(&path, &req.content, req.line, req.character).await
+        let Some(rule_at_pos) = find_rule_at_position(
+            &config,
+            project_root,
+            &path,
+            &req.content,
+            req.line,
+            req.character,
+        )
+        .await
         else {
             return vec![];
         };
 
-        // For markdown files, highlight all definitions of the same rule (typically just one)
-        if path.extension().is_some_and(|ext| ext == "md") {
+        // For spec files, highlight all definitions of the same rule (typically just one)
+        if crate::is_spec_file(&config, project_root, &path) {
             let options = marq::RenderOptions::default();
             if let Ok(doc) = marq::render(&req.content, &options).await {
                 return doc
@@ -1875,7 +2281,7 @@ impl TraceyDaemon for TraceyService {
         }
 
         // For source files, find all references to the same rule in this document
-        let reqs = tracey_core::Reqs::extract_from_content(&path, &req.content);
+        let reqs = extract_document_reqs(&config, &path, &req.content);
         reqs.references
             .iter()
             .filter(|r| r.req_id == rule_at_pos.req_id)
@@ -1990,23 +2396,40 @@ impl TraceyDaemon for TraceyService {
 struct RuleAtPosition {
     /// The rule ID
     req_id: String,
+    /// The reference's prefix (`None` for definitions in spec files)
+    prefix: Option<String>,
     /// Byte offset in the content
     span_offset: usize,
     /// Length in bytes
     span_length: usize,
 }
 
+/// References in an open document, extracted as a build would extract them
+fn extract_document_reqs(
+    config: &crate::config::Config,
+    path: &Path,
+    content: &str,
+) -> tracey_core::Reqs {
+    tracey_core::Reqs::extract_from_content_with(
+        path,
+        content,
+        &crate::config::extract_options(config),
+    )
+}
+
 /// Find a rule (reference or definition) at the given position.
 ///
-/// For markdown spec files, uses marq to extract requirement definitions.
-/// For source files, uses the lexer to extract references from comments.
+/// For spec files, uses marq to extract requirement definitions.
+/// For other files, uses the lexer to extract references from comments.
 async fn find_rule_at_position(
+    config: &crate::config::Config,
+    project_root: &Path,
     path: &Path,
     content: &str,
     line: u32,
     character: u32,
 ) -> Option<RuleAtPosition> {
-    if path.extension().is_some_and(|ext| ext == "md") {
+    if crate::is_spec_file(config, project_root, path) {
         // Parse markdown to find requirement definitions
         let options = marq::RenderOptions::default();
         let doc = marq::render(content, &options).await.ok()?;
@@ -2018,7 +2441,8 @@ async fn find_rule_at_position(
             let end = r.span.offset + r.span.length;
             if target_offset >= start && target_offset < end {
                 Some(RuleAtPosition {
-                    req_id: r.id.clone(),
+                    req_id: split_version(&r.id).0.to_string(),
+                    prefix: None,
                     span_offset: r.span.offset,
                     span_length: r.span.length,
                 })
@@ -2028,17 +2452,144 @@ async fn find_rule_at_position(
         })
     } else {
         // Parse source file to find references in comments
-        let reqs = tracey_core::Reqs::extract_from_content(path, content);
+        let reqs = extract_document_reqs(config, path, content);
         let ref_at_pos = find_ref_at_position(&reqs, content, line, character)?;
 
         Some(RuleAtPosition {
             req_id: ref_at_pos.req_id.clone(),
+            prefix: Some(ref_at_pos.prefix.clone()),
             span_offset: ref_at_pos.span.offset,
             span_length: ref_at_pos.span.length,
         })
     }
 }
 
+/// IDs of the rules in the specs using `prefix`, except removed ones
+fn rule_ids_with_prefix<'a>(data: &'a DashboardData, prefix: &str) -> Vec<&'a str> {
+    let specs: Vec<&str> = data
+        .config
+        .specs
+        .iter()
+        .filter(|s| s.prefix == prefix)
+        .map(|s| s.name.as_str())
+        .collect();
+    data.forward_by_impl
+        .iter()
+        .filter(|((spec, _), _)| specs.contains(&spec.as_str()))
+        .flat_map(|(_, forward)| forward.rules.iter())
+        .filter(|r| !r.is_removed())
+        .map(|r| r.id.as_str())
+        .collect()
+}
+
+/// How many requirements the annotate code action suggests
+const ANNOTATION_SUGGESTIONS: usize = 5;
+
+/// Actions annotating the unannotated code unit at `line` (0-indexed) with
+/// the requirements whose text best matches the unit's name, comments and
+/// identifiers, as ranked by the search index.
+///
+/// r[impl lsp.actions.annotate]
+fn annotation_actions(
+    data: &DashboardData,
+    path: &Path,
+    content: &str,
+    line: u32,
+) -> Vec<LspCodeAction> {
+    let Some(comment) = tracey_core::code_units::line_comment_prefix(path) else {
+        return Vec::new();
+    };
+
+    // Only suggest rules from the specs whose implementations cover this file
+    // (code units are keyed by canonical path)
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let specs: Vec<&str> = data
+        .code_units_by_impl
+        .iter()
+        .filter(|(_, files)| files.contains_key(&canonical))
+        .map(|((spec, _), _)| spec.as_str())
+        .collect();
+    if specs.is_empty() {
+        return Vec::new();
+    }
+
+    // The innermost code unit around the cursor
+    let line = line as usize + 1;
+    let units = tracey_core::code_units::extract(path, content);
+    let Some(unit) = units
+        .units
+        .iter()
+        .filter(|u| u.start_line <= line && line <= u.end_line)
+        .min_by_key(|u| u.end_line - u.start_line)
+    else {
+        return Vec::new();
+    };
+    if !unit.req_refs.is_empty() {
+        return Vec::new();
+    }
+
+    let query = crate::search::code_query(
+        unit.name.as_deref(),
+        &content[unit.start_byte..unit.end_byte],
+    );
+    let verb = if data.test_files.contains(path) {
+        "verify"
+    } else {
+        "impl"
+    };
+    let first_line = content.lines().nth(unit.start_line - 1).unwrap_or("");
+    let indent = &first_line[..first_line.len() - first_line.trim_start().len()];
+    let target = match &unit.name {
+        Some(name) => format!("`{}`", name),
+        None => unit.kind.to_string(),
+    };
+
+    let mut seen = std::collections::HashSet::new();
+    let mut actions = Vec::new();
+    // Over-fetch: some hits belong to other specs or don't count
+    for result in data
+        .search_index
+        .search_rules(&query, ANNOTATION_SUGGESTIONS * 4)
+    {
+        let Some((spec, rule)) = data
+            .forward_by_impl
+            .iter()
+            .filter(|((spec, _), _)| specs.contains(&spec.as_str()))
+            .find_map(|((spec, _), forward)| {
+                Some((spec, forward.rules.iter().find(|r| r.id == result.id)?))
+            })
+        else {
+            continue;
+        };
+        if !rule.counts_toward_coverage() || !seen.insert(rule.id.clone()) {
+            continue;
+        }
+        let Some(spec) = data.config.specs.iter().find(|s| &s.name == spec) else {
+            continue;
+        };
+        let annotation = format!("{}[{} {}]", spec.prefix, verb, rule.id);
+        actions.push(LspCodeAction {
+            title: format!("Annotate {} with {}", target, annotation),
+            kind: "refactor".to_string(),
+            command: String::new(),
+            arguments: Vec::new(),
+            is_preferred: false,
+            edit: Some(LspTextEdit {
+                path: path.display().to_string(),
+                start_line: (unit.start_line - 1) as u32,
+                start_char: 0,
+                end_line: (unit.start_line - 1) as u32,
+                end_char: 0,
+                new_text: format!("{}{} {}\n", indent, comment, annotation),
+            }),
+        });
+        if actions.len() == ANNOTATION_SUGGESTIONS {
+            break;
+        }
+    }
+    actions
+}
+
 /// Find a reference at the given position in the content (for source files only)
 fn find_ref_at_position<'a>(
     reqs: &'a tracey_core::Reqs,
@@ -2055,7 +2606,7 @@ fn find_ref_at_position<'a>(
     })
 }
 
-/// Convert line/column (0-indexed) to byte offset
+/// Convert line/column (0-indexed, column in UTF-16 code units) to byte offset
 fn line_col_to_offset(content: &str, line: u32, col: u32) -> Option<usize> {
     let mut current_line = 0u32;
     let mut offset = 0usize;
@@ -2064,13 +2615,15 @@ fn line_col_to_offset(content: &str, line: u32, col: u32) -> Option<usize> {
         if current_line == line {
             let line_start = offset;
             // Find the column within this line
-            for (current_col, (j, ch)) in content[line_start..].char_indices().enumerate() {
+            let mut current_col = 0u32;
+            for (j, ch) in content[line_start..].char_indices() {
                 if ch == '\n' {
                     break;
                 }
-                if current_col as u32 == col {
+                if current_col >= col {
                     return Some(line_start + j);
                 }
+                current_col += ch.len_utf16() as u32;
             }
             // If col is at or past end of line, return end of line
             return Some(i);
@@ -2089,7 +2642,8 @@ fn line_col_to_offset(content: &str, line: u32, col: u32) -> Option<usize> {
     }
 }
 
-/// Convert byte offset and length to line/column range (0-indexed)
+/// Convert byte offset and length to line/column range (0-indexed), with
+/// columns in UTF-16 code units as LSP positions expect
 fn span_to_range(content: &str, offset: usize, length: usize) -> (u32, u32, u32, u32) {
     let mut line = 0u32;
     let mut col = 0u32;
@@ -2110,7 +2664,7 @@ fn span_to_range(content: &str, offset: usize, length: usize) -> (u32, u32, u32,
             line += 1;
             col = 0;
         } else {
-            col += 1;
+            col += c.len_utf16() as u32;
         }
     }
 
@@ -2137,114 +2691,120 @@ fn find_rule_in_data<'a>(
     None
 }
 
-/// Save config to file
-fn save_config(path: &Path, config: &crate::config::Config) -> eyre::Result<()> {
-    use std::io::Write;
-    let yaml_string = facet_yaml::to_string(config)?;
-    let mut file = std::fs::File::create(path)?;
-    file.write_all(yaml_string.as_bytes())?;
-    Ok(())
+/// Requirement dependency edges (dependent, dependency) across all
+/// implementations
+fn dependency_edges(
+    data: &crate::data::DashboardData,
+    project_root: &Path,
+) -> std::collections::BTreeSet<(String, String)> {
+    data.forward_by_impl
+        .keys()
+        .flat_map(|key| data.rule_dependencies(project_root, key))
+        .flat_map(|(from, targets)| targets.into_iter().map(move |to| (from.clone(), to)))
+        .collect()
+}
+
+/// Symbols for requirements, located at their definitions, with their spec
+/// as the container (unknown IDs are skipped)
+fn requirement_symbols<'a>(
+    data: &crate::data::DashboardData,
+    ids: impl IntoIterator<Item = &'a str>,
+) -> Vec<LspSymbol> {
+    ids.into_iter()
+        .filter_map(|id| find_rule_in_data(data, id))
+        .map(|(spec, rule)| workspace_symbol(rule, &rule.id, "requirement", Some(spec.clone())))
+        .collect()
 }
 
-/// Check if a rule ID follows the naming convention
-fn is_valid_rule_id(id: &str) -> bool {
-    // Must have at least one segment
-    if id.is_empty() {
-        return false;
+/// Find the rules a reference points at: the rule itself, or every rule a
+/// wildcard matches (deduplicated by ID, sorted)
+fn find_rules_in_data<'a>(
+    data: &'a crate::data::DashboardData,
+    req_id: &str,
+) -> Vec<(&'a String, &'a ApiRule)> {
+    if !tracey_core::is_wildcard(req_id) {
+        return find_rule_in_data(data, req_id).into_iter().collect();
     }
 
-    // Split by dots and check each segment
-    for segment in id.split('.') {
-        if segment.is_empty() {
-            return false;
+    let mut matched: std::collections::BTreeMap<&str, (&String, &ApiRule)> =
+        std::collections::BTreeMap::new();
+    for ((spec, _), forward_data) in &data.forward_by_impl {
+        for rule in &forward_data.rules {
+            if !rule.is_deprecated()
+                && !rule.is_removed()
+                && tracey_core::wildcard_matches(req_id, &rule.id)
+            {
+                matched.entry(rule.id.as_str()).or_insert((spec, rule));
+            }
         }
-        // Each segment must contain only lowercase letters, digits, or hyphens
-        if !segment
-            .chars()
-            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
-        {
-            return false;
+    }
+    matched.into_values().collect()
+}
+
+/// The first heading line (0-indexed) after 1-indexed `line` in a markdown
+/// file, skipping fenced code blocks
+fn next_heading_line(content: &str, line: usize) -> Option<u32> {
+    let mut in_fence = false;
+    for (idx, text) in content.lines().enumerate() {
+        let trimmed = text.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+            continue;
         }
-        // Segment must start with a letter
-        if !segment
-            .chars()
-            .next()
-            .is_some_and(|c| c.is_ascii_lowercase())
-        {
-            return false;
+        if idx >= line && !in_fence && text.starts_with('#') {
+            return Some(idx as u32);
         }
     }
-
-    true
+    None
 }
 
-/// Detect circular dependencies in the rule dependency graph
-fn detect_circular_dependencies(forward_data: &ApiSpecForward) -> Vec<Vec<String>> {
-    use std::collections::{HashMap, HashSet};
-
-    // Build adjacency list from depends_refs
-    // Note: This is a simplified version - in a full implementation,
-    // we'd need to track which rule ID each depends ref points to
-    let mut graph: HashMap<&str, Vec<&str>> = HashMap::new();
-
-    for rule in &forward_data.rules {
-        // Initialize empty adjacency list for each rule
-        graph.entry(rule.id.as_str()).or_default();
-
-        // For now, we can't easily extract dependency targets from depends_refs
-        // since they only contain file:line references, not rule IDs.
-        // A proper implementation would require parsing the depends comments
-        // to extract the target rule IDs.
-    }
-
-    // Detect cycles using DFS
-    let mut cycles = Vec::new();
-    let mut visited = HashSet::new();
-    let mut rec_stack = HashSet::new();
-    let mut path = Vec::new();
-
-    fn dfs<'a>(
-        node: &'a str,
-        graph: &HashMap<&'a str, Vec<&'a str>>,
-        visited: &mut HashSet<&'a str>,
-        rec_stack: &mut HashSet<&'a str>,
-        path: &mut Vec<String>,
-        cycles: &mut Vec<Vec<String>>,
-    ) {
-        visited.insert(node);
-        rec_stack.insert(node);
-        path.push(node.to_string());
-
-        if let Some(neighbors) = graph.get(node) {
-            for &neighbor in neighbors {
-                if !visited.contains(neighbor) {
-                    dfs(neighbor, graph, visited, rec_stack, path, cycles);
-                } else if rec_stack.contains(neighbor) {
-                    // Found a cycle
-                    let cycle_start = path.iter().position(|n| n == neighbor).unwrap();
-                    let mut cycle: Vec<String> = path[cycle_start..].to_vec();
-                    cycle.push(neighbor.to_string());
-                    cycles.push(cycle);
-                }
-            }
-        }
+/// Parse a requested level (`MUST`, `shall`...), rejecting unknown ones like
+/// the CLI and MCP do.
+fn parse_level(level: &str) -> Result<&'static str, String> {
+    crate::level::parse(level).map_err(|e| e.to_string())
+}
 
-        path.pop();
-        rec_stack.remove(node);
+/// Workspace symbol named `name`, located at `rule`'s definition
+fn workspace_symbol(
+    rule: &ApiRule,
+    name: &str,
+    kind: &str,
+    container: Option<String>,
+) -> LspSymbol {
+    let (line, char) = if let Some(l) = rule.source_line {
+        (
+            l.saturating_sub(1) as u32,
+            rule.source_column.unwrap_or(0) as u32,
+        )
+    } else {
+        (0, 0)
+    };
+
+    LspSymbol {
+        name: name.to_string(),
+        kind: kind.to_string(),
+        start_line: line,
+        start_char: char,
+        end_line: line,
+        end_char: char + name.len() as u32,
+        path: rule.source_file.clone(),
+        container,
     }
+}
 
-    for &node in graph.keys() {
-        if !visited.contains(node) {
-            dfs(
-                node,
-                &graph,
-                &mut visited,
-                &mut rec_stack,
-                &mut path,
-                &mut cycles,
-            );
-        }
+fn stale_rule_ref(rule: crate::server::StaleRule) -> tracey_proto::StaleRuleRef {
+    tracey_proto::StaleRuleRef {
+        id: rule.id,
+        version: rule.version,
+        stale_refs: rule.stale_refs,
     }
+}
 
-    cycles
+/// Save config to file
+fn save_config(path: &Path, config: &crate::config::Config) -> eyre::Result<()> {
+    use std::io::Write;
+    let yaml_string = facet_yaml::to_string(config)?;
+    let mut file = std::fs::File::create(path)?;
+    file.write_all(yaml_string.as_bytes())?;
+    Ok(())
 }
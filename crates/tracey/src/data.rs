@@ -8,6 +8,7 @@
 use eyre::Result;
 use owo_colors::OwoColorize;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::future::Future;
 use std::path::{Path, PathBuf};
@@ -16,7 +17,10 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use tracey_core::code_units::CodeUnit;
 use tracey_core::is_supported_extension;
-use tracey_core::{RefVerb, ReqDefinition, Reqs};
+use tracey_core::{
+    CoverageReport, RefVerb, ReqDefinition, ReqReference, Reqs, is_wildcard, split_version,
+    wildcard_matches,
+};
 
 // Markdown rendering
 use marq::{
@@ -24,8 +28,11 @@ use marq::{
     ReqHandler, parse_frontmatter, render,
 };
 
+use crate::ExtractedRule;
+use crate::cache::BuildCache;
 use crate::config::Config;
 use crate::search::{self, SearchIndex};
+use crate::test_results::TestResults;
 
 // ============================================================================
 // JSON API Types
@@ -34,8 +41,8 @@ use crate::search::{self, SearchIndex};
 // Re-export API types from tracey-api crate
 pub use tracey_api::{
     ApiCodeRef, ApiCodeUnit, ApiConfig, ApiFileData, ApiFileEntry, ApiForwardData, ApiReverseData,
-    ApiRule, ApiSpecData, ApiSpecForward, ApiSpecInfo, GitStatus, OutlineCoverage, OutlineEntry,
-    SpecSection,
+    ApiRule, ApiRuleTreeEntry, ApiSpecData, ApiSpecForward, ApiSpecInfo, ApiVerb, ApiVerbRefs,
+    GitStatus, OutlineCoverage, OutlineEntry, SpecSection,
 };
 
 // ============================================================================
@@ -52,12 +59,14 @@ pub struct DashboardData {
     pub forward_by_impl: BTreeMap<ImplKey, ApiSpecForward>,
     /// Reverse data per implementation: (spec_name, impl_name) -> data
     pub reverse_by_impl: BTreeMap<ImplKey, ApiReverseData>,
+    /// Raw requirement references per implementation (all prefixes)
+    pub reqs_by_impl: BTreeMap<ImplKey, Reqs>,
     /// Code units per implementation for file API
     pub code_units_by_impl: BTreeMap<ImplKey, BTreeMap<PathBuf, Vec<CodeUnit>>>,
     /// Spec content per implementation (coverage info varies by impl)
     pub specs_content_by_impl: BTreeMap<ImplKey, ApiSpecData>,
-    /// Full-text search index for source files
-    pub search_index: Box<dyn SearchIndex>,
+    /// Full-text search index for source files (kept across rebuilds and updated in place)
+    pub search_index: Arc<dyn SearchIndex>,
     /// Version number (incremented only when content actually changes)
     pub version: u64,
     /// Hash of forward + reverse JSON for change detection
@@ -67,10 +76,138 @@ pub struct DashboardData {
     /// Files matched by test_include patterns (only verify allowed)
     /// r[impl config.impl.test_include]
     pub test_files: std::collections::HashSet<PathBuf>,
+    /// Test results per implementation that has any
+    pub test_results_by_impl: BTreeMap<ImplKey, TestResults>,
+}
+
+impl DashboardData {
+    /// Compute a `CoverageReport` for a spec/impl pair.
+    ///
+    /// Only references using the spec's prefix are considered, so annotations
+    /// for other specs in shared source files don't count as invalid.
+    pub fn coverage_report(&self, key: &ImplKey) -> Option<CoverageReport> {
+        self.coverage_report_where(key, |_| true)
+    }
+
+    /// Requirement dependencies for a spec/impl pair: each requirement's ID
+    /// mapped to the IDs of the requirements it depends on.
+    ///
+    /// A `depends` or `related` reference in a code unit makes every
+    /// requirement implemented or verified in the same unit depend on the
+    /// reference's target.
+    pub fn rule_dependencies(
+        &self,
+        project_root: &Path,
+        key: &ImplKey,
+    ) -> BTreeMap<String, BTreeSet<String>> {
+        let mut graph: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let (Some(forward), Some(units_by_file)) = (
+            self.forward_by_impl.get(key),
+            self.code_units_by_impl.get(key),
+        ) else {
+            return graph;
+        };
+        let abs_root = project_root
+            .canonicalize()
+            .unwrap_or_else(|_| project_root.to_path_buf());
+
+        for target in &forward.rules {
+            for depends in &target.depends_refs {
+                // Code units are keyed by canonical path
+                let path = abs_root.join(&depends.file);
+                let path = path.canonicalize().unwrap_or(path);
+                // The innermost code unit holding the reference
+                let Some(unit) = units_by_file.get(&path).and_then(|units| {
+                    units
+                        .iter()
+                        .filter(|u| u.start_line <= depends.line && depends.line <= u.end_line)
+                        .min_by_key(|u| u.end_line - u.start_line)
+                }) else {
+                    continue;
+                };
+                let in_unit = |r: &ApiCodeRef| {
+                    r.file == depends.file && unit.start_line <= r.line && r.line <= unit.end_line
+                };
+                for rule in &forward.rules {
+                    if rule.id != target.id
+                        && rule.impl_refs.iter().chain(&rule.verify_refs).any(in_unit)
+                    {
+                        graph
+                            .entry(rule.id.clone())
+                            .or_default()
+                            .insert(target.id.clone());
+                    }
+                }
+            }
+        }
+        graph
+    }
+
+    /// Compute a `CoverageReport` over the rules for which `keep` returns true.
+    ///
+    /// References to the other rules are dropped rather than counted as
+    /// invalid; this is how coverage is restricted to requirement levels.
+    /// Draft, deprecated and removed rules are always left out.
+    pub fn coverage_report_where(
+        &self,
+        key: &ImplKey,
+        keep: impl Fn(&ApiRule) -> bool,
+    ) -> Option<CoverageReport> {
+        let forward = self.forward_by_impl.get(key)?;
+        let reqs = self.reqs_by_impl.get(key)?;
+        let prefix = &self.config.specs.iter().find(|s| s.name == key.0)?.prefix;
+
+        let known_ids: std::collections::HashSet<String> = forward
+            .rules
+            .iter()
+            // r[impl status.coverage]
+            .filter(|r| r.counts_toward_coverage() && keep(r))
+            .map(|r| r.id.clone())
+            .collect();
+        let dropped_ids: std::collections::HashSet<&str> = forward
+            .rules
+            .iter()
+            .filter(|r| !known_ids.contains(&r.id))
+            .map(|r| r.id.as_str())
+            .collect();
+        let versions: HashMap<&str, u32> = forward
+            .rules
+            .iter()
+            .map(|r| (r.id.as_str(), r.version))
+            .collect();
+        let spec_reqs = Reqs {
+            references: reqs
+                .references
+                .iter()
+                .filter(|r| &r.prefix == prefix)
+                .filter(|r| {
+                    if is_wildcard(&r.req_id) {
+                        // A wildcard over only dropped rules is dropped too
+                        let matches = |id: &str| wildcard_matches(&r.req_id, id);
+                        known_ids.iter().any(|id| matches(id))
+                            || !dropped_ids.iter().any(|id| matches(id))
+                    } else {
+                        !dropped_ids.contains(r.req_id.as_str())
+                    }
+                })
+                // r[impl ref.version.stale]
+                .filter(|r| {
+                    matches!(r.verb, RefVerb::Define | RefVerb::Related)
+                        || versions
+                            .get(r.req_id.as_str())
+                            .is_none_or(|&v| r.version >= v)
+                })
+                .cloned()
+                .collect(),
+            warnings: reqs.warnings.clone(),
+        };
+
+        Some(CoverageReport::compute(&key.0, &known_ids, &spec_reqs))
+    }
 }
 
 /// Escape HTML special characters
-fn html_escape(s: &str) -> String {
+pub(crate) fn html_escape(s: &str) -> String {
     let mut out = String::with_capacity(s.len());
     for c in s.chars() {
         match c {
@@ -92,7 +229,7 @@ fn html_escape(s: &str) -> String {
 /// Coverage status for a rule
 #[derive(Debug, Clone)]
 struct RuleCoverage {
-    status: &'static str, // "covered", "partial", "uncovered"
+    status: &'static str, // "covered", "partial", "uncovered", "stale"
     impl_refs: Vec<ApiCodeRef>,
     verify_refs: Vec<ApiCodeRef>,
 }
@@ -256,7 +393,9 @@ impl ReqHandler for TraceyRuleHandler {
         rule: &'a ReqDefinition,
     ) -> Pin<Box<dyn Future<Output = marq::Result<String>> + Send + 'a>> {
         Box::pin(async move {
-            let coverage = self.coverage.get(&rule.id);
+            // Links and copy target the base ID; only the label shows the version
+            let (base_id, _) = split_version(&rule.id);
+            let coverage = self.coverage.get(base_id);
             let status = coverage.map(|c| c.status).unwrap_or("uncovered");
 
             // Insert <wbr> after dots for better line breaking
@@ -275,8 +414,8 @@ impl ReqHandler for TraceyRuleHandler {
             // Segmented badge group: copy button + requirement ID
             badges_html.push_str(&format!(
                 r#"<div class="req-badge-group"><button class="req-badge req-copy req-segment-left" data-req-id="{}" title="Copy requirement ID"><svg class="req-copy-icon" width="12" height="12" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2" stroke-linecap="round" stroke-linejoin="round"><rect x="9" y="9" width="13" height="13" rx="2" ry="2"></rect><path d="M5 15H4a2 2 0 0 1-2-2V4a2 2 0 0 1 2-2h9a2 2 0 0 1 2 2v1"></path></svg></button><a class="req-badge req-id req-segment-right" href="/{}/{}/spec#r--{}" data-rule="{}" data-source-file="{}" data-source-line="{}" title="{}">{}</a></div>"#,
-                rule.id,
-                self.spec_name, self.impl_name, rule.id, rule.id, source_file, rule.line, rule.id, display_id
+                base_id,
+                self.spec_name, self.impl_name, base_id, base_id, source_file, rule.line, base_id, display_id
             ));
 
             // Implementation badge
@@ -505,9 +644,40 @@ pub async fn build_dashboard_data_with_overlay(
     version: u64,
     quiet: bool,
     overlay: &FileOverlay,
+) -> Result<DashboardData> {
+    build_dashboard_data_cached(
+        project_root,
+        config,
+        version,
+        quiet,
+        overlay,
+        &mut BuildCache::new(),
+    )
+    .await
+}
+
+/// Build dashboard data, reusing per-file results from `cache`.
+///
+/// r[impl daemon.state.extraction-cache]
+///
+/// Only source files whose content (on disk or in the overlay) changed since
+/// the last build are re-parsed, and spec markdown is only re-rendered when a
+/// spec file or the coverage shown in it changed. Every file is still read and
+/// the forward/reverse data is assembled from scratch; see
+/// [`patch_dashboard_data`] for updating a build for a few changed files.
+pub async fn build_dashboard_data_cached(
+    project_root: &Path,
+    config: &Config,
+    version: u64,
+    quiet: bool,
+    overlay: &FileOverlay,
+    cache: &mut BuildCache,
 ) -> Result<DashboardData> {
     use tracey_core::WalkSources;
 
+    cache.begin();
+    cache.set_extract_options(&crate::config::extract_options(config));
+
     let abs_root = project_root
         .canonicalize()
         .unwrap_or_else(|_| project_root.to_path_buf());
@@ -519,11 +689,14 @@ pub async fn build_dashboard_data_with_overlay(
 
     let mut forward_by_impl: BTreeMap<ImplKey, ApiSpecForward> = BTreeMap::new();
     let mut reverse_by_impl: BTreeMap<ImplKey, ApiReverseData> = BTreeMap::new();
+    let mut reqs_by_impl: BTreeMap<ImplKey, Reqs> = BTreeMap::new();
     let mut code_units_by_impl: BTreeMap<ImplKey, BTreeMap<PathBuf, Vec<CodeUnit>>> =
         BTreeMap::new();
     let mut specs_content_by_impl: BTreeMap<ImplKey, ApiSpecData> = BTreeMap::new();
+    let mut test_results_by_impl: BTreeMap<ImplKey, TestResults> = BTreeMap::new();
     let mut all_file_contents: BTreeMap<PathBuf, String> = BTreeMap::new();
     let mut all_search_rules: Vec<search::RuleEntry> = Vec::new();
+    let git_status = get_git_status(project_root);
 
     // r[impl config.impl.test_include]
     // Collect all test file patterns and find matching files
@@ -586,6 +759,7 @@ pub async fn build_dashboard_data_with_overlay(
                 spec_config.prefix
             ));
         }
+        crate::config::validate_verbs(spec_config)?;
 
         api_config.specs.push(ApiSpecInfo {
             name: spec_name.clone(),
@@ -593,6 +767,14 @@ pub async fn build_dashboard_data_with_overlay(
             source: Some(include_patterns.join(", ")),
             source_url: spec_config.source_url.clone(),
             implementations: spec_config.impls.iter().map(|i| i.name.clone()).collect(),
+            verbs: spec_config
+                .verbs
+                .iter()
+                .map(|v| ApiVerb {
+                    name: v.name.clone(),
+                    counts_as: v.counts_as.clone(),
+                })
+                .collect(),
         });
 
         // Extract requirements directly from markdown files (shared across impls)
@@ -604,7 +786,8 @@ pub async fn build_dashboard_data_with_overlay(
             );
         }
         let extracted_rules =
-            crate::load_rules_from_globs(project_root, &include_patterns, quiet).await?;
+            crate::load_rules_from_globs_cached(project_root, &include_patterns, quiet, cache)
+                .await?;
 
         // Build data for each implementation
         for impl_config in &spec_config.impls {
@@ -626,85 +809,38 @@ pub async fn build_dashboard_data_with_overlay(
 
             // r[impl ref.cross-workspace.paths]
             // Extract requirement references from this impl's source files
-            let extraction_result = Reqs::extract(
-                WalkSources::new(project_root)
-                    .include(include.clone())
-                    .exclude(exclude.clone()),
-            )?;
+            let walked = WalkSources::new(project_root)
+                .include(include.clone())
+                .exclude(exclude.clone())
+                .files();
 
             // r[impl ref.cross-workspace.cli-warnings]
             // Print warnings for missing cross-workspace paths
-            for warning in &extraction_result.warnings {
+            for warning in &walked.warnings {
                 if !quiet {
                     eprintln!("{}", warning.yellow());
                 }
             }
 
-            let reqs = extraction_result.reqs;
-
-            // Build forward data for this impl
-            let mut api_rules = Vec::new();
-            for extracted in &extracted_rules {
-                let mut impl_refs = Vec::new();
-                let mut verify_refs = Vec::new();
-                let mut depends_refs = Vec::new();
-
-                for r in &reqs.references {
-                    // r[impl ref.prefix.coverage]
-                    if r.prefix == spec_config.prefix && r.req_id == extracted.def.id {
-                        // r[impl ref.cross-workspace.graceful]
-                        // Canonicalize the reference file path for consistent matching
-                        // Uses unwrap_or_else to gracefully handle missing files
-                        let canonical_ref =
-                            r.file.canonicalize().unwrap_or_else(|_| r.file.clone());
-
-                        // Compute relative path, preserving ../ for cross-workspace files
-                        let relative_display =
-                            if let Ok(rel) = canonical_ref.strip_prefix(&abs_root) {
-                                rel.display().to_string()
-                            } else {
-                                // Cross-workspace file: compute relative path from abs_root
-                                compute_relative_path(&abs_root, &canonical_ref)
-                            };
-
-                        let code_ref = ApiCodeRef {
-                            file: relative_display,
-                            line: r.line,
-                        };
-                        match r.verb {
-                            RefVerb::Impl | RefVerb::Define => impl_refs.push(code_ref),
-                            RefVerb::Verify => verify_refs.push(code_ref),
-                            RefVerb::Depends | RefVerb::Related => depends_refs.push(code_ref),
-                        }
-                    }
+            let mut reqs = Reqs::new();
+            for path in &walked.files {
+                if let Ok(content) = read_file_with_overlay(path, overlay).await {
+                    reqs.extend(cache.source_reqs(path, &content));
                 }
-
-                api_rules.push(ApiRule {
-                    id: extracted.def.id.clone(),
-                    raw: extracted.def.raw.clone(),
-                    html: extracted.def.html.clone(),
-                    status: extracted
-                        .def
-                        .metadata
-                        .status
-                        .map(|s| s.as_str().to_string()),
-                    level: extracted.def.metadata.level.map(|l| l.as_str().to_string()),
-                    source_file: Some(extracted.source_file.clone()),
-                    source_line: Some(extracted.def.line),
-                    source_column: extracted.column,
-                    section: extracted.section.clone(),
-                    section_title: extracted.section_title.clone(),
-                    impl_refs,
-                    verify_refs,
-                    depends_refs,
-                });
             }
 
-            // Sort rules by ID
-            api_rules.sort_by(|a, b| a.id.cmp(&b.id));
+            // Build forward data for this impl
+            let forward = impl_forward(
+                spec_name,
+                &spec_config.prefix,
+                impl_config,
+                &extracted_rules,
+                &reqs,
+                &abs_root,
+            )?;
 
             // Collect rules for search index (deduplicated later)
-            for r in &api_rules {
+            for r in &forward.rules {
                 all_search_rules.push(search::RuleEntry {
                     id: r.id.clone(),
                     raw: r.raw.clone(),
@@ -712,26 +848,7 @@ pub async fn build_dashboard_data_with_overlay(
             }
 
             // Build coverage map for this impl
-            let mut coverage: BTreeMap<String, RuleCoverage> = BTreeMap::new();
-            for rule in &api_rules {
-                let has_impl = !rule.impl_refs.is_empty();
-                let has_verify = !rule.verify_refs.is_empty();
-                let status = if has_impl && has_verify {
-                    "covered"
-                } else if has_impl || has_verify {
-                    "partial"
-                } else {
-                    "uncovered"
-                };
-                coverage.insert(
-                    rule.id.clone(),
-                    RuleCoverage {
-                        status,
-                        impl_refs: rule.impl_refs.clone(),
-                        verify_refs: rule.verify_refs.clone(),
-                    },
-                );
-            }
+            let coverage = coverage_map(&forward.rules);
 
             // Load spec content with coverage-aware rendering for this impl
             let mut impl_specs_content: BTreeMap<String, ApiSpecData> = BTreeMap::new();
@@ -743,19 +860,15 @@ pub async fn build_dashboard_data_with_overlay(
                 &coverage,
                 &mut impl_specs_content,
                 overlay,
+                &git_status,
+                cache,
             )
             .await?;
             if let Some(spec_data) = impl_specs_content.remove(spec_name) {
                 specs_content_by_impl.insert(impl_key.clone(), spec_data);
             }
 
-            forward_by_impl.insert(
-                impl_key.clone(),
-                ApiSpecForward {
-                    name: spec_name.clone(),
-                    rules: api_rules,
-                },
-            );
+            forward_by_impl.insert(impl_key.clone(), forward);
 
             // Extract code units for reverse traceability
             let mut impl_code_units: BTreeMap<PathBuf, Vec<CodeUnit>> = BTreeMap::new();
@@ -766,32 +879,18 @@ pub async fn build_dashboard_data_with_overlay(
 
             // Helper to process a file
             let mut process_file = async |path: &Path, root: &Path, patterns: &[&String]| {
-                if path.extension().is_some_and(is_supported_extension) {
-                    let relative = path.strip_prefix(root).unwrap_or(path);
-                    let relative_str = relative.to_string_lossy();
-
-                    let included = patterns
-                        .iter()
-                        .any(|pattern| glob_match(&relative_str, pattern));
-
-                    let excluded = exclude
-                        .iter()
-                        .any(|pattern| glob_match(&relative_str, pattern));
-
-                    if included
-                        && !excluded
-                        && let Ok(content) = read_file_with_overlay(path, overlay).await
-                    {
-                        // Use canonicalized path as key for consistent lookups
-                        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
-
-                        let code_units = tracey_core::code_units::extract(path, &content);
-                        if !code_units.is_empty() {
-                            impl_code_units.insert(canonical.clone(), code_units.units);
-                        }
-                        // Also collect for search index
-                        all_file_contents.insert(canonical, content);
+                if is_code_unit_file(path, root, patterns, &exclude)
+                    && let Ok(content) = read_file_with_overlay(path, overlay).await
+                {
+                    // Use canonicalized path as key for consistent lookups
+                    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+                    let code_units = cache.source_code_units(path, &content);
+                    if !code_units.is_empty() {
+                        impl_code_units.insert(canonical.clone(), code_units);
                     }
+                    // Also collect for search index
+                    all_file_contents.insert(canonical, content);
                 }
             };
 
@@ -846,43 +945,28 @@ pub async fn build_dashboard_data_with_overlay(
             }
 
             // Build reverse data for this impl
-            let mut total_units = 0;
-            let mut covered_units = 0;
-            let mut file_entries = Vec::new();
-
-            for (path, units) in &impl_code_units {
-                // Compute relative path, preserving ../ for cross-workspace files
-                let relative_display = if let Ok(rel) = path.strip_prefix(&abs_root) {
-                    rel.display().to_string()
-                } else {
-                    compute_relative_path(&abs_root, path)
-                };
-
-                let file_total = units.len();
-                let file_covered = units.iter().filter(|u| !u.req_refs.is_empty()).count();
-
-                total_units += file_total;
-                covered_units += file_covered;
+            reverse_by_impl.insert(impl_key.clone(), reverse_data(&abs_root, &impl_code_units));
 
-                file_entries.push(ApiFileEntry {
-                    path: relative_display,
-                    total_units: file_total,
-                    covered_units: file_covered,
-                });
+            // r[impl test-results.status]
+            // Map verify references to test outcomes, if this impl has test results
+            if !impl_config.test_results.is_empty() {
+                let (results, warnings) =
+                    TestResults::load(project_root, &impl_config.test_results);
+                if !quiet {
+                    for warning in &warnings {
+                        eprintln!("{}", warning.yellow());
+                    }
+                }
+                if let Some(forward) = forward_by_impl.get_mut(&impl_key) {
+                    for rule in &mut forward.rules {
+                        set_verify_status(rule, &results, &abs_root, &impl_code_units);
+                    }
+                }
+                test_results_by_impl.insert(impl_key.clone(), results);
             }
 
-            file_entries.sort_by(|a, b| a.path.cmp(&b.path));
-
-            reverse_by_impl.insert(
-                impl_key.clone(),
-                ApiReverseData {
-                    total_units,
-                    covered_units,
-                    files: file_entries,
-                },
-            );
-
-            code_units_by_impl.insert(impl_key, impl_code_units);
+            code_units_by_impl.insert(impl_key.clone(), impl_code_units);
+            reqs_by_impl.insert(impl_key, reqs);
         }
     }
 
@@ -890,24 +974,25 @@ pub async fn build_dashboard_data_with_overlay(
     all_search_rules.sort_by(|a, b| a.id.cmp(&b.id));
     all_search_rules.dedup_by(|a, b| a.id == b.id);
 
-    // Build search index with all sources and rules
-    let search_index = search::build_index(project_root, &all_file_contents, &all_search_rules);
+    // Build search index with all sources and rules, re-indexing only what changed
+    let search_index = cache.search_index(project_root, &all_file_contents, &all_search_rules);
 
     // Compute content hash for change detection (hash all forward/reverse data)
     let mut content_hash: u64 = 0;
     for (key, forward) in &forward_by_impl {
-        let json = facet_json::to_string(forward).unwrap_or_default();
-        content_hash ^= simple_hash(&format!("{:?}:{}", key, json));
+        content_hash ^= impl_hash(key, forward);
     }
     for (key, reverse) in &reverse_by_impl {
-        let json = facet_json::to_string(reverse).unwrap_or_default();
-        content_hash ^= simple_hash(&format!("{:?}:{}", key, json));
+        content_hash ^= impl_hash(key, reverse);
     }
 
+    cache.finish();
+
     Ok(DashboardData {
         config: api_config,
         forward_by_impl,
         reverse_by_impl,
+        reqs_by_impl,
         code_units_by_impl,
         specs_content_by_impl,
         search_index,
@@ -915,11 +1000,513 @@ pub async fn build_dashboard_data_with_overlay(
         content_hash,
         delta: crate::server::Delta::default(),
         test_files,
+        test_results_by_impl,
+    })
+}
+
+/// Update `previous` for changes to the content of the `changed` files,
+/// without walking the project.
+///
+/// r[impl daemon.state.incremental-rebuild]
+///
+/// Only the changed files are re-extracted (from the overlay if they're open
+/// there), and only the rules they referenced before or after the change get
+/// their references re-attached. Returns `Ok(None)` when the change needs a
+/// full [`build_dashboard_data_cached`] instead: a changed file is a spec or
+/// test results file, lies outside the project root, can't be read, or
+/// wasn't read by the previous build.
+pub async fn patch_dashboard_data(
+    previous: &DashboardData,
+    project_root: &Path,
+    config: &Config,
+    version: u64,
+    overlay: &FileOverlay,
+    changed: &[PathBuf],
+    cache: &mut BuildCache,
+) -> Result<Option<DashboardData>> {
+    use tracey_core::WalkSources;
+
+    cache.reset_stats();
+
+    let abs_root = project_root
+        .canonicalize()
+        .unwrap_or_else(|_| project_root.to_path_buf());
+    let test_results_files: Vec<PathBuf> = config
+        .specs
+        .iter()
+        .flat_map(|s| &s.impls)
+        .flat_map(|i| &i.test_results)
+        .map(|p| {
+            let path = project_root.join(p);
+            path.canonicalize().unwrap_or(path)
+        })
+        .collect();
+
+    // (path as walked from the project root, canonical path, content)
+    let mut files: Vec<(PathBuf, PathBuf, String)> = Vec::new();
+    for path in changed {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if crate::is_spec_file(config, project_root, path)
+            || test_results_files.contains(&canonical)
+        {
+            return Ok(None);
+        }
+        let Ok(relative) = canonical.strip_prefix(&abs_root) else {
+            return Ok(None);
+        };
+        let Ok(content) = read_file_with_overlay(path, overlay).await else {
+            return Ok(None);
+        };
+        let walk_path = project_root.join(relative);
+        files.push((walk_path, canonical, content));
+    }
+
+    let mut forward_by_impl = previous.forward_by_impl.clone();
+    let mut reverse_by_impl = previous.reverse_by_impl.clone();
+    let mut reqs_by_impl = previous.reqs_by_impl.clone();
+    let mut code_units_by_impl = previous.code_units_by_impl.clone();
+    let mut specs_content_by_impl = previous.specs_content_by_impl.clone();
+    let mut content_hash = previous.content_hash;
+    let mut search_files: BTreeMap<&Path, &str> = BTreeMap::new();
+    let mut git_status = None;
+
+    for spec_config in &config.specs {
+        let include_patterns: Vec<&str> = spec_config.include.iter().map(|i| i.as_str()).collect();
+
+        for impl_config in &spec_config.impls {
+            let impl_key: ImplKey = (spec_config.name.clone(), impl_config.name.clone());
+
+            // r[impl walk.default-include]
+            let include: Vec<String> = if impl_config.include.is_empty() {
+                vec!["**/*.rs".to_string()]
+            } else {
+                impl_config.include.to_vec()
+            };
+            let exclude: Vec<String> = impl_config.exclude.to_vec();
+            let walk = WalkSources::new(project_root)
+                .include(include.clone())
+                .exclude(exclude.clone());
+            let local_includes: Vec<&String> =
+                include.iter().filter(|p| !p.starts_with("../")).collect();
+
+            // (walk path, canonical path, content, scanned for references,
+            // scanned for code units)
+            let relevant: Vec<(&PathBuf, &PathBuf, &String, bool, bool)> = files
+                .iter()
+                .filter_map(|(walk_path, canonical, content)| {
+                    let relative = walk_path.strip_prefix(project_root).unwrap_or(walk_path);
+                    let has_refs = walk.matches(relative);
+                    let has_units =
+                        is_code_unit_file(walk_path, project_root, &local_includes, &exclude);
+                    (has_refs || has_units)
+                        .then_some((walk_path, canonical, content, has_refs, has_units))
+                })
+                .collect();
+            if relevant.is_empty() {
+                continue;
+            }
+            // A new file (or one the walk skipped) changes the file set
+            if relevant
+                .iter()
+                .any(|(walk_path, ..)| !cache.has_source(walk_path))
+            {
+                return Ok(None);
+            }
+
+            let (Some(forward), Some(reverse), Some(reqs), Some(impl_code_units)) = (
+                forward_by_impl.get_mut(&impl_key),
+                reverse_by_impl.get_mut(&impl_key),
+                reqs_by_impl.get_mut(&impl_key),
+                code_units_by_impl.get_mut(&impl_key),
+            ) else {
+                return Ok(None);
+            };
+            content_hash ^= impl_hash(&impl_key, &*forward) ^ impl_hash(&impl_key, &*reverse);
+            let old_coverage = format!("{:?}", coverage_map(&forward.rules));
+
+            // Rules referenced from the changed files, before or after the change
+            let mut affected: BTreeSet<String> = BTreeSet::new();
+            for &(walk_path, canonical, content, has_refs, has_units) in &relevant {
+                if has_refs {
+                    let file = canonical
+                        .strip_prefix(&abs_root)
+                        .unwrap_or(canonical)
+                        .display()
+                        .to_string();
+                    affected.extend(
+                        forward
+                            .rules
+                            .iter()
+                            .filter(|rule| has_ref_in(rule, &file))
+                            .map(|rule| rule.id.clone()),
+                    );
+
+                    let file_reqs = cache.source_reqs(walk_path, content);
+                    affected.extend(
+                        forward
+                            .rules
+                            .iter()
+                            .filter(|rule| {
+                                file_reqs
+                                    .references
+                                    .iter()
+                                    .any(|r| refers_to(r, rule, &spec_config.prefix))
+                            })
+                            .map(|rule| rule.id.clone()),
+                    );
+
+                    // Keep references in file order, as the walk does
+                    reqs.references.retain(|r| r.file != *walk_path);
+                    let at = reqs.references.partition_point(|r| r.file < *walk_path);
+                    reqs.references.splice(at..at, file_reqs.references);
+                    reqs.warnings.retain(|w| w.file != *walk_path);
+                    let at = reqs.warnings.partition_point(|w| w.file < *walk_path);
+                    reqs.warnings.splice(at..at, file_reqs.warnings);
+                }
+                if has_units {
+                    let code_units = cache.source_code_units(walk_path, content);
+                    if code_units.is_empty() {
+                        impl_code_units.remove(canonical);
+                    } else {
+                        impl_code_units.insert(canonical.clone(), code_units);
+                    }
+                    search_files.insert(canonical, content);
+                }
+            }
+
+            let test_results = previous.test_results_by_impl.get(&impl_key);
+            for rule in forward
+                .rules
+                .iter_mut()
+                .filter(|rule| affected.contains(&rule.id))
+            {
+                clear_refs(rule);
+                attach_refs(rule, &reqs.references, &spec_config.prefix, &abs_root);
+                if let Some(results) = test_results {
+                    set_verify_status(rule, results, &abs_root, impl_code_units);
+                }
+            }
+            *reverse = reverse_data(&abs_root, impl_code_units);
+            content_hash ^= impl_hash(&impl_key, &*forward) ^ impl_hash(&impl_key, &*reverse);
+
+            // Spec markdown only needs re-rendering if the coverage it shows changed
+            let coverage = coverage_map(&forward.rules);
+            if format!("{:?}", coverage) != old_coverage {
+                let git_status = git_status.get_or_insert_with(|| get_git_status(project_root));
+                let mut impl_specs_content: BTreeMap<String, ApiSpecData> = BTreeMap::new();
+                load_spec_content(
+                    project_root,
+                    &include_patterns,
+                    &spec_config.name,
+                    &impl_config.name,
+                    &coverage,
+                    &mut impl_specs_content,
+                    overlay,
+                    git_status,
+                    cache,
+                )
+                .await?;
+                match impl_specs_content.remove(&spec_config.name) {
+                    Some(spec_data) => specs_content_by_impl.insert(impl_key, spec_data),
+                    None => specs_content_by_impl.remove(&impl_key),
+                };
+            }
+        }
+    }
+
+    let search_files: Vec<(&Path, &str)> = search_files.into_iter().collect();
+    cache.update_search_files(project_root, &search_files);
+
+    Ok(Some(DashboardData {
+        config: previous.config.clone(),
+        forward_by_impl,
+        reverse_by_impl,
+        reqs_by_impl,
+        code_units_by_impl,
+        specs_content_by_impl,
+        search_index: Arc::clone(&previous.search_index),
+        version,
+        content_hash,
+        delta: crate::server::Delta::default(),
+        test_files: previous.test_files.clone(),
+        test_results_by_impl: previous.test_results_by_impl.clone(),
+    }))
+}
+
+/// Forward data for one impl: `rules` with the references in `reqs` attached,
+/// sorted by ID.
+///
+/// The full build and `tracey history` backfill both go through here, so
+/// their headline coverage agrees.
+pub(crate) fn impl_forward(
+    spec_name: &str,
+    prefix: &str,
+    impl_config: &crate::config::Impl,
+    rules: &[ExtractedRule],
+    reqs: &Reqs,
+    abs_root: &Path,
+) -> Result<ApiSpecForward> {
+    let mut api_rules = Vec::new();
+    for extracted in rules {
+        let mut rule = ApiRule {
+            id: extracted.def.id.clone(),
+            version: extracted.version,
+            raw: extracted.def.raw.clone(),
+            html: extracted.def.html.clone(),
+            status: extracted
+                .def
+                .metadata
+                .status
+                .map(|s| s.as_str().to_string()),
+            // r[impl level.effective]
+            level: crate::level::effective(
+                extracted.def.metadata.level.as_ref().map(|l| l.as_str()),
+                &extracted.def.raw,
+            )
+            .map(String::from),
+            source_file: Some(extracted.source_file.clone()),
+            source_line: Some(extracted.def.line),
+            source_column: extracted.column,
+            section: extracted.section.clone(),
+            section_title: extracted.section_title.clone(),
+            impl_refs: Vec::new(),
+            verify_refs: Vec::new(),
+            depends_refs: Vec::new(),
+            stale_refs: Vec::new(),
+            // Filled in once code units are known
+            verify_status: None,
+            custom_refs: Vec::new(),
+        };
+        attach_refs(&mut rule, &reqs.references, prefix, abs_root);
+        api_rules.push(rule);
+    }
+
+    // Sort rules by ID
+    api_rules.sort_by(|a, b| a.id.cmp(&b.id));
+
+    Ok(ApiSpecForward {
+        name: spec_name.to_string(),
+        rules: api_rules,
+        // r[impl level.headline]
+        exclude_levels: impl_config
+            .exclude_levels
+            .iter()
+            .map(|l| crate::level::parse(l).map(String::from))
+            .collect::<Result<_>>()?,
     })
 }
 
+/// Whether `r` refers to `rule`, in a spec with the given prefix.
+fn refers_to(r: &ReqReference, rule: &ApiRule, prefix: &str) -> bool {
+    // r[impl ref.prefix.coverage]
+    if r.prefix != prefix {
+        return false;
+    }
+    // r[impl ref.wildcard.expand]
+    // Wildcards don't pick up deprecated or removed rules: only
+    // explicit references to those get flagged
+    if is_wildcard(&r.req_id) {
+        let retired = matches!(
+            rule.status.as_deref(),
+            Some(tracey_api::STATUS_DEPRECATED | tracey_api::STATUS_REMOVED)
+        );
+        !retired && wildcard_matches(&r.req_id, &rule.id)
+    } else {
+        r.req_id == rule.id
+    }
+}
+
+/// Attach the references in `refs` that refer to `rule` to it, in order.
+fn attach_refs(rule: &mut ApiRule, refs: &[ReqReference], prefix: &str, abs_root: &Path) {
+    for r in refs {
+        if !refers_to(r, rule, prefix) {
+            continue;
+        }
+        // r[impl ref.cross-workspace.graceful]
+        // Canonicalize the reference file path for consistent matching
+        // Uses unwrap_or_else to gracefully handle missing files
+        let canonical_ref = r.file.canonicalize().unwrap_or_else(|_| r.file.clone());
+
+        // Compute relative path, preserving ../ for cross-workspace files
+        let relative_display = if let Ok(rel) = canonical_ref.strip_prefix(abs_root) {
+            rel.display().to_string()
+        } else {
+            // Cross-workspace file: compute relative path from abs_root
+            compute_relative_path(abs_root, &canonical_ref)
+        };
+
+        let code_ref = ApiCodeRef {
+            file: relative_display,
+            line: r.line,
+        };
+        // r[impl ref.version.stale]
+        // References to an older version don't count as coverage
+        // (a wildcard can't pin the version of every rule it matches)
+        if r.version < rule.version
+            && !is_wildcard(&r.req_id)
+            && !matches!(r.verb, RefVerb::Define | RefVerb::Related)
+        {
+            rule.stale_refs.push(code_ref);
+            continue;
+        }
+        // r[impl ref.verb.custom]
+        if let Some(verb) = &r.custom_verb {
+            match rule.custom_refs.iter_mut().find(|c| &c.verb == verb) {
+                Some(column) => column.refs.push(code_ref.clone()),
+                None => rule.custom_refs.push(ApiVerbRefs {
+                    verb: verb.clone(),
+                    refs: vec![code_ref.clone()],
+                }),
+            }
+            match r.verb {
+                RefVerb::Impl => rule.impl_refs.push(code_ref),
+                RefVerb::Verify => rule.verify_refs.push(code_ref),
+                _ => {}
+            }
+            continue;
+        }
+        match r.verb {
+            RefVerb::Impl | RefVerb::Define => rule.impl_refs.push(code_ref),
+            RefVerb::Verify => rule.verify_refs.push(code_ref),
+            RefVerb::Depends | RefVerb::Related => rule.depends_refs.push(code_ref),
+        }
+    }
+}
+
+/// Drop everything [`attach_refs`] and [`set_verify_status`] put on `rule`.
+fn clear_refs(rule: &mut ApiRule) {
+    rule.impl_refs.clear();
+    rule.verify_refs.clear();
+    rule.depends_refs.clear();
+    rule.stale_refs.clear();
+    rule.custom_refs.clear();
+    rule.verify_status = None;
+}
+
+/// Whether any of `rule`'s references is in `file` (relative to the project root).
+fn has_ref_in(rule: &ApiRule, file: &str) -> bool {
+    rule.impl_refs
+        .iter()
+        .chain(&rule.verify_refs)
+        .chain(&rule.depends_refs)
+        .chain(&rule.stale_refs)
+        .chain(rule.custom_refs.iter().flat_map(|c| &c.refs))
+        .any(|r| r.file == file)
+}
+
+/// Coverage shown for each rule when rendering spec markdown.
+fn coverage_map(rules: &[ApiRule]) -> BTreeMap<String, RuleCoverage> {
+    let mut coverage: BTreeMap<String, RuleCoverage> = BTreeMap::new();
+    for rule in rules {
+        let has_impl = !rule.impl_refs.is_empty();
+        let has_verify = !rule.verify_refs.is_empty();
+        let status = if rule.is_stale() {
+            "stale"
+        } else if has_impl && has_verify {
+            "covered"
+        } else if has_impl || has_verify {
+            "partial"
+        } else {
+            "uncovered"
+        };
+        coverage.insert(
+            rule.id.clone(),
+            RuleCoverage {
+                status,
+                impl_refs: rule.impl_refs.clone(),
+                verify_refs: rule.verify_refs.clone(),
+            },
+        );
+    }
+    coverage
+}
+
+// r[impl test-results.status]
+/// Map `rule`'s verify references to test outcomes.
+fn set_verify_status(
+    rule: &mut ApiRule,
+    results: &TestResults,
+    abs_root: &Path,
+    code_units: &BTreeMap<PathBuf, Vec<CodeUnit>>,
+) {
+    let refs: Vec<(PathBuf, usize)> = rule
+        .verify_refs
+        .iter()
+        .map(|r| {
+            let path = abs_root.join(&r.file);
+            (path.canonicalize().unwrap_or(path), r.line)
+        })
+        .collect();
+    rule.verify_status = results.verify_status(
+        &rule.id,
+        refs.iter().map(|(path, line)| (path.as_path(), *line)),
+        |path| code_units.get(path).map(Vec::as_slice),
+    );
+}
+
+/// Whether `path` is scanned for code units (and indexed for search) under
+/// an implementation's local include patterns.
+fn is_code_unit_file(path: &Path, root: &Path, patterns: &[&String], exclude: &[String]) -> bool {
+    if !path.extension().is_some_and(is_supported_extension) {
+        return false;
+    }
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    let relative_str = relative.to_string_lossy();
+
+    let included = patterns
+        .iter()
+        .any(|pattern| glob_match(&relative_str, pattern));
+    let excluded = exclude
+        .iter()
+        .any(|pattern| glob_match(&relative_str, pattern));
+    included && !excluded
+}
+
+/// Reverse data (code unit coverage per file) for an implementation.
+fn reverse_data(abs_root: &Path, code_units: &BTreeMap<PathBuf, Vec<CodeUnit>>) -> ApiReverseData {
+    let mut total_units = 0;
+    let mut covered_units = 0;
+    let mut file_entries = Vec::new();
+
+    for (path, units) in code_units {
+        // Compute relative path, preserving ../ for cross-workspace files
+        let relative_display = if let Ok(rel) = path.strip_prefix(abs_root) {
+            rel.display().to_string()
+        } else {
+            compute_relative_path(abs_root, path)
+        };
+
+        let file_total = units.len();
+        let file_covered = units.iter().filter(|u| !u.req_refs.is_empty()).count();
+
+        total_units += file_total;
+        covered_units += file_covered;
+
+        file_entries.push(ApiFileEntry {
+            path: relative_display,
+            total_units: file_total,
+            covered_units: file_covered,
+        });
+    }
+
+    file_entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+    ApiReverseData {
+        total_units,
+        covered_units,
+        files: file_entries,
+    }
+}
+
+/// Hash of one implementation's forward or reverse data; the content hash
+/// of a build is all of these XORed together.
+fn impl_hash<'a, T: facet::Facet<'a>>(key: &ImplKey, value: &'a T) -> u64 {
+    let json = facet_json::to_string(value).unwrap_or_default();
+    simple_hash(&format!("{:?}:{}", key, json))
+}
+
 /// Simple FNV-1a hash for change detection
-fn simple_hash(s: &str) -> u64 {
+pub(crate) fn simple_hash(s: &str) -> u64 {
     let mut hash: u64 = 0xcbf29ce484222325;
     for byte in s.bytes() {
         hash ^= byte as u64;
@@ -928,6 +1515,7 @@ fn simple_hash(s: &str) -> u64 {
     hash
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn load_spec_content(
     root: &Path,
     patterns: &[&str],
@@ -936,34 +1524,11 @@ async fn load_spec_content(
     coverage: &BTreeMap<String, RuleCoverage>,
     specs_content: &mut BTreeMap<String, ApiSpecData>,
     overlay: &FileOverlay,
+    git_status: &HashMap<String, GitStatus>,
+    cache: &mut BuildCache,
 ) -> Result<()> {
     use ignore::WalkBuilder;
 
-    // Shared source file tracker for rule handler
-    let current_source_file = Arc::new(Mutex::new(String::new()));
-
-    // Get git status for files in the project
-    let git_status = get_git_status(root);
-
-    // Set up marq handlers for consistent rendering with coverage-aware rule rendering
-    let rule_handler = TraceyRuleHandler::new(
-        coverage.clone(),
-        Arc::clone(&current_source_file),
-        spec_name.to_string(),
-        impl_name.to_string(),
-        root.to_path_buf(),
-        git_status,
-    );
-    let inline_code_handler =
-        TraceyInlineCodeHandler::new(spec_name.to_string(), impl_name.to_string());
-    let opts = RenderOptions::new()
-        .with_default_handler(ArboriumHandler::new().with_language_header(true))
-        .with_handler(&["aasvg"], AasvgHandler::new())
-        .with_handler(&["pikchr"], PikruHandler::new())
-        .with_handler(&["compare"], CompareHandler::new())
-        .with_req_handler(rule_handler)
-        .with_inline_code_handler(inline_code_handler);
-
     // Collect all matching files with their content and weight
     let mut files: Vec<(String, String, i32)> = Vec::new(); // (relative_path, content, weight)
 
@@ -1002,6 +1567,49 @@ async fn load_spec_content(
     // Sort by weight
     files.sort_by_key(|(_, _, weight)| *weight);
 
+    // Everything the rendered output depends on: the spec files, the coverage
+    // shown for each requirement, and the git status of the spec files.
+    let mut render_key = format!("{:?}\n", coverage);
+    for (source_file, content, weight) in &files {
+        render_key.push_str(&format!(
+            "{}:{}:{:?}:{:x}\n",
+            source_file,
+            weight,
+            git_status.get(source_file),
+            simple_hash(content)
+        ));
+    }
+    let render_hash = simple_hash(&render_key);
+    let impl_key: ImplKey = (spec_name.to_string(), impl_name.to_string());
+    if let Some(cached) = cache.rendered_spec(&impl_key, render_hash) {
+        if let Some(spec_data) = cached {
+            specs_content.insert(spec_name.to_string(), spec_data);
+        }
+        return Ok(());
+    }
+
+    // Shared source file tracker for rule handler
+    let current_source_file = Arc::new(Mutex::new(String::new()));
+
+    // Set up marq handlers for consistent rendering with coverage-aware rule rendering
+    let rule_handler = TraceyRuleHandler::new(
+        coverage.clone(),
+        Arc::clone(&current_source_file),
+        spec_name.to_string(),
+        impl_name.to_string(),
+        root.to_path_buf(),
+        git_status.clone(),
+    );
+    let inline_code_handler =
+        TraceyInlineCodeHandler::new(spec_name.to_string(), impl_name.to_string());
+    let opts = RenderOptions::new()
+        .with_default_handler(ArboriumHandler::new().with_language_header(true))
+        .with_handler(&["aasvg"], AasvgHandler::new())
+        .with_handler(&["pikchr"], PikruHandler::new())
+        .with_handler(&["compare"], CompareHandler::new())
+        .with_req_handler(rule_handler)
+        .with_inline_code_handler(inline_code_handler);
+
     // Concatenate all markdown files to render as one document
     // This ensures heading IDs are hierarchical across all files
     let mut combined_markdown = String::new();
@@ -1040,14 +1648,15 @@ async fn load_spec_content(
     let outline = build_outline(&all_elements, coverage);
 
     if !sections.is_empty() {
-        specs_content.insert(
-            spec_name.to_string(),
-            ApiSpecData {
-                name: spec_name.to_string(),
-                sections,
-                outline,
-            },
-        );
+        let spec_data = ApiSpecData {
+            name: spec_name.to_string(),
+            sections,
+            outline,
+        };
+        cache.insert_rendered_spec(&impl_key, render_hash, Some(spec_data.clone()));
+        specs_content.insert(spec_name.to_string(), spec_data);
+    } else {
+        cache.insert_rendered_spec(&impl_key, render_hash, None);
     }
 
     Ok(())
@@ -1078,10 +1687,14 @@ fn build_outline(
                 current_heading_idx = Some(entries.len() - 1);
             }
             DocElement::Req(r) => {
-                if let Some(idx) = current_heading_idx {
-                    let cov = coverage.get(&r.id);
+                let status = r.metadata.status.as_ref().map(|s| s.as_str());
+                if let Some(idx) = current_heading_idx
+                    && tracey_api::status_counts_toward_coverage(status)
+                {
+                    let cov = coverage.get(split_version(&r.id).0);
                     let has_impl = cov.is_some_and(|c| !c.impl_refs.is_empty());
                     let has_verify = cov.is_some_and(|c| !c.verify_refs.is_empty());
+                    let is_stale = cov.is_some_and(|c| c.status == "stale");
 
                     entries[idx].coverage.total += 1;
                     if has_impl {
@@ -1090,6 +1703,9 @@ fn build_outline(
                     if has_verify {
                         entries[idx].coverage.verify_count += 1;
                     }
+                    if is_stale {
+                        entries[idx].coverage.stale_count += 1;
+                    }
                 }
             }
             DocElement::Paragraph(_) => {
@@ -1123,6 +1739,7 @@ fn build_outline(
                 entries[i].aggregated.total += child_agg.total;
                 entries[i].aggregated.impl_count += child_agg.impl_count;
                 entries[i].aggregated.verify_count += child_agg.verify_count;
+                entries[i].aggregated.stale_count += child_agg.stale_count;
             }
             j += 1;
         }
@@ -1131,6 +1748,53 @@ fn build_outline(
     entries
 }
 
+/// Build the requirement ID tree: one entry per dotted ID prefix, depth-first.
+///
+/// r[impl tree.rollup]
+///
+/// Like [`build_outline`] does for headings, each entry's aggregated coverage
+/// includes every requirement under its prefix. Draft, deprecated and removed
+/// requirements are left out.
+pub fn build_rule_tree<'r>(rules: impl IntoIterator<Item = &'r ApiRule>) -> Vec<ApiRuleTreeEntry> {
+    // Keyed by segments rather than the joined ID so that `a.b` sorts right
+    // after `a` and before `a-c`
+    let mut entries: BTreeMap<Vec<&str>, ApiRuleTreeEntry> = BTreeMap::new();
+
+    for rule in rules.into_iter().filter(|r| r.counts_toward_coverage()) {
+        let segments: Vec<&str> = rule.id.split('.').collect();
+
+        for depth in 1..=segments.len() {
+            let path = &segments[..depth];
+            let entry = entries
+                .entry(path.to_vec())
+                .or_insert_with(|| ApiRuleTreeEntry {
+                    prefix: path.join("."),
+                    segment: path[depth - 1].to_string(),
+                    depth,
+                    is_rule: false,
+                    coverage: OutlineCoverage::default(),
+                    aggregated: OutlineCoverage::default(),
+                });
+
+            count_rule(&mut entry.aggregated, rule);
+            if depth == segments.len() {
+                entry.is_rule = true;
+                count_rule(&mut entry.coverage, rule);
+            }
+        }
+    }
+
+    entries.into_values().collect()
+}
+
+/// Add one rule to tree coverage counts
+fn count_rule(coverage: &mut OutlineCoverage, rule: &ApiRule) {
+    coverage.total += 1;
+    coverage.impl_count += !rule.impl_refs.is_empty() as usize;
+    coverage.verify_count += !rule.verify_refs.is_empty() as usize;
+    coverage.stale_count += rule.is_stale() as usize;
+}
+
 /// Simple glob pattern matching
 ///
 /// Normalizes path separators to forward slashes for cross-platform compatibility.
@@ -0,0 +1,372 @@
+//! Git-diff-scoped coverage for pull requests.
+//!
+//! r[impl diff.scope]
+//!
+//! `tracey check --since <ref>` and the daemon's `diff_scope` query restrict
+//! the analysis to what a branch changed: requirements added or edited in spec
+//! markdown since the ref, and code units touched by the diff that carry no
+//! requirement reference.
+
+use eyre::{Result, WrapErr, bail};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracey_core::code_units::CodeUnitKind;
+use tracey_proto::{ChangedRule, DiffScopeResponse, DiffUnit};
+
+use crate::data::{DashboardData, ImplKey};
+use crate::lock::hash_requirement;
+
+/// Lines changed in one file, on the working-tree side of the diff.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileChanges {
+    /// The file didn't exist at the ref (or is untracked)
+    pub is_new: bool,
+    /// Hunks as (first line, line count). A count of 0 is a pure deletion
+    /// between `first line` and the line after it.
+    pub hunks: Vec<(usize, usize)>,
+}
+
+impl FileChanges {
+    /// Whether the diff changed anything within lines `start..=end`.
+    pub fn touches(&self, start: usize, end: usize) -> bool {
+        self.is_new
+            || self.hunks.iter().any(|&(first, count)| {
+                if count == 0 {
+                    first >= start && first < end
+                } else {
+                    first <= end && first + count > start
+                }
+            })
+    }
+
+    /// Whether every line in `start..=end` was added or rewritten by the diff.
+    pub fn adds(&self, start: usize, end: usize) -> bool {
+        self.is_new
+            || (start..=end).all(|line| {
+                self.hunks
+                    .iter()
+                    .any(|&(first, count)| line >= first && line < first + count)
+            })
+    }
+}
+
+/// Changes between a git ref and the working tree, relative to the project root.
+#[derive(Debug, Clone, Default)]
+pub struct GitDiff {
+    /// The ref the working tree is compared against
+    pub since: String,
+    /// Added or modified files
+    pub files: BTreeMap<PathBuf, FileChanges>,
+    /// Files that existed at the ref and were deleted since
+    pub deleted: Vec<PathBuf>,
+}
+
+impl GitDiff {
+    /// Diff the working tree (including uncommitted and untracked files)
+    /// against `since`.
+    pub fn load(project_root: &Path, since: &str) -> Result<Self> {
+        let output = git(
+            project_root,
+            &[
+                "diff",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--no-renames",
+                "--no-prefix",
+                "--relative",
+                since,
+                "--",
+            ],
+        )?;
+        let mut diff = Self::parse(since, &output);
+
+        let untracked = git(
+            project_root,
+            &["ls-files", "--others", "--exclude-standard"],
+        )?;
+        for path in untracked.lines().filter(|l| !l.is_empty()) {
+            diff.files.insert(
+                PathBuf::from(path),
+                FileChanges {
+                    is_new: true,
+                    hunks: Vec::new(),
+                },
+            );
+        }
+
+        Ok(diff)
+    }
+
+    /// Parse `git diff --unified=0 --no-prefix` output.
+    pub fn parse(since: &str, diff_text: &str) -> Self {
+        let mut diff = Self {
+            since: since.to_string(),
+            ..Default::default()
+        };
+        let mut old_path: Option<PathBuf> = None;
+        let mut current: Option<PathBuf> = None;
+
+        for line in diff_text.lines() {
+            if let Some(path) = line.strip_prefix("--- ") {
+                old_path = (path != "/dev/null").then(|| PathBuf::from(path));
+            } else if let Some(path) = line.strip_prefix("+++ ") {
+                if path == "/dev/null" {
+                    diff.deleted.extend(old_path.take());
+                    current = None;
+                } else {
+                    let path = PathBuf::from(path);
+                    diff.files.insert(
+                        path.clone(),
+                        FileChanges {
+                            is_new: old_path.is_none(),
+                            hunks: Vec::new(),
+                        },
+                    );
+                    current = Some(path);
+                }
+            } else if let Some(header) = line.strip_prefix("@@ ")
+                && let Some(path) = &current
+                && let Some(hunk) = parse_hunk_header(header)
+                && let Some(changes) = diff.files.get_mut(path)
+            {
+                changes.hunks.push(hunk);
+            }
+        }
+
+        diff
+    }
+
+    /// Content of `path` at the ref, if it existed there.
+    pub fn old_content(&self, project_root: &Path, path: &Path) -> Option<String> {
+        let spec = format!("{}:./{}", self.since, path.display());
+        git(project_root, &["show", &spec]).ok()
+    }
+}
+
+/// Parse the new-side range of a hunk header (`-a,b +c,d @@ ...`).
+fn parse_hunk_header(header: &str) -> Option<(usize, usize)> {
+    let new_range = header
+        .split_whitespace()
+        .find_map(|s| s.strip_prefix('+'))?;
+    let (first, count) = match new_range.split_once(',') {
+        Some((first, count)) => (first.parse().ok()?, count.parse().ok()?),
+        None => (new_range.parse().ok()?, 1),
+    };
+    Some((first, count))
+}
+
+/// Run git in `project_root` and return its stdout.
+pub(crate) fn git(project_root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(project_root)
+        .output()
+        .wrap_err("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Compute what a diff changed for one spec/impl pair.
+///
+/// r[impl diff.rules]
+/// r[impl diff.units]
+/// r[impl diff.failure]
+pub async fn diff_scope(data: &DashboardData, key: &ImplKey, diff: &GitDiff) -> DiffScopeResponse {
+    let project_root = PathBuf::from(&data.config.project_root);
+    let mut response = DiffScopeResponse {
+        spec: key.0.clone(),
+        impl_name: key.1.clone(),
+        since: diff.since.clone(),
+        changed_rules: Vec::new(),
+        unannotated_units: Vec::new(),
+        passing: true,
+    };
+
+    if let Some(forward) = data.forward_by_impl.get(key) {
+        // Requirements as they were at the ref, from every changed spec file.
+        // Deleted markdown files count too, so moving a requirement into a new
+        // file doesn't make it look added.
+        let spec_files: HashSet<PathBuf> = forward
+            .rules
+            .iter()
+            .filter_map(|r| r.source_file.as_deref().map(PathBuf::from))
+            .collect();
+        let old_files = diff
+            .files
+            .iter()
+            .filter(|(path, changes)| !changes.is_new && spec_files.contains(*path))
+            .map(|(path, _)| path)
+            .chain(
+                diff.deleted
+                    .iter()
+                    .filter(|p| p.extension().is_some_and(|e| e == "md")),
+            );
+
+        let mut old_rules: HashMap<String, u64> = HashMap::new();
+        for path in old_files {
+            let Some(content) = diff.old_content(&project_root, path) else {
+                continue;
+            };
+            let Ok(rules) = crate::extract_rules_from_markdown(path, &content).await else {
+                continue;
+            };
+            for rule in rules {
+                old_rules.insert(rule.def.id, hash_requirement(&rule.def.raw));
+            }
+        }
+
+        for rule in &forward.rules {
+            let Some(source_file) = &rule.source_file else {
+                continue;
+            };
+            if !diff.files.contains_key(Path::new(source_file)) {
+                continue;
+            }
+            let added = match old_rules.get(&rule.id) {
+                None => true,
+                Some(&hash) if hash != hash_requirement(&rule.raw) => false,
+                Some(_) => continue,
+            };
+            response.changed_rules.push(ChangedRule {
+                id: rule.id.clone(),
+                added,
+                covered: !rule.impl_refs.is_empty(),
+                source_file: rule.source_file.clone(),
+                source_line: rule.source_line,
+            });
+        }
+    }
+
+    if let Some(units_by_file) = data.code_units_by_impl.get(key) {
+        for (path, units) in units_by_file {
+            let relative = path.strip_prefix(&project_root).unwrap_or(path);
+            let Some(changes) = diff.files.get(relative) else {
+                continue;
+            };
+            let in_covered_file = units.iter().any(|u| !u.req_refs.is_empty());
+
+            for unit in units {
+                // Containers are annotated through their members
+                if !unit.req_refs.is_empty()
+                    || matches!(unit.kind, CodeUnitKind::Impl | CodeUnitKind::Module)
+                    || !changes.touches(unit.start_line, unit.end_line)
+                {
+                    continue;
+                }
+                response.unannotated_units.push(DiffUnit {
+                    path: relative.display().to_string(),
+                    kind: unit.kind.as_str().to_string(),
+                    name: unit.name.clone(),
+                    start_line: unit.start_line,
+                    end_line: unit.end_line,
+                    added: changes.adds(unit.start_line, unit.end_line),
+                    in_covered_file,
+                });
+            }
+        }
+    }
+
+    response
+        .unannotated_units
+        .sort_by(|a, b| (&a.path, a.start_line).cmp(&(&b.path, b.start_line)));
+    response.passing = !response.changed_rules.iter().any(is_uncovered_addition)
+        && !response
+            .unannotated_units
+            .iter()
+            .any(is_unannotated_addition);
+
+    response
+}
+
+/// A requirement added by the diff that nothing implements.
+pub fn is_uncovered_addition(rule: &ChangedRule) -> bool {
+    rule.added && !rule.covered
+}
+
+/// A function added by the diff, without references, in an annotated file.
+pub fn is_unannotated_addition(unit: &DiffUnit) -> bool {
+    unit.added && unit.in_covered_file && unit.kind == CodeUnitKind::Function.as_str()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git src/lib.rs src/lib.rs
+index 1111111..2222222 100644
+--- src/lib.rs
++++ src/lib.rs
+@@ -3,0 +4,5 @@ fn existing() {
++fn added() {
++    todo!()
++}
++
++// comment
+@@ -20,2 +25 @@ fn other() {
+-    old();
+-    older();
++    new();
+@@ -40 +39,0 @@ fn gone() {
+-    removed();
+diff --git docs/spec.md docs/spec.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ docs/spec.md
+@@ -0,0 +1,3 @@
++r[new.rule]
++Something MUST happen.
++
+diff --git old.md old.md
+deleted file mode 100644
+index 4444444..0000000
+--- old.md
++++ /dev/null
+@@ -1 +0,0 @@
+-r[old.rule]
+";
+
+    #[test]
+    fn test_parse_diff() {
+        let diff = GitDiff::parse("main", DIFF);
+        assert_eq!(diff.since, "main");
+        assert_eq!(
+            diff.files[Path::new("src/lib.rs")],
+            FileChanges {
+                is_new: false,
+                hunks: vec![(4, 5), (25, 1), (39, 0)],
+            }
+        );
+        assert!(diff.files[Path::new("docs/spec.md")].is_new);
+        assert_eq!(diff.deleted, vec![PathBuf::from("old.md")]);
+    }
+
+    #[test]
+    fn test_touches_and_adds() {
+        let diff = GitDiff::parse("main", DIFF);
+        let changes = &diff.files[Path::new("src/lib.rs")];
+
+        // The new function
+        assert!(changes.touches(4, 6));
+        assert!(changes.adds(4, 6));
+        // A function with one rewritten line
+        assert!(changes.touches(22, 28));
+        assert!(!changes.adds(22, 28));
+        // A function with a deleted line in its body
+        assert!(changes.touches(36, 40));
+        assert!(!changes.adds(36, 40));
+        // Untouched
+        assert!(!changes.touches(10, 20));
+        // Deletion right after the last line isn't inside the unit
+        assert!(!changes.touches(30, 38));
+    }
+}
@@ -0,0 +1,148 @@
+//! Static export of the dashboard.
+//!
+//! r[impl cli.export-site]
+//!
+//! `tracey export-site <dir>` writes the embedded dashboard assets and, as
+//! plain JSON files, every response the HTTP bridge would serve, so coverage
+//! can be published on hosts that can't run `tracey web`. The exported
+//! `index.html` carries a meta tag that switches the dashboard to read-only
+//! mode.
+
+use eyre::{Result, WrapErr};
+use facet::Facet;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracey_api::ApiForwardData;
+use tracey_proto::FileRequest;
+
+use crate::bridge::http::{INDEX_CSS, INDEX_HTML, INDEX_JS, index_html_at_root};
+use crate::config::Config;
+use crate::daemon::service::render_file;
+use crate::history::{History, history_path};
+use crate::search::{RuleEntry, SimpleIndex};
+
+/// Meta tag marking an exported `index.html`; the dashboard checks for it.
+pub const STATIC_MODE_META: &str = r#"<meta name="tracey-mode" content="static">"#;
+
+/// What an export wrote.
+#[derive(Debug, Clone, Default)]
+pub struct ExportSummary {
+    /// Spec/impl pairs exported
+    pub pairs: usize,
+    /// Source files exported, counted once per pair
+    pub files: usize,
+}
+
+/// Build the data in-process and write a static copy of the dashboard to `out_dir`.
+///
+/// r[impl export.layout]
+/// r[impl export.deep-links]
+pub async fn export_site(
+    project_root: &Path,
+    config: &Config,
+    out_dir: &Path,
+) -> Result<ExportSummary> {
+    let data = crate::data::build_dashboard_data(project_root, config, 1, true).await?;
+    let project_root = PathBuf::from(&data.config.project_root);
+    let history = History::load(&history_path(&project_root))?;
+
+    let static_mode =
+        |html: &str| html.replacen("<head>", &format!("<head>\n  {}", STATIC_MODE_META), 1);
+    // index.html and the data use relative URLs, so the site can be published
+    // under a subpath
+    write(&out_dir.join("index.html"), &static_mode(INDEX_HTML))?;
+    // Static hosts commonly serve 404.html for unknown paths, which makes
+    // deep links into the dashboard work. It's served at arbitrary depths, so
+    // it needs root-absolute asset URLs.
+    write(
+        &out_dir.join("404.html"),
+        &static_mode(index_html_at_root()),
+    )?;
+    write(&out_dir.join("assets/index.js"), INDEX_JS)?;
+    write(&out_dir.join("assets/index.css"), INDEX_CSS)?;
+
+    let api_dir = out_dir.join("api");
+    write_json(&api_dir.join("config.json"), &data.config)?;
+
+    let mut summary = ExportSummary::default();
+    let mut highlighter = arborium::Highlighter::new();
+    let mut search_files: BTreeMap<PathBuf, String> = BTreeMap::new();
+    let mut search_rules: Vec<RuleEntry> = Vec::new();
+    let mut seen_rules: HashSet<&str> = HashSet::new();
+
+    for (key, forward) in &data.forward_by_impl {
+        let (spec, impl_name) = key;
+        let pair_dir = api_dir.join(spec).join(impl_name);
+
+        write_json(
+            &pair_dir.join("forward.json"),
+            &ApiForwardData {
+                specs: vec![forward.clone()],
+            },
+        )?;
+        if let Some(spec_data) = data.specs_content_by_impl.get(key) {
+            write_json(&pair_dir.join("spec.json"), spec_data)?;
+        }
+        write_json(
+            &pair_dir.join("history.json"),
+            &history.for_impl(spec, impl_name),
+        )?;
+
+        // Source files are numbered, since their paths can't always be
+        // used as URLs (`../` for cross-workspace files)
+        let mut file_index: BTreeMap<String, usize> = BTreeMap::new();
+        if let Some(reverse) = data.reverse_by_impl.get(key) {
+            write_json(&pair_dir.join("reverse.json"), reverse)?;
+
+            for entry in &reverse.files {
+                let req = FileRequest {
+                    spec: spec.clone(),
+                    impl_name: impl_name.clone(),
+                    path: entry.path.clone(),
+                };
+                let Some(file) = render_file(&data, &project_root, &mut highlighter, req) else {
+                    continue;
+                };
+                let n = file_index.len();
+                write_json(&pair_dir.join("files").join(format!("{}.json", n)), &file)?;
+                file_index.insert(entry.path.clone(), n);
+                search_files
+                    .entry(project_root.join(&file.path))
+                    .or_insert(file.content);
+            }
+        }
+        write_json(&pair_dir.join("files.json"), &file_index)?;
+        summary.files += file_index.len();
+
+        for rule in &forward.rules {
+            if seen_rules.insert(rule.id.as_str()) {
+                search_rules.push(RuleEntry {
+                    id: rule.id.clone(),
+                    raw: rule.raw.clone(),
+                });
+            }
+        }
+        summary.pairs += 1;
+    }
+
+    // The browser can't run the search index, so export what it would index
+    // and let the dashboard match against it
+    let search = SimpleIndex::build(&project_root, &search_files, &search_rules);
+    write_json(&api_dir.join("search.json"), &search.entries())?;
+
+    Ok(summary)
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, contents).wrap_err_with(|| format!("Failed to write {}", path.display()))
+}
+
+fn write_json<'a, T: Facet<'a>>(path: &Path, value: &'a T) -> Result<()> {
+    let json = facet_json::to_string(value)
+        .map_err(|e| eyre::eyre!("Failed to serialize {}: {}", path.display(), e))?;
+    write(path, &json)
+}
@@ -0,0 +1,482 @@
+//! Coverage history across commits.
+//!
+//! r[impl history.store]
+//!
+//! `tracey history` records a `CoverageStats` snapshot per spec/impl, keyed by
+//! commit SHA and commit time, in `.tracey/history.jsonl`. Older commits can be
+//! backfilled by reading their trees straight from the git object database,
+//! without checking them out. The daemon serves the history to the dashboard,
+//! which plots it and flags regressions.
+
+use eyre::{Result, WrapErr, bail};
+use std::collections::{BTreeSet, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tracey_api::{ApiHistory, ApiHistoryEntry, ApiRegression};
+use tracey_core::{Reqs, WalkSources};
+
+use crate::config::Config;
+use crate::data::{DashboardData, ImplKey};
+use crate::diff::git;
+use crate::server::CoverageStats;
+
+/// File name of the history store within `.tracey`.
+pub const HISTORY_FILE_NAME: &str = "history.jsonl";
+
+/// Coverage drops smaller than this (in percentage points) are rounding noise.
+const REGRESSION_EPSILON: f64 = 0.01;
+
+/// Path of the history store for a project.
+pub fn history_path(project_root: &Path) -> PathBuf {
+    project_root.join(".tracey").join(HISTORY_FILE_NAME)
+}
+
+/// Coverage snapshots, one JSON object per line.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    pub entries: Vec<ApiHistoryEntry>,
+}
+
+impl History {
+    /// Load the history store. A missing file is an empty history.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(e).wrap_err_with(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        Self::parse(&content).wrap_err_with(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Parse the JSON lines format.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+        for (idx, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: ApiHistoryEntry =
+                facet_json::from_str(line).map_err(|e| eyre::eyre!("line {}: {}", idx + 1, e))?;
+            entries.push(entry);
+        }
+        Ok(Self { entries })
+    }
+
+    /// Render the store, oldest entry first.
+    pub fn render(&self) -> String {
+        let mut entries: Vec<&ApiHistoryEntry> = self.entries.iter().collect();
+        entries.sort_by(|a, b| {
+            (a.timestamp, &a.spec, &a.impl_name).cmp(&(b.timestamp, &b.spec, &b.impl_name))
+        });
+
+        let mut out = String::new();
+        for entry in entries {
+            out.push_str(&facet_json::to_string(entry).expect("history serialization failed"));
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Write the store, creating `.tracey` if needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .wrap_err_with(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(path, self.render())
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Add entries, replacing any earlier snapshot of the same commit and spec/impl.
+    ///
+    /// r[impl history.record]
+    pub fn record(&mut self, entries: impl IntoIterator<Item = ApiHistoryEntry>) {
+        for entry in entries {
+            self.entries.retain(|e| {
+                !(e.commit == entry.commit
+                    && e.spec == entry.spec
+                    && e.impl_name == entry.impl_name)
+            });
+            self.entries.push(entry);
+        }
+    }
+
+    /// Whether any snapshot was recorded for `commit`.
+    pub fn contains_commit(&self, commit: &str) -> bool {
+        self.entries.iter().any(|e| e.commit == commit)
+    }
+
+    /// History of one spec/impl pair, oldest first, with its regressions.
+    ///
+    /// r[impl history.regressions]
+    pub fn for_impl(&self, spec: &str, impl_name: &str) -> ApiHistory {
+        let mut entries: Vec<ApiHistoryEntry> = self
+            .entries
+            .iter()
+            .filter(|e| e.spec == spec && e.impl_name == impl_name)
+            .cloned()
+            .collect();
+        entries.sort_by_key(|e| e.timestamp);
+
+        let mut regressions = Vec::new();
+        for pair in entries.windows(2) {
+            let (prev, curr) = (&pair[0], &pair[1]);
+            let metrics = [
+                ("impl", prev.impl_percent, curr.impl_percent),
+                ("verify", prev.verify_percent, curr.verify_percent),
+            ];
+            for (metric, from_percent, to_percent) in metrics {
+                if to_percent < from_percent - REGRESSION_EPSILON {
+                    regressions.push(ApiRegression {
+                        commit: curr.commit.clone(),
+                        timestamp: curr.timestamp,
+                        metric: metric.to_string(),
+                        from_percent,
+                        to_percent,
+                    });
+                }
+            }
+        }
+
+        ApiHistory {
+            spec: spec.to_string(),
+            impl_name: impl_name.to_string(),
+            entries,
+            regressions,
+        }
+    }
+}
+
+fn history_entry(
+    commit: &str,
+    timestamp: i64,
+    key: &ImplKey,
+    stats: &CoverageStats,
+) -> ApiHistoryEntry {
+    ApiHistoryEntry {
+        commit: commit.to_string(),
+        timestamp,
+        spec: key.0.clone(),
+        impl_name: key.1.clone(),
+        total_rules: stats.total_rules,
+        impl_covered: stats.impl_covered,
+        verify_covered: stats.verify_covered,
+        impl_percent: stats.impl_percent,
+        verify_percent: stats.verify_percent,
+    }
+}
+
+/// Snapshot every spec/impl pair in `data` as of `commit`.
+pub fn snapshot(data: &DashboardData, commit: &str, timestamp: i64) -> Vec<ApiHistoryEntry> {
+    data.forward_by_impl
+        .iter()
+        .map(|(key, forward)| {
+            let stats = CoverageStats::headline(forward);
+            history_entry(commit, timestamp, key, &stats)
+        })
+        .collect()
+}
+
+/// SHA and commit time of `HEAD`.
+pub fn head_commit(project_root: &Path) -> Result<(String, i64)> {
+    let output = git(project_root, &["log", "-1", "--format=%H %ct"])?;
+    parse_log_line(output.trim())
+}
+
+fn parse_log_line(line: &str) -> Result<(String, i64)> {
+    let Some((commit, timestamp)) = line.split_once(' ') else {
+        bail!("unexpected git log output: '{}'", line);
+    };
+    let timestamp = timestamp
+        .trim()
+        .parse()
+        .wrap_err_with(|| format!("invalid commit time in '{}'", line))?;
+    Ok((commit.to_string(), timestamp))
+}
+
+/// Analyse up to `limit` commits reachable from `rev`, skipping commits that
+/// are already in `history`.
+///
+/// r[impl history.backfill]
+///
+/// Each commit's tree is read from the object database, so the working tree
+/// is left alone. The current config is applied to every commit, and
+/// cross-workspace (`../`) patterns are ignored since they live outside the
+/// repository.
+pub async fn backfill(
+    project_root: &Path,
+    config: &Config,
+    rev: &str,
+    limit: usize,
+    history: &History,
+) -> Result<Vec<ApiHistoryEntry>> {
+    let max_count = format!("--max-count={}", limit);
+    let log = git(
+        project_root,
+        &["log", "--format=%H %ct", &max_count, rev, "--"],
+    )?;
+    let prefix = git(project_root, &["rev-parse", "--show-prefix"])?
+        .trim()
+        .to_string();
+
+    let mut entries = Vec::new();
+    for line in log.lines().filter(|l| !l.is_empty()) {
+        let (commit, timestamp) = parse_log_line(line)?;
+        if history.contains_commit(&commit) {
+            continue;
+        }
+        let stats = analyze_commit(project_root, config, &commit, &prefix)
+            .await
+            .wrap_err_with(|| format!("Failed to analyse commit {}", commit))?;
+        entries.extend(
+            stats
+                .iter()
+                .map(|(key, stats)| history_entry(&commit, timestamp, key, stats)),
+        );
+    }
+    Ok(entries)
+}
+
+/// Coverage of every spec/impl pair at `commit`.
+async fn analyze_commit(
+    project_root: &Path,
+    config: &Config,
+    commit: &str,
+    prefix: &str,
+) -> Result<Vec<(ImplKey, CoverageStats)>> {
+    let output = git(
+        project_root,
+        &["ls-tree", "-r", "-z", "--name-only", "--full-tree", commit],
+    )?;
+    let files: Vec<&str> = output
+        .split('\0')
+        .filter_map(|path| path.strip_prefix(prefix))
+        .filter(|path| !path.is_empty())
+        .collect();
+
+    // Select spec and source files first, so all blobs come from one git call
+    let mut wanted: BTreeSet<&str> = BTreeSet::new();
+    let mut spec_files: Vec<Vec<&str>> = Vec::new();
+    let mut impl_files: Vec<Vec<Vec<&str>>> = Vec::new();
+    for spec_config in &config.specs {
+        let matching: Vec<&str> = files
+            .iter()
+            .copied()
+            .filter(|path| {
+                spec_config
+                    .include
+                    .iter()
+                    .filter(|pattern| !pattern.starts_with("../"))
+                    .any(|pattern| crate::matches_glob(path, pattern))
+            })
+            .collect();
+        wanted.extend(&matching);
+        spec_files.push(matching);
+
+        let mut per_impl = Vec::new();
+        for impl_config in &spec_config.impls {
+            let include: Vec<String> = if impl_config.include.is_empty() {
+                vec!["**/*.rs".to_string()]
+            } else {
+                impl_config.include.to_vec()
+            };
+            let sources = WalkSources::new(project_root)
+                .include(include)
+                .exclude(impl_config.exclude.to_vec());
+            let matching: Vec<&str> = files
+                .iter()
+                .copied()
+                .filter(|path| sources.matches(Path::new(path)))
+                .collect();
+            wanted.extend(&matching);
+            per_impl.push(matching);
+        }
+        impl_files.push(per_impl);
+    }
+
+    let wanted: Vec<&str> = wanted.into_iter().collect();
+    let blobs = read_blobs(project_root, commit, prefix, &wanted)?;
+
+    let extract_options = crate::config::extract_options(config);
+    let mut stats = Vec::new();
+    for (spec_idx, spec_config) in config.specs.iter().enumerate() {
+        let mut rules = Vec::new();
+        for path in &spec_files[spec_idx] {
+            let Some(content) = blobs.get(*path) else {
+                continue;
+            };
+            // A spec file that doesn't render at this commit contributes no rules
+            if let Ok(file_rules) =
+                crate::extract_rules_from_markdown(Path::new(path), content).await
+            {
+                rules.extend(file_rules);
+            }
+        }
+
+        for (impl_idx, impl_config) in spec_config.impls.iter().enumerate() {
+            let mut reqs = Reqs::new();
+            for path in &impl_files[spec_idx][impl_idx] {
+                if let Some(content) = blobs.get(*path) {
+                    reqs.extend(Reqs::extract_from_content_with(
+                        &project_root.join(path),
+                        content,
+                        &extract_options,
+                    ));
+                }
+            }
+            let key: ImplKey = (spec_config.name.clone(), impl_config.name.clone());
+            let forward = crate::data::impl_forward(
+                &spec_config.name,
+                &spec_config.prefix,
+                impl_config,
+                &rules,
+                &reqs,
+                project_root,
+            )?;
+            stats.push((key, CoverageStats::headline(&forward)));
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Read files at `commit` with a single `git cat-file --batch`.
+fn read_blobs(
+    project_root: &Path,
+    commit: &str,
+    prefix: &str,
+    paths: &[&str],
+) -> Result<HashMap<String, String>> {
+    let mut child = Command::new("git")
+        .args(["cat-file", "--batch"])
+        .current_dir(project_root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .wrap_err("Failed to run git cat-file")?;
+
+    let input: String = paths
+        .iter()
+        .map(|path| format!("{}:{}{}\n", commit, prefix, path))
+        .collect();
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    // Write from another thread so a full stdout pipe can't deadlock us
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output().wrap_err("git cat-file failed")?;
+    writer
+        .join()
+        .map_err(|_| eyre::eyre!("git cat-file writer panicked"))?
+        .wrap_err("Failed to write to git cat-file")?;
+
+    let mut blobs = HashMap::new();
+    let mut rest = output.stdout.as_slice();
+    for path in paths {
+        let Some(newline) = rest.iter().position(|&b| b == b'\n') else {
+            break;
+        };
+        let header = String::from_utf8_lossy(&rest[..newline]).into_owned();
+        rest = &rest[newline + 1..];
+
+        // "<oid> <type> <size>", or "<name> missing"
+        let fields: Vec<&str> = header.split(' ').collect();
+        let [_, kind, size] = fields.as_slice() else {
+            continue;
+        };
+        let size: usize = size.parse().wrap_err("Bad git cat-file header")?;
+        if rest.len() < size {
+            bail!("Truncated git cat-file output");
+        }
+        let (content, tail) = rest.split_at(size);
+        rest = tail.strip_prefix(b"\n").unwrap_or(tail);
+
+        if *kind == "blob"
+            && let Ok(content) = String::from_utf8(content.to_vec())
+        {
+            blobs.insert(path.to_string(), content);
+        }
+    }
+    Ok(blobs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(
+        commit: &str,
+        timestamp: i64,
+        impl_percent: f64,
+        verify_percent: f64,
+    ) -> ApiHistoryEntry {
+        ApiHistoryEntry {
+            commit: commit.to_string(),
+            timestamp,
+            spec: "spec".to_string(),
+            impl_name: "rust".to_string(),
+            total_rules: 4,
+            impl_covered: 0,
+            verify_covered: 0,
+            impl_percent,
+            verify_percent,
+        }
+    }
+
+    #[test]
+    fn test_history_roundtrip() {
+        let mut history = History::default();
+        history.record([entry("bbb", 20, 75.0, 50.0), entry("aaa", 10, 50.0, 25.0)]);
+
+        let rendered = history.render();
+        assert_eq!(rendered.lines().count(), 2);
+        assert!(rendered.lines().next().unwrap().contains("\"aaa\""));
+
+        let parsed = History::parse(&rendered).unwrap();
+        assert_eq!(parsed.for_impl("spec", "rust").entries.len(), 2);
+        assert!(parsed.contains_commit("aaa"));
+        assert!(!parsed.contains_commit("ccc"));
+    }
+
+    // r[verify history.record]
+    #[test]
+    fn test_record_replaces_same_commit() {
+        let mut history = History::default();
+        history.record([entry("aaa", 10, 50.0, 25.0)]);
+        history.record([entry("aaa", 10, 75.0, 25.0)]);
+        assert_eq!(history.entries, vec![entry("aaa", 10, 75.0, 25.0)]);
+    }
+
+    // r[verify history.regressions]
+    #[test]
+    fn test_regressions() {
+        let mut history = History::default();
+        history.record([
+            entry("ccc", 30, 75.0, 25.0),
+            entry("aaa", 10, 50.0, 50.0),
+            entry("bbb", 20, 75.0, 50.0),
+        ]);
+
+        let result = history.for_impl("spec", "rust");
+        let commits: Vec<_> = result.entries.iter().map(|e| e.commit.as_str()).collect();
+        assert_eq!(commits, vec!["aaa", "bbb", "ccc"]);
+        assert_eq!(
+            result.regressions,
+            vec![ApiRegression {
+                commit: "ccc".to_string(),
+                timestamp: 30,
+                metric: "verify".to_string(),
+                from_percent: 50.0,
+                to_percent: 25.0,
+            }]
+        );
+        assert!(history.for_impl("spec", "other").entries.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_garbage() {
+        assert!(History::parse("not json\n").is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//! Requirement levels (MUST/SHOULD/MAY).
+//!
+//! r[impl level.effective]
+//!
+//! A requirement's level comes from its marq metadata when set, otherwise from
+//! the RFC 2119 keywords in its text. Levels are kept as the lower-case names
+//! in [`LEVELS`], which is what `ApiRule::level` carries.
+
+use eyre::{Result, eyre};
+use tracey_api::ApiRule;
+
+/// Known levels, strongest first.
+pub const LEVELS: [&str; 3] = ["must", "should", "may"];
+
+/// RFC 2119 keywords for each level.
+const KEYWORDS: [(&str, &[&str]); 3] = [
+    ("must", &["MUST", "SHALL", "REQUIRED"]),
+    ("should", &["SHOULD", "RECOMMENDED"]),
+    ("may", &["MAY", "OPTIONAL"]),
+];
+
+/// Normalize a level name or keyword (`MUST`, `shall`, `may`...) to one of [`LEVELS`].
+pub fn normalize(name: &str) -> Option<&'static str> {
+    KEYWORDS
+        .iter()
+        .find(|(level, keywords)| {
+            level.eq_ignore_ascii_case(name)
+                || keywords.iter().any(|k| k.eq_ignore_ascii_case(name))
+        })
+        .map(|(level, _)| *level)
+}
+
+/// Parse a level given on the command line or to an MCP tool.
+pub fn parse(name: &str) -> Result<&'static str> {
+    normalize(name.trim()).ok_or_else(|| {
+        eyre!(
+            "Unknown requirement level '{}' (expected one of: {})",
+            name,
+            LEVELS.join(", ")
+        )
+    })
+}
+
+/// Infer a level from requirement text.
+///
+/// r[impl level.infer]
+///
+/// Only upper-case keywords count, so "may" in ordinary prose doesn't make a
+/// requirement optional. When several levels appear, the strongest wins.
+pub fn infer(text: &str) -> Option<&'static str> {
+    let words: Vec<&str> = text
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    KEYWORDS
+        .iter()
+        .find(|(_, keywords)| keywords.iter().any(|k| words.contains(k)))
+        .map(|(level, _)| *level)
+}
+
+/// Effective level of a requirement: its explicit level if recognized, otherwise
+/// the one inferred from its text.
+pub fn effective(explicit: Option<&str>, text: &str) -> Option<&'static str> {
+    explicit.and_then(normalize).or_else(|| infer(text))
+}
+
+/// Whether `rule` is at `level`; no level matches every rule.
+pub fn matches(rule: &ApiRule, level: Option<&str>) -> bool {
+    level.is_none_or(|l| rule.level.as_deref() == Some(l))
+}
+
+/// Whether `rule` counts toward the headline coverage percentage.
+///
+/// r[impl level.headline]
+pub fn in_headline(rule: &ApiRule, exclude_levels: &[String]) -> bool {
+    rule.level
+        .as_deref()
+        .is_none_or(|l| !exclude_levels.iter().any(|e| normalize(e) == Some(l)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // r[verify level.infer]
+    #[test]
+    fn test_infer_prefers_strongest_keyword() {
+        assert_eq!(infer("Users MUST log in."), Some("must"));
+        assert_eq!(
+            infer("It SHOULD be fast but MUST be correct."),
+            Some("must")
+        );
+        assert_eq!(infer("Caching is RECOMMENDED."), Some("should"));
+        assert_eq!(infer("Clients MAY retry."), Some("may"));
+        assert_eq!(infer("The server shall not say what it may do."), None);
+        assert_eq!(infer("MUSTARD is not a keyword."), None);
+    }
+
+    // r[verify level.effective]
+    #[test]
+    fn test_effective_prefers_explicit_level() {
+        assert_eq!(effective(Some("may"), "Users MUST log in."), Some("may"));
+        assert_eq!(effective(Some("SHALL"), ""), Some("must"));
+        assert_eq!(
+            effective(Some("bogus"), "Users SHOULD log in."),
+            Some("should")
+        );
+        assert_eq!(effective(None, "Users log in."), None);
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_levels() {
+        assert_eq!(parse("MUST").unwrap(), "must");
+        assert_eq!(parse(" optional ").unwrap(), "may");
+        assert!(parse("could").is_err());
+    }
+}
@@ -4,24 +4,39 @@
 //! and embedding purposes.
 
 pub mod bridge;
+pub mod cache;
+pub mod check;
 pub mod config;
 pub mod daemon;
 pub mod data;
+pub mod diff;
+pub mod export;
+pub mod history;
+pub mod level;
+pub mod lock;
+pub mod output;
 pub mod search;
 pub mod server;
+pub mod test_results;
+pub mod validation;
 pub mod vite;
 
 use config::Config;
 use eyre::{Result, WrapErr};
+use facet::Facet;
 use std::path::PathBuf;
-use tracey_core::ReqDefinition;
+use tracey_core::{ReqDefinition, split_version};
 
 // Re-export from marq for rule extraction
 use marq::{RenderOptions, render};
 
 /// Extracted rule with source location info
+#[derive(Clone, Facet)]
 pub struct ExtractedRule {
+    /// The definition as parsed by marq, with any `+N` suffix stripped from its ID
     pub def: ReqDefinition,
+    /// Requirement version from the `+N` suffix (1 if absent)
+    pub version: u32,
     pub source_file: String,
     /// 1-indexed column where the rule marker starts
     pub column: Option<usize>,
@@ -49,6 +64,17 @@ pub async fn load_rules_from_glob(
     root: &std::path::Path,
     pattern: &str,
     quiet: bool,
+) -> Result<Vec<ExtractedRule>> {
+    load_rules_from_glob_cached(root, pattern, quiet, &mut cache::BuildCache::new()).await
+}
+
+/// Like [`load_rules_from_glob`], but only re-renders files whose content
+/// changed since they were last put in `cache`.
+pub async fn load_rules_from_glob_cached(
+    root: &std::path::Path,
+    pattern: &str,
+    quiet: bool,
+    cache: &mut cache::BuildCache,
 ) -> Result<Vec<ExtractedRule>> {
     use ignore::WalkBuilder;
     use owo_colors::OwoColorize;
@@ -131,16 +157,22 @@ pub async fn load_rules_from_glob(
         let content = std::fs::read_to_string(path)
             .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
 
-        let doc = render(&content, &RenderOptions::default())
-            .await
-            .map_err(|e| eyre::eyre!("Failed to process {}: {}", path.display(), e))?;
+        let hash = data::simple_hash(&content);
+        let file_rules = match cache.spec_file_rules(path, hash) {
+            Some(file_rules) => file_rules,
+            None => {
+                let file_rules = extract_rules_from_markdown(path, &content).await?;
+                cache.insert_spec_file_rules(path, hash, file_rules.clone());
+                file_rules
+            }
+        };
 
-        if !doc.reqs.is_empty() {
+        if !file_rules.is_empty() {
             if !quiet {
                 eprintln!(
                     "   {} {} requirements from {}",
                     "Found".green(),
-                    doc.reqs.len(),
+                    file_rules.len(),
                     display_path
                 );
             }
@@ -148,55 +180,75 @@ pub async fn load_rules_from_glob(
             // Check for duplicates
             // r[impl markdown.duplicates.same-file] - caught when marq returns duplicate reqs from single file
             // r[impl markdown.duplicates.cross-file] - caught via seen_ids persisting across files
-            for req in &doc.reqs {
-                if seen_ids.contains(&req.id) {
+            for extracted in &file_rules {
+                if seen_ids.contains(&extracted.def.id) {
                     eyre::bail!(
                         "Duplicate requirement '{}' found in {}",
-                        req.id.red(),
+                        extracted.def.id.red(),
                         display_path
                     );
                 }
-                seen_ids.insert(req.id.clone());
+                seen_ids.insert(extracted.def.id.clone());
             }
 
-            // Build a mapping from rule ID to section info by processing elements in order
-            use marq::DocElement;
-            use std::collections::HashMap;
-            let mut rule_sections: HashMap<String, (Option<String>, Option<String>)> =
-                HashMap::new();
-            let mut current_section: Option<(String, String)> = None; // (slug, title)
-
-            for element in &doc.elements {
-                match element {
-                    DocElement::Heading(h) => {
-                        current_section = Some((h.id.clone(), h.title.clone()));
-                    }
-                    DocElement::Req(r) => {
-                        if let Some((slug, title)) = &current_section {
-                            rule_sections
-                                .insert(r.id.clone(), (Some(slug.clone()), Some(title.clone())));
-                        }
-                    }
-                    DocElement::Paragraph(_) => {}
-                }
+            // Add requirements with their source file
+            for mut extracted in file_rules {
+                extracted.source_file = display_path.clone();
+                rules.push(extracted);
             }
+        }
+    }
+
+    Ok(rules)
+}
 
-            // Add requirements with their source file, computed column, and section
-            for req in doc.reqs {
-                let column = Some(compute_column(&content, req.span.offset));
-                let (section, section_title) =
-                    rule_sections.remove(&req.id).unwrap_or((None, None));
-                rules.push(ExtractedRule {
-                    def: req,
-                    source_file: display_path.clone(),
-                    column,
-                    section,
-                    section_title,
-                });
+/// Render a markdown file and extract its requirements, with their computed
+/// column and section. `source_file` is left empty for the caller to fill in.
+pub(crate) async fn extract_rules_from_markdown(
+    path: &std::path::Path,
+    content: &str,
+) -> Result<Vec<ExtractedRule>> {
+    let doc = render(content, &RenderOptions::default())
+        .await
+        .map_err(|e| eyre::eyre!("Failed to process {}: {}", path.display(), e))?;
+
+    // Build a mapping from rule ID to section info by processing elements in order
+    use marq::DocElement;
+    use std::collections::HashMap;
+    let mut rule_sections: HashMap<String, (Option<String>, Option<String>)> = HashMap::new();
+    let mut current_section: Option<(String, String)> = None; // (slug, title)
+
+    for element in &doc.elements {
+        match element {
+            DocElement::Heading(h) => {
+                current_section = Some((h.id.clone(), h.title.clone()));
+            }
+            DocElement::Req(r) => {
+                if let Some((slug, title)) = &current_section {
+                    rule_sections.insert(r.id.clone(), (Some(slug.clone()), Some(title.clone())));
+                }
             }
+            DocElement::Paragraph(_) => {}
         }
     }
 
+    let mut rules = Vec::with_capacity(doc.reqs.len());
+    for mut req in doc.reqs {
+        let column = Some(compute_column(content, req.span.offset));
+        let (section, section_title) = rule_sections.remove(&req.id).unwrap_or((None, None));
+        // r[impl markdown.version.suffix]
+        let (id, version) = split_version(&req.id);
+        req.id = id.to_string();
+        rules.push(ExtractedRule {
+            def: req,
+            version,
+            source_file: String::new(),
+            column,
+            section,
+            section_title,
+        });
+    }
+
     Ok(rules)
 }
 
@@ -205,6 +257,16 @@ pub async fn load_rules_from_globs(
     root: &std::path::Path,
     patterns: &[&str],
     quiet: bool,
+) -> Result<Vec<ExtractedRule>> {
+    load_rules_from_globs_cached(root, patterns, quiet, &mut cache::BuildCache::new()).await
+}
+
+/// Like [`load_rules_from_globs`], reusing requirements from unchanged files.
+pub async fn load_rules_from_globs_cached(
+    root: &std::path::Path,
+    patterns: &[&str],
+    quiet: bool,
+    cache: &mut cache::BuildCache,
 ) -> Result<Vec<ExtractedRule>> {
     use owo_colors::OwoColorize;
     use std::collections::HashSet;
@@ -213,7 +275,7 @@ pub async fn load_rules_from_globs(
     let mut seen_ids: HashSet<String> = HashSet::new();
 
     for pattern in patterns {
-        let rules = load_rules_from_glob(root, pattern, quiet).await?;
+        let rules = load_rules_from_glob_cached(root, pattern, quiet, cache).await?;
 
         // r[impl validation.duplicates]
         // Check for duplicates across patterns
@@ -233,8 +295,42 @@ pub async fn load_rules_from_globs(
     Ok(all_rules)
 }
 
+/// Whether `path` is a spec file: a markdown file matched by one of the
+/// spec `include` patterns, resolved against `root` the way the spec loader
+/// resolves them. Other markdown files (READMEs, test plans) are sources.
+pub(crate) fn is_spec_file(
+    config: &Config,
+    root: &std::path::Path,
+    path: &std::path::Path,
+) -> bool {
+    if path.extension().is_none_or(|ext| ext != "md") {
+        return false;
+    }
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    config.specs.iter().flat_map(|s| &s.include).any(|pattern| {
+        // External patterns are relative to their directory prefix
+        let parts: Vec<&str> = pattern.split('/').collect();
+        let (base, effective) = if pattern.starts_with("..") {
+            let glob = parts
+                .iter()
+                .position(|p| p.contains('*') || p.contains('?') || p.contains('['));
+            match glob {
+                Some(idx) => (parts[..idx].join("/"), parts[idx..].join("/")),
+                None => (pattern.clone(), "**/*.md".to_string()),
+            }
+        } else {
+            (String::new(), pattern.clone())
+        };
+        let Ok(base) = root.join(base).canonicalize() else {
+            return false;
+        };
+        path.strip_prefix(&base)
+            .is_ok_and(|relative| matches_glob(&relative.to_string_lossy(), &effective))
+    })
+}
+
 /// Simple glob pattern matching
-fn matches_glob(path: &str, pattern: &str) -> bool {
+pub(crate) fn matches_glob(path: &str, pattern: &str) -> bool {
     // Make path separators consistent in case of windows
     let path = path.replace('\\', "/");
     let pattern = pattern.replace('\\', "/");
@@ -0,0 +1,213 @@
+//! Content-hash pinning of requirement text.
+//!
+//! r[impl lock.file]
+//!
+//! `tracey lock` records a hash of every requirement's normalized `raw` text in
+//! `tracey.lock`, next to the config file. `tracey check` compares the current
+//! text against those hashes and reports requirements that changed since their
+//! references were last acknowledged, so reviewers have to re-confirm the
+//! impl/verify sites before re-locking.
+
+use eyre::{Result, WrapErr, bail};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use crate::data::{DashboardData, simple_hash};
+
+/// File name of the lock file, resolved relative to the config file.
+pub const LOCK_FILE_NAME: &str = "tracey.lock";
+
+const HEADER: &str = "# Generated by `tracey lock`. Do not edit by hand.\n\
+# Each line pins the text of one requirement: <spec> <rule-id> <hash>\n";
+
+/// Path of the lock file that belongs to `config_path`.
+pub fn lock_path(config_path: &Path) -> PathBuf {
+    config_path.with_file_name(LOCK_FILE_NAME)
+}
+
+/// Normalize requirement text before hashing.
+///
+/// r[impl lock.normalize]
+///
+/// Blockquote markers and whitespace differences (re-wrapping, indentation,
+/// trailing spaces) don't count as a change to the requirement.
+pub fn normalize(raw: &str) -> String {
+    raw.lines()
+        .map(|line| line.trim_start().trim_start_matches('>'))
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Hash of a requirement's normalized text.
+pub fn hash_requirement(raw: &str) -> u64 {
+    simple_hash(&normalize(raw))
+}
+
+/// Pinned requirement hashes, keyed by (spec name, rule id).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Lock {
+    pub entries: BTreeMap<(String, String), u64>,
+}
+
+/// A requirement whose text no longer matches the lock file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriftedRule {
+    pub spec: String,
+    pub rule_id: String,
+    pub locked_hash: u64,
+    pub current_hash: u64,
+}
+
+impl Lock {
+    /// Hash every requirement in the dashboard data.
+    pub fn from_data(data: &DashboardData) -> Self {
+        let mut entries = BTreeMap::new();
+        for ((spec, _impl), forward) in &data.forward_by_impl {
+            for rule in &forward.rules {
+                entries
+                    .entry((spec.clone(), rule.id.clone()))
+                    .or_insert_with(|| hash_requirement(&rule.raw));
+            }
+        }
+        Self { entries }
+    }
+
+    /// Load the lock file. A missing file is an empty lock.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(e).wrap_err_with(|| format!("Failed to read {}", path.display()));
+            }
+        };
+        Self::parse(&content).wrap_err_with(|| format!("Failed to parse {}", path.display()))
+    }
+
+    /// Parse the lock file format.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut entries = BTreeMap::new();
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let [spec, rule_id, hash] = parts.as_slice() else {
+                bail!(
+                    "line {}: expected '<spec> <rule-id> <hash>', got '{}'",
+                    idx + 1,
+                    line
+                );
+            };
+            let hash = u64::from_str_radix(hash, 16)
+                .wrap_err_with(|| format!("line {}: invalid hash '{}'", idx + 1, hash))?;
+            entries.insert((spec.to_string(), rule_id.to_string()), hash);
+        }
+        Ok(Self { entries })
+    }
+
+    /// Render the lock file, one sorted line per requirement.
+    pub fn render(&self) -> String {
+        let mut out = String::from(HEADER);
+        for ((spec, rule_id), hash) in &self.entries {
+            out.push_str(&format!("{} {} {:016x}\n", spec, rule_id, hash));
+        }
+        out
+    }
+
+    /// Write the lock file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.render())
+            .wrap_err_with(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Requirements of `spec` whose text changed since they were locked.
+    ///
+    /// r[impl lock.drift]
+    ///
+    /// Only requirements that are referenced by impl or verify sites are
+    /// reported: those are the references that need re-confirming. Requirements
+    /// missing from the lock are new, not drifted.
+    pub fn drift(&self, data: &DashboardData, spec: &str) -> Vec<DriftedRule> {
+        let mut referenced = BTreeSet::new();
+        let mut current = BTreeMap::new();
+        for ((spec_name, _impl), forward) in &data.forward_by_impl {
+            if spec_name != spec {
+                continue;
+            }
+            for rule in &forward.rules {
+                current
+                    .entry(rule.id.as_str())
+                    .or_insert_with(|| hash_requirement(&rule.raw));
+                if !rule.impl_refs.is_empty() || !rule.verify_refs.is_empty() {
+                    referenced.insert(rule.id.as_str());
+                }
+            }
+        }
+
+        referenced
+            .into_iter()
+            .filter_map(|rule_id| {
+                let locked_hash = *self.entries.get(&(spec.to_string(), rule_id.to_string()))?;
+                let current_hash = current[rule_id];
+                (locked_hash != current_hash).then(|| DriftedRule {
+                    spec: spec.to_string(),
+                    rule_id: rule_id.to_string(),
+                    locked_hash,
+                    current_hash,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_ignores_wrapping_and_blockquotes() {
+        let a = "> r[auth.login]\n> Users MUST log in\n> with a password.";
+        let b = "r[auth.login]\nUsers   MUST log in with a password.  ";
+        assert_eq!(normalize(a), normalize(b));
+        assert_eq!(hash_requirement(a), hash_requirement(b));
+        assert_ne!(
+            hash_requirement(a),
+            hash_requirement("r[auth.login]\nUsers MAY log in with a password.")
+        );
+    }
+
+    #[test]
+    fn test_lock_roundtrip() {
+        let mut lock = Lock::default();
+        lock.entries
+            .insert(("spec".to_string(), "b.rule".to_string()), 0xdead_beef);
+        lock.entries
+            .insert(("spec".to_string(), "a.rule".to_string()), u64::MAX);
+
+        let rendered = lock.render();
+        let lines: Vec<_> = rendered.lines().filter(|l| !l.starts_with('#')).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "spec a.rule ffffffffffffffff",
+                "spec b.rule 00000000deadbeef"
+            ]
+        );
+        assert_eq!(Lock::parse(&rendered).unwrap(), lock);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        assert!(Lock::parse("spec rule").is_err());
+        assert!(Lock::parse("spec rule not-hex").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let lock = Lock::load(Path::new("/nonexistent/tracey.lock")).unwrap();
+        assert!(lock.entries.is_empty());
+    }
+}
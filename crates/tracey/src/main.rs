@@ -10,7 +10,7 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 
 // Use the library crate
-use tracey::{bridge, daemon, find_project_root};
+use tracey::{bridge, check, daemon, export, find_project_root, history, lock, output};
 
 /// CLI arguments
 #[derive(Debug, facet::Facet)]
@@ -112,6 +112,126 @@ enum Command {
         #[facet(args::positional, default)]
         root: Option<PathBuf>,
     },
+
+    /// Validate and enforce coverage thresholds without a daemon (for CI)
+    Check {
+        /// Project root directory (default: current directory)
+        #[facet(args::positional, default)]
+        root: Option<PathBuf>,
+
+        /// Path to config file
+        #[facet(args::named, args::short = 'c', default = ".config/tracey/config.styx")]
+        config: PathBuf,
+
+        /// Only check this spec
+        #[facet(args::named, default)]
+        spec: Option<String>,
+
+        /// Only check this implementation
+        #[facet(args::named, rename = "impl", default)]
+        impl_name: Option<String>,
+
+        /// Minimum coverage percentage, overriding per-impl thresholds
+        #[facet(args::named, args::short = 't', default)]
+        threshold: Option<f64>,
+
+        /// Also write validation errors as a SARIF 2.1.0 log to this file
+        #[facet(args::named, default)]
+        sarif: Option<PathBuf>,
+
+        /// Test results (libtest JSON or JUnit XML) to check verify references against
+        #[facet(args::named, default)]
+        test_results: Option<PathBuf>,
+
+        /// Also check requirements and code changed since this git ref
+        #[facet(args::named, default)]
+        since: Option<String>,
+
+        /// Requirement levels to leave out of the headline percentage (e.g. "may")
+        #[facet(args::named, default)]
+        exclude_level: Option<String>,
+    },
+
+    /// Write a coverage report (text, json, markdown, html, junit, csv or spreadsheet)
+    Report {
+        /// Project root directory (default: current directory)
+        #[facet(args::positional, default)]
+        root: Option<PathBuf>,
+
+        /// Path to config file
+        #[facet(args::named, args::short = 'c', default = ".config/tracey/config.styx")]
+        config: PathBuf,
+
+        /// Output format: text, json, markdown, html, junit, csv or spreadsheet (default: text)
+        #[facet(args::named, args::short = 'f', default)]
+        format: Option<String>,
+
+        /// Only report on this spec
+        #[facet(args::named, default)]
+        spec: Option<String>,
+
+        /// Only report on this implementation
+        #[facet(args::named, rename = "impl", default)]
+        impl_name: Option<String>,
+
+        /// Write the report to this file instead of stdout
+        #[facet(args::named, args::short = 'o', default)]
+        out: Option<PathBuf>,
+
+        /// Include every covered rule and its references
+        #[facet(args::named, args::short = 'v', default)]
+        verbose: bool,
+
+        /// Only report on requirements at this level (must, should or may)
+        #[facet(args::named, default)]
+        level: Option<String>,
+    },
+
+    /// Pin the current text of every requirement in tracey.lock
+    Lock {
+        /// Project root directory (default: current directory)
+        #[facet(args::positional, default)]
+        root: Option<PathBuf>,
+
+        /// Path to config file
+        #[facet(args::named, args::short = 'c', default = ".config/tracey/config.styx")]
+        config: PathBuf,
+    },
+
+    /// Record coverage at HEAD in the coverage history
+    History {
+        /// Project root directory (default: current directory)
+        #[facet(args::positional, default)]
+        root: Option<PathBuf>,
+
+        /// Path to config file
+        #[facet(args::named, args::short = 'c', default = ".config/tracey/config.styx")]
+        config: PathBuf,
+
+        /// Analyse up to this many commits from git history instead of the working tree
+        #[facet(args::named, default)]
+        backfill: Option<usize>,
+
+        /// Commit to backfill from (default: HEAD)
+        #[facet(args::named, default)]
+        rev: Option<String>,
+    },
+
+    /// Write the dashboard and its data as a static site
+    #[facet(rename = "export-site")]
+    ExportSite {
+        /// Directory to write the site to
+        #[facet(args::positional)]
+        dir: PathBuf,
+
+        /// Project root directory (default: current directory)
+        #[facet(args::named, default)]
+        root: Option<PathBuf>,
+
+        /// Path to config file
+        #[facet(args::named, args::short = 'c', default = ".config/tracey/config.styx")]
+        config: PathBuf,
+    },
 }
 
 // Embed the config schema for zero-execution discovery by styx tooling
@@ -195,6 +315,205 @@ fn main() -> Result<()> {
             let rt = tokio::runtime::Runtime::new()?;
             rt.block_on(kill_daemon(root))
         }
+        // r[impl cli.check]
+        Some(Command::Check {
+            root,
+            config,
+            spec,
+            impl_name,
+            threshold,
+            sarif,
+            test_results,
+            since,
+            exclude_level,
+        }) => {
+            let project_root = match root {
+                Some(r) => r,
+                None => find_project_root()?,
+            };
+            let config_path = project_root.join(&config);
+            let mut config = tracey::load_config(&config_path)?;
+
+            // r[impl cli.check.test-results]
+            if let Some(path) = test_results {
+                let path = std::env::current_dir()?.join(path).display().to_string();
+                for impl_config in config.specs.iter_mut().flat_map(|s| s.impls.iter_mut()) {
+                    impl_config.test_results.push(path.clone());
+                }
+            }
+            let options = check::CheckOptions {
+                spec,
+                impl_name,
+                threshold,
+                lock_path: Some(lock::lock_path(&config_path)),
+                since,
+                // r[impl cli.check.exclude-level]
+                exclude_levels: exclude_level
+                    .iter()
+                    .flat_map(|l| l.split(','))
+                    .map(|l| l.trim().to_string())
+                    .filter(|l| !l.is_empty())
+                    .collect(),
+            };
+
+            let rt = tokio::runtime::Runtime::new()?;
+            let outcome = rt.block_on(check::check(&project_root, &config, &options))?;
+            check::print_summary(&outcome);
+
+            // r[impl cli.check.sarif]
+            if let Some(path) = sarif {
+                let results: Vec<_> = outcome.impls.iter().map(|i| i.validation.clone()).collect();
+                std::fs::write(&path, output::render_sarif(&results))?;
+                eprintln!("Wrote SARIF log to {}", path.display());
+            }
+
+            // r[impl cli.check.exit-code]
+            if !outcome.is_passing() {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        // r[impl cli.report]
+        Some(Command::Report {
+            root,
+            config,
+            format,
+            spec,
+            impl_name,
+            out,
+            verbose,
+            level,
+        }) => {
+            let format = match format {
+                Some(f) => f.parse()?,
+                None => ReportFormat::Coverage(output::OutputFormat::default()),
+            };
+            let level = level.as_deref().map(tracey::level::parse).transpose()?;
+            let rt = tokio::runtime::Runtime::new()?;
+            rt.block_on(write_report(
+                root, config, format, spec, impl_name, out, verbose, level,
+            ))
+        }
+        // r[impl cli.lock]
+        Some(Command::Lock { root, config }) => {
+            let project_root = match root {
+                Some(r) => r,
+                None => find_project_root()?,
+            };
+            let config_path = project_root.join(&config);
+            let config = tracey::load_config(&config_path)?;
+
+            let rt = tokio::runtime::Runtime::new()?;
+            let data = rt.block_on(tracey::data::build_dashboard_data(
+                &project_root,
+                &config,
+                1,
+                true,
+            ))?;
+            let lock_path = lock::lock_path(&config_path);
+            let lock = lock::Lock::from_data(&data);
+            lock.save(&lock_path)?;
+            eprintln!(
+                "Locked {} requirement(s) in {}",
+                lock.entries.len(),
+                lock_path.display()
+            );
+            Ok(())
+        }
+        // r[impl cli.history]
+        Some(Command::History {
+            root,
+            config,
+            backfill,
+            rev,
+        }) => {
+            let project_root = match root {
+                Some(r) => r,
+                None => find_project_root()?,
+            };
+            let config = tracey::load_config(&project_root.join(&config))?;
+            daemon::ensure_tracey_dir(&project_root)?;
+            let history_path = history::history_path(&project_root);
+            let mut store = history::History::load(&history_path)?;
+
+            let rt = tokio::runtime::Runtime::new()?;
+            let entries = match backfill {
+                Some(limit) => rt.block_on(history::backfill(
+                    &project_root,
+                    &config,
+                    rev.as_deref().unwrap_or("HEAD"),
+                    limit,
+                    &store,
+                ))?,
+                None => {
+                    let data = rt.block_on(tracey::data::build_dashboard_data(
+                        &project_root,
+                        &config,
+                        1,
+                        true,
+                    ))?;
+                    let (commit, timestamp) = history::head_commit(&project_root)?;
+                    history::snapshot(&data, &commit, timestamp)
+                }
+            };
+            let recorded = entries.len();
+            store.record(entries);
+            store.save(&history_path)?;
+            eprintln!(
+                "Recorded {} snapshot(s) in {}",
+                recorded,
+                history_path.display()
+            );
+
+            let short = |commit: &str| commit.chars().take(8).collect::<String>();
+            for spec_config in &config.specs {
+                for impl_config in &spec_config.impls {
+                    let trend = store.for_impl(&spec_config.name, &impl_config.name);
+                    let Some(latest) = trend.entries.last() else {
+                        continue;
+                    };
+                    eprintln!(
+                        "  {}/{}: impl {:.1}%, verify {:.1}% at {} ({} snapshot(s))",
+                        spec_config.name,
+                        impl_config.name,
+                        latest.impl_percent,
+                        latest.verify_percent,
+                        short(&latest.commit),
+                        trend.entries.len()
+                    );
+                    for regression in &trend.regressions {
+                        eprintln!(
+                            "    {} {} coverage {:.1}% -> {:.1}% at {}",
+                            "regression:".yellow(),
+                            regression.metric,
+                            regression.from_percent,
+                            regression.to_percent,
+                            short(&regression.commit)
+                        );
+                    }
+                }
+            }
+            Ok(())
+        }
+        // r[impl cli.export-site]
+        Some(Command::ExportSite { dir, root, config }) => {
+            let project_root = match root {
+                Some(r) => r,
+                None => find_project_root()?,
+            };
+            let config = tracey::load_config(&project_root.join(&config))?;
+
+            let rt = tokio::runtime::Runtime::new()?;
+            let summary = rt.block_on(export::export_site(&project_root, &config, &dir))?;
+            eprintln!(
+                "{}: Exported {} spec/impl pair(s) and {} source file(s) to {}",
+                "Success".green(),
+                summary.pairs,
+                summary.files,
+                dir.display()
+            );
+            Ok(())
+        }
         // r[impl cli.no-args]
         None => {
             print_help();
@@ -211,13 +530,18 @@ fn print_help() {
     tracey <COMMAND> [OPTIONS]
 
 {commands}:
-    {web}       Start the interactive web dashboard
-    {mcp}       Start the MCP server for AI assistants
-    {lsp}       Start the LSP server for editor integration
-    {daemon}    Start the tracey daemon (persistent server)
-    {logs}      Show daemon logs
-    {status}    Show daemon status
-    {kill}      Stop the running daemon
+    {web}          Start the interactive web dashboard
+    {mcp}          Start the MCP server for AI assistants
+    {lsp}          Start the LSP server for editor integration
+    {daemon}       Start the tracey daemon (persistent server)
+    {logs}         Show daemon logs
+    {status}       Show daemon status
+    {kill}         Stop the running daemon
+    {check}        Validate and check coverage without a daemon (for CI)
+    {report}       Write a coverage report (text, json, markdown, html, junit, csv, spreadsheet)
+    {lock}         Pin the current requirement text in tracey.lock
+    {history}      Record coverage history (or backfill it from git)
+    {export_site}  Write the dashboard as a static site
 
 {options}:
     -h, --help      Show this help message
@@ -232,10 +556,123 @@ Run 'tracey <COMMAND> --help' for more information on a command."#,
         logs = "logs".cyan(),
         status = "status".cyan(),
         kill = "kill".cyan(),
+        check = "check".cyan(),
+        report = "report".cyan(),
+        lock = "lock".cyan(),
+        history = "history".cyan(),
+        export_site = "export-site".cyan(),
         options = "Options".bold(),
     );
 }
 
+/// Formats accepted by `tracey report`
+enum ReportFormat {
+    /// One of the `CoverageReport` renderers
+    Coverage(output::OutputFormat),
+    /// JUnit XML built from the forward data, one test case per requirement
+    Junit,
+    /// Requirement × impl traceability matrix built from the forward data
+    Matrix(output::MatrixFormat),
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s.eq_ignore_ascii_case("junit") {
+            return Ok(Self::Junit);
+        }
+        if let Ok(format) = s.parse() {
+            return Ok(Self::Matrix(format));
+        }
+        s.parse().map(Self::Coverage).map_err(|_| {
+            eyre::eyre!(
+                "Unknown format '{}' (expected text, json, markdown, html, junit, csv or spreadsheet)",
+                s
+            )
+        })
+    }
+}
+
+/// r[impl cli.report]
+/// Build coverage reports in-process and write them to stdout or a file
+#[allow(clippy::too_many_arguments)]
+async fn write_report(
+    root: Option<PathBuf>,
+    config: PathBuf,
+    format: ReportFormat,
+    spec: Option<String>,
+    impl_name: Option<String>,
+    out: Option<PathBuf>,
+    verbose: bool,
+    level: Option<&str>,
+) -> Result<()> {
+    let project_root = match root {
+        Some(r) => r,
+        None => find_project_root()?,
+    };
+    let config = tracey::load_config(&project_root.join(&config))?;
+    let data = tracey::data::build_dashboard_data(&project_root, &config, 1, true).await?;
+
+    // r[impl cli.report.level]
+    let selected: Vec<_> = data
+        .forward_by_impl
+        .iter()
+        .filter(|(key, _)| {
+            spec.as_ref().is_none_or(|s| s == &key.0)
+                && impl_name.as_ref().is_none_or(|i| i == &key.1)
+        })
+        .map(|(key, forward)| {
+            let mut forward = forward.clone();
+            forward.rules.retain(|r| tracey::level::matches(r, level));
+            (key, forward)
+        })
+        .collect();
+
+    if selected.is_empty() {
+        eyre::bail!(
+            "No spec/impl pairs matched (spec: {}, impl: {})",
+            spec.as_deref().unwrap_or("*"),
+            impl_name.as_deref().unwrap_or("*")
+        );
+    }
+
+    let rendered = match format {
+        ReportFormat::Coverage(format) => {
+            let reports: Vec<_> = selected
+                .iter()
+                .filter_map(|(key, _)| {
+                    let mut report =
+                        data.coverage_report_where(key, |r| tracey::level::matches(r, level))?;
+                    // Several impls can share a spec, so label reports by both
+                    report.spec_name = format!("{}/{}", key.0, key.1);
+                    Some(report)
+                })
+                .collect();
+            output::render_reports(&reports, format, verbose)
+        }
+        // r[impl cli.report.junit]
+        ReportFormat::Junit => output::render_junit(selected.iter().map(|(k, f)| (*k, f))),
+        // r[impl cli.report.matrix]
+        ReportFormat::Matrix(format) => format.render(selected.iter().map(|(k, f)| (*k, f))),
+    };
+
+    match out {
+        Some(path) => {
+            std::fs::write(&path, rendered)?;
+            eprintln!(
+                "{}: Wrote {} report(s) to {}",
+                "Success".green(),
+                selected.len(),
+                path.display()
+            );
+        }
+        None => print!("{}", rendered),
+    }
+
+    Ok(())
+}
+
 /// Configuration for tracing initialization.
 struct TracingConfig {
     /// If Some, also log to this file (creating parent dirs as needed).
@@ -1,11 +1,17 @@
 //! Output formatting for coverage reports
+//!
+//! r[impl cli.report.formats]
 
 use facet::Facet;
 use owo_colors::OwoColorize;
+use std::collections::HashMap;
+use tracey_api::{ValidationError, ValidationErrorCode, ValidationResult, VerifyStatus};
 use tracey_core::{CoverageReport, RefVerb};
 
+use crate::data::{ApiCodeRef, ApiRule, ApiSpecForward, ImplKey, html_escape};
+
 /// Output format
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum OutputFormat {
     #[default]
     Text,
@@ -14,14 +20,19 @@ pub enum OutputFormat {
     Html,
 }
 
-impl OutputFormat {
-    pub fn from_str(s: &str) -> Option<Self> {
+impl std::str::FromStr for OutputFormat {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
-            "text" => Some(Self::Text),
-            "json" => Some(Self::Json),
-            "markdown" | "md" => Some(Self::Markdown),
-            "html" => Some(Self::Html),
-            _ => None,
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "html" => Ok(Self::Html),
+            _ => Err(eyre::eyre!(
+                "Unknown format '{}' (expected text, json, markdown or html)",
+                s
+            )),
         }
     }
 }
@@ -36,6 +47,40 @@ pub fn render_report(report: &CoverageReport, format: OutputFormat, verbose: boo
     }
 }
 
+/// Render several coverage reports as a single document.
+///
+/// JSON output is an array of reports and HTML output is a single page,
+/// so the result is always valid for the chosen format.
+pub fn render_reports(reports: &[CoverageReport], format: OutputFormat, verbose: bool) -> String {
+    match format {
+        OutputFormat::Text => reports
+            .iter()
+            .map(|r| render_text(r, verbose))
+            .collect::<Vec<_>>()
+            .join(""),
+        OutputFormat::Json => {
+            let json_reports: Vec<JsonReport> = reports.iter().map(json_report).collect();
+            facet_json::to_string_pretty(&json_reports).expect("JSON serialization failed")
+        }
+        OutputFormat::Markdown => reports
+            .iter()
+            .map(|r| render_markdown(r, verbose))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputFormat::Html => {
+            let title = match reports {
+                [single] => format!("{} Coverage Report", html_escape(&single.spec_name)),
+                _ => "Coverage Report".to_string(),
+            };
+            let body: String = reports
+                .iter()
+                .map(|r| render_html_body(r, verbose))
+                .collect();
+            html_document(&title, &body)
+        }
+    }
+}
+
 fn render_text(report: &CoverageReport, verbose: bool) -> String {
     let mut output = String::new();
 
@@ -107,8 +152,8 @@ fn render_text(report: &CoverageReport, verbose: bool) -> String {
                 "-".red(),
                 r.file.display(),
                 r.line,
-                r.verb.as_str().dimmed(),
-                r.rule_id.yellow()
+                r.verb_name().dimmed(),
+                r.req_id.yellow()
             ));
         }
         output.push('\n');
@@ -199,16 +244,34 @@ struct JsonReport {
 #[derive(Facet)]
 struct JsonReference {
     verb: String,
-    rule_id: String,
+    req_id: String,
     file: String,
     line: usize,
 }
 
 fn render_json(report: &CoverageReport) -> String {
+    facet_json::to_string_pretty(&json_report(report)).expect("JSON serialization failed")
+}
+
+fn json_report(report: &CoverageReport) -> JsonReport {
     let mut uncovered: Vec<_> = report.uncovered_rules.iter().cloned().collect();
     uncovered.sort();
 
-    let json_report = JsonReport {
+    let mut references: Vec<JsonReference> = report
+        .references_by_rule
+        .values()
+        .flatten()
+        .map(|r| JsonReference {
+            verb: r.verb_name().to_string(),
+            req_id: r.req_id.clone(),
+            file: r.file.display().to_string(),
+            line: r.line,
+        })
+        .collect();
+    // HashMap iteration order is random; keep snapshots diffable
+    references.sort_by(|a, b| (&a.req_id, &a.file, a.line).cmp(&(&b.req_id, &b.file, b.line)));
+
+    JsonReport {
         spec_name: report.spec_name.clone(),
         total_rules: report.total_rules,
         covered_rules: report.covered_rules.len(),
@@ -218,26 +281,14 @@ fn render_json(report: &CoverageReport) -> String {
             .invalid_references
             .iter()
             .map(|r| JsonReference {
-                verb: r.verb.as_str().to_string(),
-                rule_id: r.rule_id.clone(),
+                verb: r.verb_name().to_string(),
+                req_id: r.req_id.clone(),
                 file: r.file.display().to_string(),
                 line: r.line,
             })
             .collect(),
-        references: report
-            .references_by_rule
-            .values()
-            .flatten()
-            .map(|r| JsonReference {
-                verb: r.verb.as_str().to_string(),
-                rule_id: r.rule_id.clone(),
-                file: r.file.display().to_string(),
-                line: r.line,
-            })
-            .collect(),
-    };
-
-    facet_json::to_string_pretty(&json_report).expect("JSON serialization failed")
+        references,
+    }
 }
 
 fn render_markdown(report: &CoverageReport, verbose: bool) -> String {
@@ -261,8 +312,8 @@ fn render_markdown(report: &CoverageReport, verbose: bool) -> String {
                 "- `{}:{}` - unknown rule `[{} {}]`\n",
                 r.file.display(),
                 r.line,
-                r.verb.as_str(),
-                r.rule_id
+                r.verb_name(),
+                r.req_id
             ));
         }
         output.push('\n');
@@ -296,14 +347,18 @@ fn render_markdown(report: &CoverageReport, verbose: bool) -> String {
 }
 
 fn render_html(report: &CoverageReport, verbose: bool) -> String {
+    html_document(
+        &format!("{} Coverage Report", html_escape(&report.spec_name)),
+        &render_html_body(report, verbose),
+    )
+}
+
+fn html_document(title: &str, body: &str) -> String {
     let mut output = String::new();
 
     output.push_str("<!DOCTYPE html>\n<html>\n<head>\n");
     output.push_str("<meta charset=\"utf-8\">\n");
-    output.push_str(&format!(
-        "<title>{} Coverage Report</title>\n",
-        report.spec_name
-    ));
+    output.push_str(&format!("<title>{}</title>\n", title));
     output.push_str("<style>\n");
     output.push_str("body { font-family: system-ui, sans-serif; max-width: 800px; margin: 2rem auto; padding: 0 1rem; }\n");
     output.push_str(".good { color: green; }\n");
@@ -312,8 +367,18 @@ fn render_html(report: &CoverageReport, verbose: bool) -> String {
     output.push_str("code { background: #f0f0f0; padding: 0.2em 0.4em; border-radius: 3px; }\n");
     output.push_str("</style>\n");
     output.push_str("</head>\n<body>\n");
+    output.push_str(body);
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+fn render_html_body(report: &CoverageReport, verbose: bool) -> String {
+    let mut output = String::new();
 
-    output.push_str(&format!("<h1>{} Coverage Report</h1>\n", report.spec_name));
+    output.push_str(&format!(
+        "<h1>{} Coverage Report</h1>\n",
+        html_escape(&report.spec_name)
+    ));
 
     let percent = report.coverage_percent();
     let class = if percent >= 80.0 {
@@ -337,10 +402,10 @@ fn render_html(report: &CoverageReport, verbose: bool) -> String {
         for r in &report.invalid_references {
             output.push_str(&format!(
                 "<li><code>{}:{}</code> - unknown rule <code>[{} {}]</code></li>\n",
-                r.file.display(),
+                html_escape(&r.file.display().to_string()),
                 r.line,
-                r.verb.as_str(),
-                r.rule_id
+                html_escape(r.verb_name()),
+                html_escape(&r.req_id)
             ));
         }
         output.push_str("</ul>\n");
@@ -352,7 +417,7 @@ fn render_html(report: &CoverageReport, verbose: bool) -> String {
         let mut uncovered: Vec<_> = report.uncovered_rules.iter().collect();
         uncovered.sort();
         for rule_id in uncovered {
-            output.push_str(&format!("<li><code>{}</code></li>\n", rule_id));
+            output.push_str(&format!("<li><code>{}</code></li>\n", html_escape(rule_id)));
         }
         output.push_str("</ul>\n");
     }
@@ -367,12 +432,979 @@ fn render_html(report: &CoverageReport, verbose: bool) -> String {
             let count = refs.map(|r| r.len()).unwrap_or(0);
             output.push_str(&format!(
                 "<li><code>{}</code> ({} references)</li>\n",
-                rule_id, count
+                html_escape(rule_id),
+                count
             ));
         }
         output.push_str("</ul>\n");
     }
 
-    output.push_str("</body>\n</html>\n");
     output
 }
+
+// ============================================================================
+// SARIF
+// ============================================================================
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Every validation error code, in the order rule descriptors are emitted
+const SARIF_RULES: &[ValidationErrorCode] = &[
+    ValidationErrorCode::CircularDependency,
+    ValidationErrorCode::InvalidNaming,
+    ValidationErrorCode::UnknownRequirement,
+    ValidationErrorCode::DuplicateRequirement,
+    ValidationErrorCode::UnknownPrefix,
+    ValidationErrorCode::ImplInTestFile,
+    ValidationErrorCode::StaleReference,
+    ValidationErrorCode::FailingVerification,
+    ValidationErrorCode::DeprecatedRequirement,
+    ValidationErrorCode::RemovedRequirement,
+    ValidationErrorCode::UnmatchedWildcard,
+];
+
+/// Stable rule ID and short description for a validation error code
+fn sarif_rule(code: ValidationErrorCode) -> (&'static str, &'static str) {
+    match code {
+        ValidationErrorCode::CircularDependency => (
+            "circular-dependency",
+            "Requirements depend on each other in a cycle",
+        ),
+        ValidationErrorCode::InvalidNaming => (
+            "invalid-naming",
+            "Requirement ID doesn't follow the dot-separated naming convention",
+        ),
+        ValidationErrorCode::UnknownRequirement => (
+            "unknown-requirement",
+            "Reference to a requirement that is not defined in the spec",
+        ),
+        ValidationErrorCode::DuplicateRequirement => (
+            "duplicate-requirement",
+            "Requirement ID is defined more than once",
+        ),
+        ValidationErrorCode::UnknownPrefix => (
+            "unknown-prefix",
+            "Reference uses a prefix that no configured spec declares",
+        ),
+        ValidationErrorCode::ImplInTestFile => (
+            "impl-in-test-file",
+            "Test file contains an impl annotation; only verify is allowed",
+        ),
+        ValidationErrorCode::StaleReference => (
+            "stale-reference",
+            "Reference was written against an older version of the requirement",
+        ),
+        ValidationErrorCode::FailingVerification => (
+            "failing-verification",
+            "A test verifying the requirement failed",
+        ),
+        ValidationErrorCode::DeprecatedRequirement => (
+            "deprecated-requirement",
+            "Implementation of a requirement whose status is deprecated",
+        ),
+        ValidationErrorCode::RemovedRequirement => (
+            "removed-requirement",
+            "Reference to a requirement whose status is removed",
+        ),
+        ValidationErrorCode::UnmatchedWildcard => (
+            "unmatched-wildcard",
+            "Wildcard reference that matches no requirement in the spec",
+        ),
+    }
+}
+
+/// SARIF level for a validation error code
+fn sarif_level(code: ValidationErrorCode) -> &'static str {
+    if code.is_warning() {
+        "warning"
+    } else {
+        "error"
+    }
+}
+
+#[derive(Facet)]
+struct SarifLog {
+    #[facet(rename = "$schema")]
+    schema: String,
+    version: String,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct SarifRun {
+    tool: SarifTool,
+    automation_details: SarifAutomationDetails,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Facet)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct SarifDriver {
+    name: String,
+    version: String,
+    information_uri: String,
+    rules: Vec<SarifReportingDescriptor>,
+}
+
+#[derive(Clone, Facet)]
+#[facet(rename_all = "camelCase")]
+struct SarifReportingDescriptor {
+    id: String,
+    name: String,
+    short_description: SarifMessage,
+    default_configuration: SarifConfiguration,
+}
+
+#[derive(Clone, Facet)]
+struct SarifConfiguration {
+    level: String,
+}
+
+#[derive(Facet)]
+struct SarifAutomationDetails {
+    id: String,
+}
+
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: String,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Clone, Facet)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Facet)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Facet)]
+#[facet(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    start_column: usize,
+}
+
+/// Render validation results as a SARIF 2.1.0 log.
+///
+/// Each spec/impl pair becomes its own run (with a distinct automation ID so
+/// code-scanning tools can tell them apart), and every validation error
+/// becomes one result. Errors without a file, such as circular dependencies,
+/// are emitted without a location.
+///
+/// r[impl cli.check.sarif]
+pub fn render_sarif(results: &[ValidationResult]) -> String {
+    let rules: Vec<SarifReportingDescriptor> = SARIF_RULES
+        .iter()
+        .map(|code| {
+            let (id, description) = sarif_rule(*code);
+            SarifReportingDescriptor {
+                id: id.to_string(),
+                name: format!("{:?}", code),
+                short_description: SarifMessage {
+                    text: description.to_string(),
+                },
+                default_configuration: SarifConfiguration {
+                    level: sarif_level(*code).to_string(),
+                },
+            }
+        })
+        .collect();
+
+    let runs = results
+        .iter()
+        .map(|result| SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "tracey".to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    information_uri: "https://github.com/bearcove/tracey".to_string(),
+                    rules: rules.clone(),
+                },
+            },
+            automation_details: SarifAutomationDetails {
+                id: format!("tracey/{}/{}/", result.spec, result.impl_name),
+            },
+            results: result.errors.iter().map(sarif_result).collect(),
+        })
+        .collect();
+
+    let log = SarifLog {
+        schema: SARIF_SCHEMA.to_string(),
+        version: "2.1.0".to_string(),
+        runs,
+    };
+
+    facet_json::to_string_pretty(&log).expect("SARIF serialization failed")
+}
+
+fn sarif_result(error: &ValidationError) -> SarifResult {
+    let (rule_id, _) = sarif_rule(error.code);
+    let rule_index = SARIF_RULES
+        .iter()
+        .position(|c| *c == error.code)
+        .unwrap_or_default();
+
+    let locations = match &error.file {
+        Some(file) => vec![SarifLocation {
+            physical_location: SarifPhysicalLocation {
+                artifact_location: SarifArtifactLocation {
+                    uri: file.replace('\\', "/"),
+                },
+                region: SarifRegion {
+                    start_line: error.line.unwrap_or(1).max(1),
+                    start_column: error.column.unwrap_or(1).max(1),
+                },
+            },
+        }],
+        None => Vec::new(),
+    };
+
+    SarifResult {
+        rule_id: rule_id.to_string(),
+        rule_index,
+        level: sarif_level(error.code).to_string(),
+        message: SarifMessage {
+            text: error.message.clone(),
+        },
+        locations,
+    }
+}
+
+// ============================================================================
+// JUnit XML
+// ============================================================================
+
+/// Render forward data as a JUnit XML document.
+///
+/// Each spec/impl pair becomes a `<testsuite>` and each requirement a
+/// `<testcase>`: it passes when it has both impl and verify references,
+/// fails when it has no impl references, and is skipped when it is
+/// implemented but untested. Draft, deprecated and removed requirements are
+/// skipped with their status. `<system-out>` lists the referencing locations.
+///
+/// r[impl cli.report.junit]
+pub fn render_junit<'a>(
+    suites: impl IntoIterator<Item = (&'a ImplKey, &'a ApiSpecForward)>,
+) -> String {
+    let mut body = String::new();
+    let mut total_tests = 0;
+    let mut total_failures = 0;
+    let mut total_skipped = 0;
+
+    for ((spec, impl_name), forward) in suites {
+        let suite_name = format!("{}/{}", spec, impl_name);
+        let tests = forward.rules.len();
+        let failures = forward.rules.iter().filter(|r| junit_failed(r)).count();
+        let skipped = forward
+            .rules
+            .iter()
+            .filter(|r| junit_skipped(r).is_some())
+            .count();
+
+        total_tests += tests;
+        total_failures += failures;
+        total_skipped += skipped;
+
+        body.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">\n",
+            xml_escape(&suite_name),
+            tests,
+            failures,
+            skipped
+        ));
+
+        for rule in &forward.rules {
+            let classname = match &rule.section_title {
+                Some(section) => format!("{}.{}", suite_name, section),
+                None => suite_name.clone(),
+            };
+            body.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\"",
+                xml_escape(&rule.id),
+                xml_escape(&classname)
+            ));
+            if let Some(file) = &rule.source_file {
+                body.push_str(&format!(" file=\"{}\"", xml_escape(file)));
+            }
+            if let Some(line) = rule.source_line {
+                body.push_str(&format!(" line=\"{}\"", line));
+            }
+            body.push_str(">\n");
+
+            if let Some(message) = junit_skipped(rule) {
+                body.push_str(&format!(
+                    "      <skipped message=\"{}\"/>\n",
+                    xml_escape(message)
+                ));
+            } else if junit_failed(rule) {
+                body.push_str(
+                    "      <failure message=\"No implementation references\" type=\"uncovered\"/>\n",
+                );
+            }
+
+            let mut out = String::new();
+            for (verb, refs) in [
+                ("impl", &rule.impl_refs),
+                ("verify", &rule.verify_refs),
+                ("depends", &rule.depends_refs),
+            ] {
+                for r in refs {
+                    out.push_str(&format!("{}: {}:{}\n", verb, r.file, r.line));
+                }
+            }
+            if !out.is_empty() {
+                body.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    xml_escape(&out)
+                ));
+            }
+
+            body.push_str("    </testcase>\n");
+        }
+
+        body.push_str("  </testsuite>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"tracey\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\">\n\
+         {}</testsuites>\n",
+        total_tests, total_failures, total_skipped, body
+    )
+}
+
+/// Whether `rule` fails as a JUnit test case: it counts toward coverage but
+/// has no impl references.
+fn junit_failed(rule: &ApiRule) -> bool {
+    rule.counts_toward_coverage() && rule.impl_refs.is_empty()
+}
+
+/// Why `rule` is skipped as a JUnit test case, if it is.
+///
+/// r[impl status.coverage]
+fn junit_skipped(rule: &ApiRule) -> Option<&str> {
+    if !rule.counts_toward_coverage() {
+        rule.status.as_deref()
+    } else if !rule.impl_refs.is_empty() && rule.verify_refs.is_empty() {
+        Some("Implemented but not verified")
+    } else {
+        None
+    }
+}
+
+/// Escape XML special characters in text and attribute values
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// ============================================================================
+// Traceability matrix
+// ============================================================================
+
+/// Formats of the requirement × impl traceability matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixFormat {
+    /// RFC 4180 CSV, one row per requirement of every spec
+    Csv,
+    /// SpreadsheetML 2003 XML, one worksheet per spec
+    Spreadsheet,
+}
+
+impl std::str::FromStr for MatrixFormat {
+    type Err = eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "spreadsheet" | "xml" => Ok(Self::Spreadsheet),
+            _ => Err(eyre::eyre!(
+                "Unknown matrix format '{}' (expected csv or spreadsheet)",
+                s
+            )),
+        }
+    }
+}
+
+impl MatrixFormat {
+    /// Render the matrix for `suites` in this format.
+    pub fn render<'a>(
+        self,
+        suites: impl IntoIterator<Item = (&'a ImplKey, &'a ApiSpecForward)>,
+    ) -> String {
+        match self {
+            Self::Csv => render_matrix_csv(suites),
+            Self::Spreadsheet => render_matrix_spreadsheet(suites),
+        }
+    }
+
+    /// MIME type of rendered matrices
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Spreadsheet => "application/vnd.ms-excel",
+        }
+    }
+
+    /// File extension of rendered matrices
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Spreadsheet => "xml",
+        }
+    }
+}
+
+/// A spec and the forward data of each of its impls.
+struct MatrixSpec<'a> {
+    name: &'a str,
+    impls: Vec<(&'a str, &'a ApiSpecForward)>,
+}
+
+fn group_matrix_specs<'a>(
+    suites: impl IntoIterator<Item = (&'a ImplKey, &'a ApiSpecForward)>,
+) -> Vec<MatrixSpec<'a>> {
+    let mut specs: Vec<MatrixSpec<'a>> = Vec::new();
+    for ((spec, impl_name), forward) in suites {
+        match specs.iter_mut().find(|s| s.name == spec) {
+            Some(s) => s.impls.push((impl_name, forward)),
+            None => specs.push(MatrixSpec {
+                name: spec,
+                impls: vec![(impl_name, forward)],
+            }),
+        }
+    }
+    specs
+}
+
+/// Coverage verdict of a requirement in one impl.
+///
+/// r[impl matrix.verdict]
+fn matrix_verdict(rule: &ApiRule) -> &'static str {
+    if rule.impl_refs.is_empty() {
+        "uncovered"
+    } else if rule.verify_status == Some(VerifyStatus::Failing) {
+        "failing"
+    } else if rule.verify_refs.is_empty() {
+        "untested"
+    } else {
+        "covered"
+    }
+}
+
+fn matrix_locations(refs: &[ApiCodeRef]) -> String {
+    refs.iter()
+        .map(|r| format!("{}:{}", r.file, r.line))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Header and rows of the matrix for `specs`, with one column group per
+/// entry of `impls`. Requirements come from the spec's first impl, since
+/// every impl of a spec sees the same requirements.
+///
+/// r[impl matrix.rows]
+/// r[impl matrix.columns]
+fn matrix_table(specs: &[MatrixSpec], impls: &[&str], spec_column: bool) -> Vec<Vec<String>> {
+    let mut header: Vec<String> = Vec::new();
+    if spec_column {
+        header.push("spec".to_string());
+    }
+    header.extend(
+        ["id", "section_title", "source", "status", "level"]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    for impl_name in impls {
+        for column in ["impl", "verify", "depends", "verdict"] {
+            header.push(format!("{} {}", impl_name, column));
+        }
+    }
+
+    let mut table = vec![header];
+    for spec in specs {
+        let Some((_, first)) = spec.impls.first() else {
+            continue;
+        };
+        let by_impl: HashMap<&str, HashMap<&str, &ApiRule>> = spec
+            .impls
+            .iter()
+            .map(|(name, forward)| {
+                let rules = forward.rules.iter().map(|r| (r.id.as_str(), r)).collect();
+                (*name, rules)
+            })
+            .collect();
+
+        for rule in &first.rules {
+            let mut row: Vec<String> = Vec::new();
+            if spec_column {
+                row.push(spec.name.to_string());
+            }
+            let source = match (&rule.source_file, rule.source_line) {
+                (Some(file), Some(line)) => format!("{}:{}", file, line),
+                (Some(file), None) => file.clone(),
+                _ => String::new(),
+            };
+            row.extend([
+                rule.id.clone(),
+                rule.section_title.clone().unwrap_or_default(),
+                source,
+                rule.status.clone().unwrap_or_default(),
+                rule.level.clone().unwrap_or_default(),
+            ]);
+            for impl_name in impls {
+                match by_impl.get(impl_name).and_then(|r| r.get(rule.id.as_str())) {
+                    Some(r) => row.extend([
+                        matrix_locations(&r.impl_refs),
+                        matrix_locations(&r.verify_refs),
+                        matrix_locations(&r.depends_refs),
+                        matrix_verdict(r).to_string(),
+                    ]),
+                    None => row.extend(std::iter::repeat_n(String::new(), 4)),
+                }
+            }
+            table.push(row);
+        }
+    }
+    table
+}
+
+/// Render the traceability matrix as CSV.
+///
+/// r[impl matrix.csv]
+///
+/// All specs go in one table with a `spec` column, and a column group for
+/// every impl name seen in any spec.
+pub fn render_matrix_csv<'a>(
+    suites: impl IntoIterator<Item = (&'a ImplKey, &'a ApiSpecForward)>,
+) -> String {
+    let specs = group_matrix_specs(suites);
+    let mut impls: Vec<&str> = Vec::new();
+    for (name, _) in specs.iter().flat_map(|s| &s.impls) {
+        if !impls.contains(name) {
+            impls.push(name);
+        }
+    }
+
+    let mut out = String::new();
+    for row in matrix_table(&specs, &impls, true) {
+        let cells: Vec<String> = row.iter().map(|c| csv_escape(c)).collect();
+        out.push_str(&cells.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Render the traceability matrix as a SpreadsheetML 2003 workbook, which
+/// Excel and LibreOffice open directly.
+///
+/// r[impl matrix.spreadsheet]
+pub fn render_matrix_spreadsheet<'a>(
+    suites: impl IntoIterator<Item = (&'a ImplKey, &'a ApiSpecForward)>,
+) -> String {
+    let mut body = String::new();
+    let mut sheet_names: Vec<String> = Vec::new();
+    for spec in group_matrix_specs(suites) {
+        let impls: Vec<&str> = spec.impls.iter().map(|(name, _)| *name).collect();
+        let table = matrix_table(std::slice::from_ref(&spec), &impls, false);
+
+        body.push_str(&format!(
+            " <Worksheet ss:Name=\"{}\">\n  <Table>\n",
+            xml_escape(&sheet_name(spec.name, &mut sheet_names))
+        ));
+        for (idx, row) in table.iter().enumerate() {
+            body.push_str(if idx == 0 {
+                "   <Row ss:StyleID=\"header\">"
+            } else {
+                "   <Row>"
+            });
+            for cell in row {
+                body.push_str(&format!(
+                    "<Cell><Data ss:Type=\"String\">{}</Data></Cell>",
+                    xml_escape(cell)
+                ));
+            }
+            body.push_str("</Row>\n");
+        }
+        body.push_str("  </Table>\n </Worksheet>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <?mso-application progid=\"Excel.Sheet\"?>\n\
+         <Workbook xmlns=\"urn:schemas-microsoft-com:office:spreadsheet\" \
+         xmlns:ss=\"urn:schemas-microsoft-com:office:spreadsheet\">\n\
+         \x20<Styles><Style ss:ID=\"header\"><Font ss:Bold=\"1\"/></Style></Styles>\n\
+         {}</Workbook>\n",
+        body
+    )
+}
+
+/// Quote a CSV field if it needs it
+fn csv_escape(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Worksheet names are at most 31 characters, can't contain `[]:*?/\` and
+/// must be unique within a workbook (ignoring case). Names that collide after
+/// sanitizing get a ` (2)`, ` (3)`... suffix; `used` collects the names given
+/// out so far.
+fn sheet_name(name: &str, used: &mut Vec<String>) -> String {
+    let sanitized: Vec<char> = name
+        .chars()
+        .map(|c| if "[]:*?/\\".contains(c) { '_' } else { c })
+        .collect();
+    let mut candidate: String = sanitized.iter().take(31).collect();
+    let mut n = 2;
+    while used
+        .iter()
+        .any(|u| u.to_lowercase() == candidate.to_lowercase())
+    {
+        let suffix = format!(" ({})", n);
+        candidate = sanitized.iter().take(31 - suffix.len()).collect();
+        candidate.push_str(&suffix);
+        n += 1;
+    }
+    used.push(candidate.clone());
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::path::Path;
+    use tracey_core::Reqs;
+
+    /// A rule with `impl_refs` references in src/lib.rs and `verify_refs` in tests/a&b.rs
+    fn test_rule(id: &str, impl_refs: usize, verify_refs: usize) -> ApiRule {
+        ApiRule {
+            id: id.to_string(),
+            version: 1,
+            raw: String::new(),
+            html: String::new(),
+            status: None,
+            level: None,
+            source_file: Some("spec.md".to_string()),
+            source_line: Some(1),
+            source_column: None,
+            section: None,
+            section_title: None,
+            impl_refs: (0..impl_refs)
+                .map(|i| ApiCodeRef {
+                    file: "src/lib.rs".to_string(),
+                    line: i + 1,
+                })
+                .collect(),
+            verify_refs: (0..verify_refs)
+                .map(|i| ApiCodeRef {
+                    file: "tests/a&b.rs".to_string(),
+                    line: i + 1,
+                })
+                .collect(),
+            depends_refs: vec![],
+            stale_refs: vec![],
+            verify_status: None,
+            custom_refs: Vec::new(),
+        }
+    }
+
+    fn sample_report(name: &str) -> CoverageReport {
+        let known: HashSet<String> = ["auth.login", "auth.logout"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let reqs = Reqs::extract_from_content(
+            Path::new("src/lib.rs"),
+            "// r[impl auth.login]\nfn login() {}\n// r[impl auth.missing]\nfn other() {}\n",
+        );
+        CoverageReport::compute(name, &known, &reqs)
+    }
+
+    // r[verify cli.report.formats]
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!(
+            "md".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Markdown
+        );
+        assert_eq!("JSON".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_render_reports_json_is_array() {
+        let reports = [sample_report("a/main"), sample_report("b/main")];
+        let json = render_reports(&reports, OutputFormat::Json, false);
+
+        assert!(json.trim_start().starts_with('['), "got: {json}");
+        assert!(json.contains("a/main") && json.contains("b/main"));
+        assert!(
+            json.contains("auth.missing"),
+            "invalid refs should be listed"
+        );
+    }
+
+    #[test]
+    fn test_render_reports_html_is_single_document() {
+        let reports = [sample_report("a/main"), sample_report("b/main")];
+        let html = render_reports(&reports, OutputFormat::Html, false);
+
+        assert_eq!(html.matches("<html>").count(), 1);
+        assert_eq!(html.matches("<h1>").count(), 2);
+    }
+
+    #[test]
+    fn test_render_html_escapes_paths() {
+        let known: HashSet<String> = ["auth.login"].iter().map(|s| s.to_string()).collect();
+        let reqs = Reqs::extract_from_content(
+            Path::new("src/<gen>&.rs"),
+            "// r[impl auth.missing]\nfn other() {}\n",
+        );
+        let report = CoverageReport::compute("a/main", &known, &reqs);
+        let html = render_reports(&[report], OutputFormat::Html, false);
+
+        assert!(html.contains("src/&lt;gen&gt;&amp;.rs:1"), "got: {html}");
+        assert!(!html.contains("<gen>"));
+    }
+
+    // r[verify cli.check.sarif]
+    #[test]
+    fn test_render_sarif() {
+        let result = ValidationResult {
+            spec: "test".to_string(),
+            impl_name: "rust".to_string(),
+            errors: vec![
+                ValidationError {
+                    code: ValidationErrorCode::UnknownRequirement,
+                    message: "Reference to unknown rule 'auth.missing'".to_string(),
+                    file: Some("src/lib.rs".to_string()),
+                    line: Some(3),
+                    column: None,
+                    related_rules: vec![],
+                },
+                ValidationError {
+                    code: ValidationErrorCode::CircularDependency,
+                    message: "Circular dependency detected: a → b → a".to_string(),
+                    file: None,
+                    line: None,
+                    column: None,
+                    related_rules: vec!["a".to_string(), "b".to_string()],
+                },
+            ],
+            warning_count: 0,
+            error_count: 2,
+        };
+
+        let sarif = render_sarif(&[result]);
+
+        assert!(sarif.contains("2.1.0"), "got: {sarif}");
+        assert!(sarif.contains("\"$schema\""));
+        // One descriptor per error code
+        for code in SARIF_RULES {
+            assert!(sarif.contains(sarif_rule(*code).0));
+        }
+        assert!(sarif.contains("\"ruleId\""));
+        assert!(sarif.contains("\"src/lib.rs\""));
+        assert!(sarif.contains("\"startLine\""));
+        assert!(sarif.contains("tracey/test/rust/"));
+    }
+
+    // r[verify cli.report.junit]
+    #[test]
+    fn test_render_junit() {
+        let forward = ApiSpecForward {
+            name: "test".to_string(),
+            rules: vec![
+                test_rule("a.pass", 1, 1),
+                test_rule("a.fail", 0, 0),
+                test_rule("a.skip", 1, 0),
+            ],
+            exclude_levels: vec![],
+        };
+        let key: ImplKey = ("test".to_string(), "rust".to_string());
+
+        let xml = render_junit([(&key, &forward)]);
+
+        assert!(xml.contains("<testsuite name=\"test/rust\" tests=\"3\" failures=\"1\""));
+        assert!(xml.contains("skipped=\"1\""));
+        assert_eq!(xml.matches("<testcase ").count(), 3);
+        assert_eq!(xml.matches("<failure ").count(), 1);
+        assert_eq!(xml.matches("<skipped ").count(), 1);
+        assert!(xml.contains("impl: src/lib.rs:1"));
+        assert!(xml.contains("verify: tests/a&amp;b.rs:1"));
+    }
+
+    // r[verify cli.report.junit]
+    #[test]
+    fn test_render_junit_skips_non_counting_rules() {
+        let mut draft = test_rule("a.draft", 0, 0);
+        draft.status = Some(tracey_api::STATUS_DRAFT.to_string());
+        let mut removed = test_rule("a.removed", 0, 0);
+        removed.status = Some(tracey_api::STATUS_REMOVED.to_string());
+        let forward = ApiSpecForward {
+            name: "test".to_string(),
+            rules: vec![draft, removed, test_rule("a.fail", 0, 0)],
+            exclude_levels: vec![],
+        };
+        let key: ImplKey = ("test".to_string(), "rust".to_string());
+
+        let xml = render_junit([(&key, &forward)]);
+
+        assert!(xml.contains("tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"2\""));
+        assert_eq!(xml.matches("<failure ").count(), 1);
+        assert!(xml.contains(&format!(
+            "<skipped message=\"{}\"/>",
+            tracey_api::STATUS_DRAFT
+        )));
+        assert!(xml.contains(&format!(
+            "<skipped message=\"{}\"/>",
+            tracey_api::STATUS_REMOVED
+        )));
+    }
+
+    // r[verify cli.report.matrix]
+    // r[verify matrix.rows]
+    // r[verify matrix.columns]
+    // r[verify matrix.verdict]
+    // r[verify matrix.csv]
+    #[test]
+    fn test_render_matrix_csv() {
+        let mut failing = test_rule("a.failing", 1, 1);
+        failing.verify_status = Some(VerifyStatus::Failing);
+        let mut titled = test_rule("a.covered", 2, 1);
+        titled.section_title = Some("Login, logout".to_string());
+        titled.level = Some("must".to_string());
+
+        let rust_key: ImplKey = ("test".to_string(), "rust".to_string());
+        let rust = ApiSpecForward {
+            name: "test".to_string(),
+            rules: vec![titled, test_rule("a.untested", 1, 0), failing],
+            exclude_levels: vec![],
+        };
+        let ts_key: ImplKey = ("test".to_string(), "ts".to_string());
+        let ts = ApiSpecForward {
+            name: "test".to_string(),
+            rules: vec![test_rule("a.covered", 0, 0), test_rule("a.untested", 0, 0)],
+            exclude_levels: vec![],
+        };
+        let other_key: ImplKey = ("other".to_string(), "rust".to_string());
+        let other = ApiSpecForward {
+            name: "other".to_string(),
+            rules: vec![test_rule("o.one", 1, 1)],
+            exclude_levels: vec![],
+        };
+
+        let csv = render_matrix_csv([(&rust_key, &rust), (&ts_key, &ts), (&other_key, &other)]);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(
+            lines[0],
+            "spec,id,section_title,source,status,level,\
+             rust impl,rust verify,rust depends,rust verdict,\
+             ts impl,ts verify,ts depends,ts verdict"
+        );
+        assert_eq!(
+            lines[1],
+            "test,a.covered,\"Login, logout\",spec.md:1,,must,\
+             src/lib.rs:1; src/lib.rs:2,tests/a&b.rs:1,,covered,,,,uncovered"
+        );
+        assert!(lines[2].ends_with(",untested,,,,uncovered"));
+        // Not in the ts impl at all
+        assert!(lines[3].ends_with(",failing,,,,"));
+        assert!(lines[4].starts_with("other,o.one,"));
+        assert_eq!(lines.len(), 6, "trailing CRLF");
+    }
+
+    // r[verify matrix.spreadsheet]
+    #[test]
+    fn test_render_matrix_spreadsheet() {
+        let rust_key: ImplKey = ("test".to_string(), "rust".to_string());
+        let rust = ApiSpecForward {
+            name: "test".to_string(),
+            rules: vec![test_rule("a.one", 1, 1)],
+            exclude_levels: vec![],
+        };
+        let other_key: ImplKey = ("a/b".to_string(), "rust".to_string());
+        let other = ApiSpecForward {
+            name: "a/b".to_string(),
+            rules: vec![test_rule("o.one", 0, 0)],
+            exclude_levels: vec![],
+        };
+
+        let xml = render_matrix_spreadsheet([(&rust_key, &rust), (&other_key, &other)]);
+
+        assert!(xml.contains("<?mso-application progid=\"Excel.Sheet\"?>"));
+        assert_eq!(xml.matches("<Worksheet ").count(), 2);
+        assert!(xml.contains("<Worksheet ss:Name=\"test\">"));
+        assert!(xml.contains("<Worksheet ss:Name=\"a_b\">"));
+        assert_eq!(xml.matches("<Row").count(), 4);
+        assert!(xml.contains(">tests/a&amp;b.rs:1<"));
+        assert!(!xml.contains(">spec<"), "no spec column per sheet");
+        assert_eq!(
+            "XML".parse::<MatrixFormat>().unwrap(),
+            MatrixFormat::Spreadsheet
+        );
+    }
+
+    #[test]
+    fn test_sheet_names_are_unique() {
+        let mut used = Vec::new();
+        let long = "requirements-for-the-storage-engine";
+        assert_eq!(sheet_name("a/b", &mut used), "a_b");
+        assert_eq!(sheet_name("A_b", &mut used), "A_b (2)");
+        assert_eq!(sheet_name(long, &mut used), &long[..31]);
+        let second = sheet_name(&format!("{long}-v2"), &mut used);
+        assert_eq!(second, format!("{} (2)", &long[..27]));
+        assert_eq!(second.chars().count(), 31);
+    }
+
+    #[test]
+    fn test_render_markdown_lists_uncovered() {
+        let markdown = render_report(&sample_report("a/main"), OutputFormat::Markdown, false);
+
+        assert!(markdown.contains("## Uncovered Rules"));
+        assert!(markdown.contains("`auth.logout`"));
+    }
+}
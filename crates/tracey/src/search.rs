@@ -9,6 +9,7 @@
 use facet::Facet;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
 /// Result type for unified search
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Facet)]
@@ -51,12 +52,52 @@ pub trait SearchIndex: Send + Sync {
     /// Search for a query string, returning up to `limit` results
     fn search(&self, query: &str, limit: usize) -> Vec<SearchResult>;
 
+    /// Search rules only, best match first, for a query of plain words any
+    /// of which may match (see [`code_query`])
+    fn search_rules(&self, query: &str, limit: usize) -> Vec<SearchResult>;
+
+    /// Re-index the given source files (`None` drops a file) and, if `rules`
+    /// is given, replace the indexed rules, leaving the rest of the index as is
+    fn update(
+        &self,
+        project_root: &Path,
+        files: &[(&Path, Option<&str>)],
+        rules: Option<&[RuleEntry]>,
+    );
+
     /// Check if search is available
     fn is_available(&self) -> bool {
         true
     }
 }
 
+/// Lines of context indexed on each side of a source line
+const CONTEXT_LINES: usize = 2;
+
+/// ID of an indexed source file: its path relative to the project root
+fn source_id(project_root: &Path, path: &Path) -> String {
+    path.strip_prefix(project_root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// The indexed lines of a source file as (1-indexed line number, line with
+/// surrounding context), skipping empty and very short lines
+fn source_lines(content: &str) -> Vec<(usize, String)> {
+    let lines: Vec<&str> = content.lines().collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.trim().len() >= 3)
+        .map(|(idx, _)| {
+            let start = idx.saturating_sub(CONTEXT_LINES);
+            let end = (idx + CONTEXT_LINES + 1).min(lines.len());
+            (idx + 1, lines[start..end].join("\n"))
+        })
+        .collect()
+}
+
 // ============================================================================
 // Tantivy implementation (when 'search' feature is enabled)
 // ============================================================================
@@ -64,6 +105,7 @@ pub trait SearchIndex: Send + Sync {
 #[cfg(feature = "search")]
 mod tantivy_impl {
     use super::*;
+    use std::sync::Mutex;
     use tantivy::collector::TopDocs;
     use tantivy::query::QueryParser;
     use tantivy::schema::{
@@ -71,11 +113,13 @@ mod tantivy_impl {
         Value,
     };
     use tantivy::snippet::SnippetGenerator;
-    use tantivy::{Index, IndexWriter, ReloadPolicy, doc};
+    use tantivy::{Index, IndexWriter, ReloadPolicy, Term, doc};
 
     pub struct TantivyIndex {
         #[allow(dead_code)]
         index: Index,
+        /// Kept open so files can be re-indexed without rebuilding the index
+        writer: Mutex<IndexWriter>,
         reader: tantivy::IndexReader,
         query_parser: QueryParser,
         schema: Schema,
@@ -107,11 +151,11 @@ mod tantivy_impl {
             );
 
             // "kind" field: "source" or "rule"
-            let kind_field = schema_builder.add_text_field("kind", STRING | STORED);
+            schema_builder.add_text_field("kind", STRING | STORED);
             // "id" field: file path for source, rule ID for rules
-            let id_field = schema_builder.add_text_field("id", STRING | STORED);
+            schema_builder.add_text_field("id", STRING | STORED);
             // "line" field: line number for source (0 for rules)
-            let line_field = schema_builder.add_u64_field("line", INDEXED | STORED);
+            schema_builder.add_u64_field("line", INDEXED | STORED);
             // "content" field: the searchable text content with stemming
             let content_field = schema_builder.add_text_field("content", text_options);
             // "rule_id" field: searchable rule ID with dot-separated parts (not stored)
@@ -122,57 +166,7 @@ mod tantivy_impl {
             let index = Index::create_in_ram(schema.clone());
 
             // Use single thread to avoid worker thread panics on some platforms
-            let mut index_writer: IndexWriter = index.writer_with_num_threads(1, 15_000_000)?;
-
-            // Index source files with context lines
-            const CONTEXT_LINES: usize = 2;
-
-            for (path, content) in files {
-                let relative = path
-                    .strip_prefix(project_root)
-                    .unwrap_or(path)
-                    .display()
-                    .to_string();
-
-                let lines: Vec<&str> = content.lines().collect();
-
-                // Index each line with surrounding context
-                for (idx, line_content) in lines.iter().enumerate() {
-                    let line_num = idx + 1; // 1-indexed
-                    let trimmed = line_content.trim();
-
-                    // Skip empty lines and very short lines
-                    if trimmed.len() < 3 {
-                        continue;
-                    }
-
-                    // Build content with context: 2 lines before + current + 2 lines after
-                    let start = idx.saturating_sub(CONTEXT_LINES);
-                    let end = (idx + CONTEXT_LINES + 1).min(lines.len());
-                    let content_with_context = lines[start..end].join("\n");
-
-                    index_writer.add_document(doc!(
-                        kind_field => "source",
-                        id_field => relative.clone(),
-                        line_field => line_num as u64,
-                        content_field => content_with_context,
-                    ))?;
-                }
-            }
-
-            // Index rules - use raw markdown directly (rule_id_field handles ID search)
-            // r[impl dashboard.search.render-requirements]
-            for rule in rules {
-                index_writer.add_document(doc!(
-                    kind_field => "rule",
-                    id_field => rule.id.clone(),
-                    line_field => 0u64,
-                    content_field => rule.raw.clone(),
-                    rule_id_field => rule.id.clone(),
-                ))?;
-            }
-
-            index_writer.commit()?;
+            let writer: IndexWriter = index.writer_with_num_threads(1, 15_000_000)?;
 
             let reader = index
                 .reader_builder()
@@ -184,13 +178,72 @@ mod tantivy_impl {
                 QueryParser::for_index(&index, vec![content_field, rule_id_field]);
             query_parser.set_field_boost(rule_id_field, 5.0); // Boost rule ID matches
 
-            Ok(Self {
+            let index = Self {
                 index,
+                writer: Mutex::new(writer),
                 reader,
                 query_parser,
                 schema,
                 content_field,
-            })
+            };
+            let files: Vec<(&Path, Option<&str>)> = files
+                .iter()
+                .map(|(path, content)| (path.as_path(), Some(content.as_str())))
+                .collect();
+            index.write(project_root, &files, Some(rules))?;
+            Ok(index)
+        }
+
+        /// Replace the documents of the given source files (and of all
+        /// rules, if given), then commit and reload the reader
+        fn write(
+            &self,
+            project_root: &Path,
+            files: &[(&Path, Option<&str>)],
+            rules: Option<&[RuleEntry]>,
+        ) -> eyre::Result<()> {
+            let kind_field = self.schema.get_field("kind")?;
+            let id_field = self.schema.get_field("id")?;
+            let line_field = self.schema.get_field("line")?;
+            let rule_id_field = self.schema.get_field("rule_id")?;
+            let content_field = self.content_field;
+
+            let mut writer = self.writer.lock().unwrap();
+
+            // Index source files with context lines
+            for (path, content) in files {
+                let relative = source_id(project_root, path);
+                writer.delete_term(Term::from_field_text(id_field, &relative));
+                for (line_num, content_with_context) in
+                    content.map(source_lines).unwrap_or_default()
+                {
+                    writer.add_document(doc!(
+                        kind_field => "source",
+                        id_field => relative.clone(),
+                        line_field => line_num as u64,
+                        content_field => content_with_context,
+                    ))?;
+                }
+            }
+
+            // Index rules - use raw markdown directly (rule_id_field handles ID search)
+            // r[impl dashboard.search.render-requirements]
+            if let Some(rules) = rules {
+                writer.delete_term(Term::from_field_text(kind_field, "rule"));
+                for rule in rules {
+                    writer.add_document(doc!(
+                        kind_field => "rule",
+                        id_field => rule.id.clone(),
+                        line_field => 0u64,
+                        content_field => rule.raw.clone(),
+                        rule_id_field => rule.id.clone(),
+                    ))?;
+                }
+            }
+
+            writer.commit()?;
+            self.reader.reload()?;
+            Ok(())
         }
     }
 
@@ -283,6 +336,24 @@ mod tantivy_impl {
 
             results
         }
+
+        fn search_rules(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+            if query.trim().is_empty() {
+                return vec![];
+            }
+            self.search(&format!("+kind:rule +({})", query), limit)
+        }
+
+        fn update(
+            &self,
+            project_root: &Path,
+            files: &[(&Path, Option<&str>)],
+            rules: Option<&[RuleEntry]>,
+        ) {
+            if let Err(e) = self.write(project_root, files, rules) {
+                eprintln!("Warning: Failed to update tantivy index: {}", e);
+            }
+        }
     }
 
     /// Insert `<mark>` tags at the given byte ranges without HTML-escaping content.
@@ -332,16 +403,21 @@ pub use tantivy_impl::TantivyIndex;
 // ============================================================================
 
 /// Entry in the simple index
-struct SimpleEntry {
-    kind: ResultKind,
-    id: String,
-    line: usize,
-    content: String,
+#[derive(Debug, Clone, Facet)]
+pub struct SimpleEntry {
+    /// Type of entry
+    pub kind: ResultKind,
+    /// For Source: file path. For Rule: rule ID
+    pub id: String,
+    /// For Source: line number. For Rule: 0
+    pub line: usize,
+    /// Line content with context, or rule text
+    pub content: String,
 }
 
 /// Simple substring search fallback when tantivy is not available
 pub struct SimpleIndex {
-    entries: Vec<SimpleEntry>,
+    entries: RwLock<Vec<SimpleEntry>>,
 }
 
 impl SimpleIndex {
@@ -351,49 +427,20 @@ impl SimpleIndex {
         files: &BTreeMap<PathBuf, String>,
         rules: &[RuleEntry],
     ) -> Self {
-        let mut entries = Vec::new();
-
-        // Index source files with context lines
-        const CONTEXT_LINES: usize = 2;
-
-        for (path, content) in files {
-            let relative = path
-                .strip_prefix(project_root)
-                .unwrap_or(path)
-                .display()
-                .to_string();
-
-            let lines: Vec<&str> = content.lines().collect();
-
-            for (idx, line_content) in lines.iter().enumerate() {
-                let line_num = idx + 1;
-                if line_content.trim().len() >= 3 {
-                    // Build content with context
-                    let start = idx.saturating_sub(CONTEXT_LINES);
-                    let end = (idx + CONTEXT_LINES + 1).min(lines.len());
-                    let content_with_context = lines[start..end].join("\n");
-
-                    entries.push(SimpleEntry {
-                        kind: ResultKind::Source,
-                        id: relative.clone(),
-                        line: line_num,
-                        content: content_with_context,
-                    });
-                }
-            }
-        }
-
-        // Index rules - use raw markdown directly
-        for rule in rules {
-            entries.push(SimpleEntry {
-                kind: ResultKind::Rule,
-                id: rule.id.clone(),
-                line: 0,
-                content: rule.raw.clone(),
-            });
-        }
+        let index = Self {
+            entries: RwLock::new(Vec::new()),
+        };
+        let files: Vec<(&Path, Option<&str>)> = files
+            .iter()
+            .map(|(path, content)| (path.as_path(), Some(content.as_str())))
+            .collect();
+        index.update(project_root, &files, Some(rules));
+        index
+    }
 
-        Self { entries }
+    /// All indexed entries, for searching outside of tracey (static exports)
+    pub fn entries(&self) -> Vec<SimpleEntry> {
+        self.entries.read().unwrap().clone()
     }
 }
 
@@ -401,8 +448,8 @@ impl SearchIndex for SimpleIndex {
     fn search(&self, query: &str, limit: usize) -> Vec<SearchResult> {
         let query_lower = query.to_lowercase();
 
-        let mut results: Vec<SearchResult> = self
-            .entries
+        let entries = self.entries.read().unwrap();
+        let mut results: Vec<SearchResult> = entries
             .iter()
             // Match against both content and id (for rule ID searches)
             .filter(|e| {
@@ -435,11 +482,137 @@ impl SearchIndex for SimpleIndex {
         results
     }
 
+    fn search_rules(&self, query: &str, limit: usize) -> Vec<SearchResult> {
+        let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+
+        // Score each rule by how many of the words it contains
+        let entries = self.entries.read().unwrap();
+        let mut results: Vec<SearchResult> = entries
+            .iter()
+            .filter(|e| e.kind == ResultKind::Rule)
+            .filter_map(|e| {
+                let content = e.content.to_lowercase();
+                let hits = words
+                    .iter()
+                    .filter(|w| content.contains(w.as_str()) || e.id.contains(w.as_str()))
+                    .count();
+                (hits > 0).then(|| SearchResult {
+                    kind: e.kind,
+                    id: e.id.clone(),
+                    line: e.line,
+                    content: e.content.clone(),
+                    highlighted: html_escape(&e.content),
+                    score: hits as f32,
+                })
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.total_cmp(&a.score));
+        results.truncate(limit);
+        results
+    }
+
+    fn update(
+        &self,
+        project_root: &Path,
+        files: &[(&Path, Option<&str>)],
+        rules: Option<&[RuleEntry]>,
+    ) {
+        let mut entries = self.entries.write().unwrap();
+
+        // Index source files with context lines, ahead of the rules
+        for (path, content) in files {
+            let relative = source_id(project_root, path);
+            entries.retain(|e| e.kind != ResultKind::Source || e.id != relative);
+            let at = entries
+                .iter()
+                .position(|e| e.kind == ResultKind::Rule)
+                .unwrap_or(entries.len());
+            let lines = content.map(source_lines).unwrap_or_default();
+            entries.splice(
+                at..at,
+                lines.into_iter().map(|(line, content)| SimpleEntry {
+                    kind: ResultKind::Source,
+                    id: relative.clone(),
+                    line,
+                    content,
+                }),
+            );
+        }
+
+        // Index rules - use raw markdown directly
+        if let Some(rules) = rules {
+            entries.retain(|e| e.kind != ResultKind::Rule);
+            entries.extend(rules.iter().map(|rule| SimpleEntry {
+                kind: ResultKind::Rule,
+                id: rule.id.clone(),
+                line: 0,
+                content: rule.raw.clone(),
+            }));
+        }
+    }
+
     fn is_available(&self) -> bool {
         true
     }
 }
 
+/// Words too common in code to say anything about what it implements
+const CODE_STOPWORDS: &[&str] = &[
+    "and", "args", "async", "await", "bool", "class", "const", "def", "else", "enum", "err",
+    "false", "for", "from", "func", "function", "impl", "into", "let", "match", "mut", "new",
+    "nil", "none", "not", "null", "option", "pub", "result", "return", "self", "some", "static",
+    "str", "string", "struct", "the", "this", "true", "unwrap", "use", "usize", "var", "vec",
+    "void", "while", "with",
+];
+
+/// Build a [`SearchIndex::search_rules`] query for a piece of code.
+///
+/// The words are the code's name followed by the identifiers and comment
+/// words in `text`, split at underscores and camelCase humps, lowercased and
+/// deduplicated, minus short words and common keywords.
+pub fn code_query(name: Option<&str>, text: &str) -> String {
+    const MAX_TERMS: usize = 32;
+
+    let mut terms: Vec<String> = Vec::new();
+    let words = name
+        .into_iter()
+        .chain([text])
+        .flat_map(|s| s.split(|c: char| !c.is_alphanumeric()))
+        .flat_map(camel_case_words);
+    for word in words {
+        let word = word.to_lowercase();
+        if word.len() < 3
+            || word.chars().all(|c| c.is_ascii_digit())
+            || CODE_STOPWORDS.contains(&word.as_str())
+            || terms.contains(&word)
+        {
+            continue;
+        }
+        terms.push(word);
+        if terms.len() == MAX_TERMS {
+            break;
+        }
+    }
+    terms.join(" ")
+}
+
+/// Split `parseHTTPRequest`-style identifiers into `parse`, `HTTPRequest`
+fn camel_case_words(word: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut start = 0;
+    let mut after_lower = false;
+    for (i, c) in word.char_indices() {
+        if c.is_uppercase() && after_lower {
+            words.push(&word[start..i]);
+            start = i;
+        }
+        after_lower = c.is_lowercase() || c.is_ascii_digit();
+    }
+    words.push(&word[start..]);
+    words
+}
+
 /// Simple case-insensitive highlighting for fallback
 fn highlight_simple(content: &str, query: &str) -> String {
     let content_lower = content.to_lowercase();
@@ -490,3 +663,55 @@ pub fn build_index(
 ) -> Box<dyn SearchIndex> {
     Box::new(SimpleIndex::build(project_root, files, rules))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_query() {
+        let code = "/// Refresh the session token\nfn refresh_session(token: &str) -> Result<SessionToken> {\n    let expiresAt = now();\n}";
+        assert_eq!(
+            code_query(Some("refresh_session"), code),
+            "refresh session token expires now"
+        );
+    }
+
+    #[test]
+    fn test_simple_index_search_rules() {
+        let rules = [
+            RuleEntry {
+                id: "auth.session".to_string(),
+                raw: "Sessions MUST expire after an hour.".to_string(),
+            },
+            RuleEntry {
+                id: "auth.login".to_string(),
+                raw: "Users MUST be able to log in.".to_string(),
+            },
+        ];
+        let index = SimpleIndex::build(Path::new("/"), &BTreeMap::new(), &rules);
+
+        let results = index.search_rules("session expire token", 5);
+        let ids: Vec<_> = results.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["auth.session"]);
+    }
+    #[test]
+    fn test_simple_index_update_replaces_file() {
+        let root = Path::new("/project");
+        let files = BTreeMap::from([
+            (root.join("a.rs"), "fn alpha() {}".to_string()),
+            (root.join("b.rs"), "fn beta() {}".to_string()),
+        ]);
+        let index = SimpleIndex::build(root, &files, &[]);
+
+        let a = root.join("a.rs");
+        index.update(root, &[(a.as_path(), Some("fn gamma() {}"))], None);
+        assert!(index.search("alpha", 10).is_empty());
+        assert_eq!(index.search("gamma", 10)[0].id, "a.rs");
+        assert_eq!(index.search("beta", 10).len(), 1);
+
+        let b = root.join("b.rs");
+        index.update(root, &[(b.as_path(), None)], None);
+        assert!(index.search("beta", 10).is_empty());
+    }
+}
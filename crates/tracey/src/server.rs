@@ -10,7 +10,11 @@
 
 use std::collections::BTreeMap;
 
-use crate::data::{ApiCodeRef, ApiFileEntry, ApiRule, DashboardData, ImplKey, OutlineEntry};
+use crate::data::{
+    ApiCodeRef, ApiFileEntry, ApiRule, ApiRuleTreeEntry, ApiSpecForward, DashboardData, ImplKey,
+    OutlineEntry,
+};
+use tracey_api::VerifyStatus;
 
 // ============================================================================
 // Delta Tracking
@@ -41,15 +45,53 @@ pub struct CoverageStats {
 }
 
 impl CoverageStats {
-    pub fn from_rules(rules: &[ApiRule]) -> Self {
-        let total = rules.len();
-        let impl_covered = rules.iter().filter(|r| !r.impl_refs.is_empty()).count();
-        let verify_covered = rules.iter().filter(|r| !r.verify_refs.is_empty()).count();
-        let fully_covered = rules
+    /// Stats over `rules`, leaving out draft, deprecated and removed ones
+    ///
+    /// r[impl status.coverage]
+    pub fn from_rules<'r>(rules: impl IntoIterator<Item = &'r ApiRule>) -> Self {
+        let (mut total, mut impl_covered, mut verify_covered, mut fully_covered) = (0, 0, 0, 0);
+        for rule in rules.into_iter().filter(|r| r.counts_toward_coverage()) {
+            let has_impl = !rule.impl_refs.is_empty();
+            let has_verify = !rule.verify_refs.is_empty();
+            total += 1;
+            impl_covered += has_impl as usize;
+            verify_covered += has_verify as usize;
+            fully_covered += (has_impl && has_verify) as usize;
+        }
+
+        Self::from_counts(total, impl_covered, verify_covered, fully_covered)
+    }
+
+    /// Headline stats for an impl: [`Self::from_rules`] without the impl's
+    /// excluded levels
+    ///
+    /// r[impl level.headline]
+    pub fn headline(forward: &ApiSpecForward) -> Self {
+        Self::from_rules(
+            forward
+                .rules
+                .iter()
+                .filter(|r| crate::level::in_headline(r, &forward.exclude_levels)),
+        )
+    }
+
+    /// Stats for each requirement level that has rules, strongest first.
+    ///
+    /// r[impl level.breakdown]
+    pub fn by_level(rules: &[ApiRule]) -> Vec<(&'static str, Self)> {
+        crate::level::LEVELS
             .iter()
-            .filter(|r| !r.impl_refs.is_empty() && !r.verify_refs.is_empty())
-            .count();
+            .map(|&level| (level, Self::from_rules(rules_at_level(rules, Some(level)))))
+            .filter(|(_, stats)| stats.total_rules > 0)
+            .collect()
+    }
 
+    pub fn from_counts(
+        total: usize,
+        impl_covered: usize,
+        verify_covered: usize,
+        fully_covered: usize,
+    ) -> Self {
         Self {
             total_rules: total,
             impl_covered,
@@ -160,10 +202,8 @@ impl Delta {
                 }
             }
 
-            let prev_stats = old_forward
-                .map(|f| CoverageStats::from_rules(&f.rules))
-                .unwrap_or_default();
-            let curr_stats = CoverageStats::from_rules(&new_forward.rules);
+            let prev_stats = old_forward.map(CoverageStats::headline).unwrap_or_default();
+            let curr_stats = CoverageStats::headline(new_forward);
 
             by_impl.insert(
                 impl_key,
@@ -222,7 +262,7 @@ impl<'a> QueryEngine<'a> {
             .forward_by_impl
             .iter()
             .map(|(key, forward)| {
-                let stats = CoverageStats::from_rules(&forward.rules);
+                let stats = CoverageStats::headline(forward);
                 (key.0.clone(), key.1.clone(), stats)
             })
             .collect()
@@ -231,32 +271,38 @@ impl<'a> QueryEngine<'a> {
     /// Get uncovered rules (no impl refs) for a spec/impl
     // r[impl mcp.discovery.pagination] - Prefix filtering provides pagination
     // r[impl validation.orphaned]
+    // r[impl level.filter]
     pub fn uncovered(
         &self,
         spec: &str,
         impl_name: &str,
         prefix_filter: Option<&str>,
+        level_filter: Option<&str>,
     ) -> Option<UncoveredResult> {
         let key: ImplKey = (spec.to_string(), impl_name.to_string());
         let forward = self.data.forward_by_impl.get(&key)?;
         let spec_data = self.data.specs_content_by_impl.get(&key)?;
 
-        let stats = CoverageStats::from_rules(&forward.rules);
+        let rules = rules_at_level(&forward.rules, level_filter);
+        let stats = CoverageStats::from_rules(rules.iter().copied());
 
         // Filter uncovered rules, optionally by ID prefix (case-insensitive)
-        let uncovered_rules: Vec<&ApiRule> = forward
-            .rules
+        let (stale_rules, uncovered_rules): (Vec<&ApiRule>, Vec<&ApiRule>) = rules
             .iter()
-            .filter(|r| r.impl_refs.is_empty())
+            .copied()
+            .filter(|r| r.counts_toward_coverage() && r.impl_refs.is_empty())
             .filter(|r| {
                 prefix_filter
                     .map(|p| r.id.to_lowercase().starts_with(&p.to_lowercase()))
                     .unwrap_or(true)
             })
-            .collect();
+            .partition(|r| r.is_stale());
 
         // Build section mapping from outline
         let by_section = group_rules_by_section(&uncovered_rules, &spec_data.outline);
+        let rollup = prefix_filter
+            .map(|p| subtree_rollup(&rules, p))
+            .unwrap_or_default();
 
         Some(UncoveredResult {
             spec: spec.to_string(),
@@ -264,37 +310,48 @@ impl<'a> QueryEngine<'a> {
             stats,
             by_section,
             total_uncovered: uncovered_rules.len(),
+            stale: stale_rules.into_iter().map(StaleRule::from_rule).collect(),
+            rollup,
             prefix_filter: prefix_filter.map(|s| s.to_string()),
+            level_filter: level_filter.map(|s| s.to_string()),
         })
     }
 
     /// Get untested rules (have impl but no verify refs) for a spec/impl
     // r[impl mcp.discovery.pagination] - Prefix filtering provides pagination
+    // r[impl level.filter]
     pub fn untested(
         &self,
         spec: &str,
         impl_name: &str,
         prefix_filter: Option<&str>,
+        level_filter: Option<&str>,
     ) -> Option<UntestedResult> {
         let key: ImplKey = (spec.to_string(), impl_name.to_string());
         let forward = self.data.forward_by_impl.get(&key)?;
         let spec_data = self.data.specs_content_by_impl.get(&key)?;
 
-        let stats = CoverageStats::from_rules(&forward.rules);
+        let rules = rules_at_level(&forward.rules, level_filter);
+        let stats = CoverageStats::from_rules(rules.iter().copied());
 
         // Filter untested rules, optionally by ID prefix (case-insensitive)
-        let untested_rules: Vec<&ApiRule> = forward
-            .rules
+        let (stale_rules, untested_rules): (Vec<&ApiRule>, Vec<&ApiRule>) = rules
             .iter()
-            .filter(|r| !r.impl_refs.is_empty() && r.verify_refs.is_empty())
+            .copied()
+            .filter(|r| {
+                r.counts_toward_coverage() && !r.impl_refs.is_empty() && r.verify_refs.is_empty()
+            })
             .filter(|r| {
                 prefix_filter
                     .map(|p| r.id.to_lowercase().starts_with(&p.to_lowercase()))
                     .unwrap_or(true)
             })
-            .collect();
+            .partition(|r| r.is_stale());
 
         let by_section = group_rules_by_section(&untested_rules, &spec_data.outline);
+        let rollup = prefix_filter
+            .map(|p| subtree_rollup(&rules, p))
+            .unwrap_or_default();
 
         Some(UntestedResult {
             spec: spec.to_string(),
@@ -302,7 +359,37 @@ impl<'a> QueryEngine<'a> {
             stats,
             by_section,
             total_untested: untested_rules.len(),
+            stale: stale_rules.into_iter().map(StaleRule::from_rule).collect(),
+            rollup,
+            prefix_filter: prefix_filter.map(|s| s.to_string()),
+            level_filter: level_filter.map(|s| s.to_string()),
+        })
+    }
+
+    /// Get the requirement ID tree for a spec/impl, optionally under an ID prefix
+    // r[impl tree.prefix]
+    pub fn rule_tree(
+        &self,
+        spec: &str,
+        impl_name: &str,
+        prefix_filter: Option<&str>,
+        level_filter: Option<&str>,
+    ) -> Option<RuleTreeResult> {
+        let key: ImplKey = (spec.to_string(), impl_name.to_string());
+        let forward = self.data.forward_by_impl.get(&key)?;
+
+        let rules = rules_at_level(&forward.rules, level_filter);
+        let entries = crate::data::build_rule_tree(rules)
+            .into_iter()
+            .filter(|e| prefix_filter.is_none_or(|p| starts_with_ignore_case(&e.prefix, p)))
+            .collect();
+
+        Some(RuleTreeResult {
+            spec: spec.to_string(),
+            impl_name: impl_name.to_string(),
+            entries,
             prefix_filter: prefix_filter.map(|s| s.to_string()),
+            level_filter: level_filter.map(|s| s.to_string()),
         })
     }
 
@@ -429,6 +516,7 @@ impl<'a> QueryEngine<'a> {
                     impl_name: key.1.clone(),
                     impl_refs: rule.impl_refs.clone(),
                     verify_refs: rule.verify_refs.clone(),
+                    verify_status: rule.verify_status,
                 });
             }
         }
@@ -453,7 +541,12 @@ pub struct UncoveredResult {
     pub stats: CoverageStats,
     pub by_section: BTreeMap<String, Vec<RuleRef>>,
     pub total_uncovered: usize,
+    /// Rules left without implementation because their refs are stale
+    pub stale: Vec<StaleRule>,
+    /// Coverage of each ID subtree under the prefix filter, if any
+    pub rollup: Vec<ApiRuleTreeEntry>,
     pub prefix_filter: Option<String>,
+    pub level_filter: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -463,7 +556,22 @@ pub struct UntestedResult {
     pub stats: CoverageStats,
     pub by_section: BTreeMap<String, Vec<RuleRef>>,
     pub total_untested: usize,
+    /// Implemented rules left without verification because their refs are stale
+    pub stale: Vec<StaleRule>,
+    /// Coverage of each ID subtree under the prefix filter, if any
+    pub rollup: Vec<ApiRuleTreeEntry>,
+    pub prefix_filter: Option<String>,
+    pub level_filter: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RuleTreeResult {
+    pub spec: String,
+    pub impl_name: String,
+    /// Tree entries in depth-first order
+    pub entries: Vec<ApiRuleTreeEntry>,
     pub prefix_filter: Option<String>,
+    pub level_filter: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -472,6 +580,46 @@ pub struct RuleRef {
     pub impl_refs: Vec<ApiCodeRef>,
 }
 
+/// A rule whose references predate its current version
+///
+/// r[impl ref.version.stale]
+#[derive(Debug, Clone)]
+pub struct StaleRule {
+    pub id: String,
+    /// Current version of the rule
+    pub version: u32,
+    pub stale_refs: Vec<ApiCodeRef>,
+}
+
+impl StaleRule {
+    fn from_rule(rule: &ApiRule) -> Self {
+        Self {
+            id: rule.id.clone(),
+            version: rule.version,
+            stale_refs: rule.stale_refs.clone(),
+        }
+    }
+}
+
+/// Format the stale-rule section shared by the uncovered/untested responses
+fn format_stale_rules(out: &mut String, stale: &[StaleRule]) {
+    if stale.is_empty() {
+        return;
+    }
+    out.push_str(&format!(
+        "## Stale ({} changed since they were annotated)\n",
+        stale.len()
+    ));
+    for rule in stale {
+        out.push_str(&format!("  {} (now +{})", rule.id, rule.version));
+        if let Some(loc) = rule.stale_refs.first() {
+            out.push_str(&format!(" (stale ref: {}:{})", loc.file, loc.line));
+        }
+        out.push('\n');
+    }
+    out.push('\n');
+}
+
 #[derive(Debug, Clone)]
 pub struct UnmappedResult {
     pub spec: String,
@@ -524,6 +672,8 @@ pub struct ImplCoverage {
     pub impl_name: String,
     pub impl_refs: Vec<ApiCodeRef>,
     pub verify_refs: Vec<ApiCodeRef>,
+    /// Outcome of the verifying tests, when test results are configured
+    pub verify_status: Option<VerifyStatus>,
 }
 
 #[derive(Debug, Clone)]
@@ -561,6 +711,39 @@ impl RuleInfo {
 // Helpers
 // ============================================================================
 
+/// Heading suffix naming the level a result was filtered to.
+fn level_suffix(level: Option<&str>) -> String {
+    level
+        .map(|l| format!(" ({} only)", l.to_uppercase()))
+        .unwrap_or_default()
+}
+
+/// Rules at `level`, or all rules if no level is given.
+fn rules_at_level<'r>(rules: &'r [ApiRule], level: Option<&str>) -> Vec<&'r ApiRule> {
+    rules
+        .iter()
+        .filter(|r| crate::level::matches(r, level))
+        .collect()
+}
+
+fn starts_with_ignore_case(id: &str, prefix: &str) -> bool {
+    id.to_lowercase().starts_with(&prefix.to_lowercase())
+}
+
+/// Subtrees of the ID tree under `prefix`, for rolling up coverage.
+///
+/// r[impl tree.rollup.query]
+///
+/// Only entries with requirements below them are kept; single requirements
+/// are already listed individually.
+fn subtree_rollup(rules: &[&ApiRule], prefix: &str) -> Vec<ApiRuleTreeEntry> {
+    crate::data::build_rule_tree(rules.iter().copied())
+        .into_iter()
+        .filter(|e| starts_with_ignore_case(&e.prefix, prefix))
+        .filter(|e| e.aggregated.total > e.coverage.total)
+        .collect()
+}
+
 fn group_rules_by_section(
     rules: &[&ApiRule],
     _outline: &[OutlineEntry],
@@ -655,7 +838,7 @@ pub fn format_status_header(data: &DashboardData, delta: &Delta) -> String {
         .forward_by_impl
         .iter()
         .map(|(key, forward)| {
-            let stats = CoverageStats::from_rules(&forward.rules);
+            let stats = CoverageStats::headline(forward);
             let impl_key = format!("{}/{}", key.0, key.1);
 
             // Check if there's a delta for this impl
@@ -712,15 +895,19 @@ impl UncoveredResult {
     pub fn format_text(&self) -> String {
         let mut out = String::new();
         out.push_str(&format!(
-            "# Uncovered Rules in {}/{}\n\n",
-            self.spec, self.impl_name
+            "# Uncovered Rules in {}/{}{}\n\n",
+            self.spec,
+            self.impl_name,
+            level_suffix(self.level_filter.as_deref())
         ));
         out.push_str(&format!(
             "Implementation coverage: {:.0}% ({}/{} rules)\n\n",
             self.stats.impl_percent, self.stats.impl_covered, self.stats.total_rules
         ));
 
-        if self.total_uncovered == 0 {
+        format_stale_rules(&mut out, &self.stale);
+
+        if self.total_uncovered == 0 && self.stale.is_empty() {
             out.push_str("All rules have implementation references! 🎉\n");
             return out;
         }
@@ -757,15 +944,19 @@ impl UntestedResult {
     pub fn format_text(&self) -> String {
         let mut out = String::new();
         out.push_str(&format!(
-            "# Untested Rules in {}/{}\n\n",
-            self.spec, self.impl_name
+            "# Untested Rules in {}/{}{}\n\n",
+            self.spec,
+            self.impl_name,
+            level_suffix(self.level_filter.as_deref())
         ));
         out.push_str(&format!(
             "Verification coverage: {:.0}% ({}/{} rules)\n\n",
             self.stats.verify_percent, self.stats.verify_covered, self.stats.total_rules
         ));
 
-        if self.total_untested == 0 {
+        format_stale_rules(&mut out, &self.stale);
+
+        if self.total_untested == 0 && self.stale.is_empty() {
             out.push_str("All implemented rules have verification! 🎉\n");
             return out;
         }
@@ -970,7 +1161,18 @@ impl RuleInfo {
                         }
                     }
                     if !cov.verify_refs.is_empty() {
-                        out.push_str("  **Verifications:**\n");
+                        match cov.verify_status {
+                            Some(VerifyStatus::Passing) => {
+                                out.push_str("  **Verifications:** (tests passing)\n")
+                            }
+                            Some(VerifyStatus::Failing) => {
+                                out.push_str("  **Verifications:** (tests FAILING)\n")
+                            }
+                            Some(VerifyStatus::NotRun) => {
+                                out.push_str("  **Verifications:** (tests not run)\n")
+                            }
+                            None => out.push_str("  **Verifications:**\n"),
+                        }
                         for r in &cov.verify_refs {
                             out.push_str(&format!("    - {}:{}\n", r.file, r.line));
                         }
@@ -1120,6 +1322,80 @@ fn do_foo_baz() {}
         );
     }
 
+    // r[verify tree.rollup]
+    // r[verify tree.prefix]
+    // r[verify tree.rollup.query]
+    #[tokio::test]
+    async fn test_rule_tree_rolls_up_by_prefix() {
+        let (_tmp, root) = create_test_fixture().await;
+        let config_path = root.join(".config/tracey/config.styx");
+        let config = crate::load_config(&config_path).unwrap();
+
+        let data = crate::data::build_dashboard_data(&root, &config, 1, true)
+            .await
+            .unwrap();
+
+        let engine = QueryEngine::new(&data);
+
+        let tree = engine
+            .rule_tree("test-spec", "main", None, None)
+            .expect("tree should exist");
+        let entries: Vec<_> = tree
+            .entries
+            .iter()
+            .map(|e| {
+                (
+                    e.prefix.as_str(),
+                    e.depth,
+                    e.is_rule,
+                    e.aggregated.impl_count,
+                    e.aggregated.total,
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("foo", 1, false, 2, 2),
+                ("foo.bar", 2, true, 1, 1),
+                ("foo.baz", 2, true, 1, 1),
+                ("uncovered", 1, false, 0, 1),
+                ("uncovered.rule", 2, true, 0, 1),
+            ]
+        );
+
+        let tree = engine
+            .rule_tree("test-spec", "main", Some("FOO."), None)
+            .expect("tree should exist");
+        assert_eq!(
+            tree.entries.len(),
+            2,
+            "Only foo.bar and foo.baz are under 'foo.'"
+        );
+
+        // Prefix queries roll up each subtree, but don't repeat single rules
+        let uncovered = engine
+            .uncovered("test-spec", "main", Some("foo"), None)
+            .expect("uncovered should exist");
+        let rollup: Vec<_> = uncovered
+            .rollup
+            .iter()
+            .map(|e| {
+                (
+                    e.prefix.as_str(),
+                    e.aggregated.impl_count,
+                    e.aggregated.total,
+                )
+            })
+            .collect();
+        assert_eq!(rollup, vec![("foo", 2, 2)]);
+
+        let uncovered = engine
+            .uncovered("test-spec", "main", None, None)
+            .expect("uncovered should exist");
+        assert!(uncovered.rollup.is_empty(), "No roll-up without a prefix");
+    }
+
     #[tokio::test]
     async fn test_rule_lookup_finds_covered_rules() {
         let (_tmp, root) = create_test_fixture().await;
@@ -0,0 +1,451 @@
+//! Test outcome import.
+//!
+//! r[impl test-results.formats]
+//!
+//! A `verify` annotation only says a test exists. To tell whether it passes,
+//! tracey reads test outcome files listed in an impl's `test_results` config:
+//! libtest JSON (`cargo test -- -Z unstable-options --format json`) and JUnit
+//! XML (as written by `cargo nextest` and most other test runners). Each
+//! `verify` reference is mapped to the test function that contains it via the
+//! code units, and that function is looked up by name in the results.
+
+use eyre::{Result, WrapErr, bail};
+use facet::Facet;
+use std::path::Path;
+use tracey_api::VerifyStatus;
+use tracey_core::code_units::{CodeUnit, CodeUnitKind};
+
+/// Outcome of a single test
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+/// Test outcomes, keyed by the test's fully qualified name
+#[derive(Debug, Clone, Default)]
+pub struct TestResults {
+    /// (name, outcome) in the order they were read
+    pub tests: Vec<(String, TestOutcome)>,
+}
+
+/// One line of libtest's JSON output
+#[derive(Facet)]
+struct LibtestEvent {
+    #[facet(rename = "type")]
+    kind: String,
+    #[facet(default)]
+    event: Option<String>,
+    #[facet(default)]
+    name: Option<String>,
+}
+
+impl TestResults {
+    /// Load and merge test result files, resolved relative to `project_root`.
+    ///
+    /// Files that are missing or can't be parsed are reported as warnings:
+    /// their tests simply count as not run.
+    pub fn load(project_root: &Path, paths: &[String]) -> (Self, Vec<String>) {
+        let mut results = Self::default();
+        let mut warnings = Vec::new();
+        for path in paths {
+            let full_path = project_root.join(path);
+            let parsed = std::fs::read_to_string(&full_path)
+                .wrap_err_with(|| format!("Failed to read test results {}", path))
+                .and_then(|content| {
+                    Self::parse(&content)
+                        .wrap_err_with(|| format!("Failed to parse test results {}", path))
+                });
+            match parsed {
+                Ok(parsed) => results.tests.extend(parsed.tests),
+                Err(e) => warnings.push(format!("Warning: {:#}", e)),
+            }
+        }
+        (results, warnings)
+    }
+
+    /// Parse a test result file, detecting JUnit XML vs. libtest JSON.
+    pub fn parse(content: &str) -> Result<Self> {
+        if content.trim_start().starts_with('<') {
+            Self::parse_junit(content)
+        } else {
+            Self::parse_libtest_json(content)
+        }
+    }
+
+    /// Parse libtest's line-delimited JSON events.
+    pub fn parse_libtest_json(content: &str) -> Result<Self> {
+        let mut tests = Vec::new();
+        let mut saw_event = false;
+        for line in content.lines() {
+            let line = line.trim();
+            if !line.starts_with('{') {
+                // cargo interleaves plain-text lines such as "Running ..."
+                continue;
+            }
+            let Ok(event) = facet_json::from_str::<LibtestEvent>(line) else {
+                continue;
+            };
+            saw_event = true;
+            if event.kind != "test" {
+                continue;
+            }
+            let (Some(name), Some(event)) = (event.name, event.event) else {
+                continue;
+            };
+            let outcome = match event.as_str() {
+                "ok" => TestOutcome::Passed,
+                "failed" | "timeout" => TestOutcome::Failed,
+                "ignored" => TestOutcome::Skipped,
+                _ => continue,
+            };
+            tests.push((name, outcome));
+        }
+        if !saw_event {
+            bail!("no libtest JSON events found");
+        }
+        Ok(Self { tests })
+    }
+
+    /// Parse `<testcase>` elements from a JUnit XML report.
+    ///
+    /// Only the bits tracey needs are read: the `name` and `classname`
+    /// attributes, and whether the test case has a `<failure>`, `<error>` or
+    /// `<skipped>` child.
+    pub fn parse_junit(content: &str) -> Result<Self> {
+        let mut tests = Vec::new();
+        let mut rest = content;
+        while let Some(start) = rest.find("<testcase") {
+            let after = &rest[start + "<testcase".len()..];
+            let Some(tag_end) = after.find('>') else {
+                bail!("unterminated <testcase> element");
+            };
+            let attrs = &after[..tag_end];
+            let self_closing = attrs.trim_end().ends_with('/');
+
+            let (body, next) = if self_closing {
+                ("", &after[tag_end + 1..])
+            } else {
+                let inner = &after[tag_end + 1..];
+                match inner.find("</testcase>") {
+                    Some(end) => (&inner[..end], &inner[end + "</testcase>".len()..]),
+                    None => bail!("unterminated <testcase> element"),
+                }
+            };
+
+            let Some(name) = xml_attr(attrs, "name") else {
+                rest = next;
+                continue;
+            };
+            let full_name = match xml_attr(attrs, "classname") {
+                Some(class) if !class.is_empty() => format!("{}::{}", class, name),
+                _ => name,
+            };
+            let outcome = if body.contains("<failure") || body.contains("<error") {
+                TestOutcome::Failed
+            } else if body.contains("<skipped") {
+                TestOutcome::Skipped
+            } else {
+                TestOutcome::Passed
+            };
+            tests.push((full_name, outcome));
+            rest = next;
+        }
+        if tests.is_empty() && !content.contains("<testsuite") {
+            bail!("not a JUnit XML report");
+        }
+        Ok(Self { tests })
+    }
+
+    /// Outcome of the test function `function` defined in `file`.
+    ///
+    /// r[impl test-results.mapping]
+    ///
+    /// Tests are matched on the last segment of their name (`::` or `.`
+    /// separated). When several tests share that name, the ones whose name
+    /// also mentions the file stem (module or test binary) win. If several
+    /// still match, any failure wins over a pass, and a pass over a skip.
+    pub fn outcome_for(&self, function: &str, file: &Path) -> Option<TestOutcome> {
+        let candidates: Vec<_> = self
+            .tests
+            .iter()
+            .filter(|(name, _)| last_segment(name) == function)
+            .collect();
+        let stem = file.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        let narrowed: Vec<_> = candidates
+            .iter()
+            .filter(|(name, _)| segments(name).any(|s| s == stem))
+            .collect();
+        let outcomes: Vec<TestOutcome> = if narrowed.is_empty() {
+            candidates.iter().map(|(_, o)| *o).collect()
+        } else {
+            narrowed.iter().map(|(_, o)| *o).collect()
+        };
+
+        [
+            TestOutcome::Failed,
+            TestOutcome::Passed,
+            TestOutcome::Skipped,
+        ]
+        .into_iter()
+        .find(|o| outcomes.contains(o))
+    }
+
+    /// Combined status of a rule's `verify` references.
+    ///
+    /// r[impl test-results.status]
+    ///
+    /// `refs` are the (file, line) locations of the references, and `units`
+    /// looks up the code units of a file. A rule is failing if any test
+    /// behind it failed, passing if at least one passed, and otherwise none of
+    /// its tests ran.
+    pub fn verify_status<'a>(
+        &self,
+        rule_id: &str,
+        refs: impl IntoIterator<Item = (&'a Path, usize)>,
+        units: impl Fn(&Path) -> Option<&'a [CodeUnit]>,
+    ) -> Option<VerifyStatus> {
+        let mut any = false;
+        let mut passed = false;
+        for (file, line) in refs {
+            any = true;
+            let Some(function) = units(file).and_then(|u| test_function(u, rule_id, line)) else {
+                continue;
+            };
+            match self.outcome_for(function, file) {
+                Some(TestOutcome::Failed) => return Some(VerifyStatus::Failing),
+                Some(TestOutcome::Passed) => passed = true,
+                Some(TestOutcome::Skipped) | None => {}
+            }
+        }
+        match (any, passed) {
+            (false, _) => None,
+            (true, true) => Some(VerifyStatus::Passing),
+            (true, false) => Some(VerifyStatus::NotRun),
+        }
+    }
+}
+
+/// Name of the function a `verify` reference on `line` belongs to: the
+/// innermost function containing the line, or else the function whose doc
+/// comment lists the rule directly below the reference.
+fn test_function<'a>(units: &'a [CodeUnit], rule_id: &str, line: usize) -> Option<&'a str> {
+    let containing = units
+        .iter()
+        .filter(|u| u.kind == CodeUnitKind::Function)
+        .filter(|u| u.start_line <= line && line <= u.end_line)
+        .min_by_key(|u| u.end_line - u.start_line);
+    let annotated = || {
+        // Only the very next code unit, so a reference can't reach past other code
+        let next = units
+            .iter()
+            .filter(|u| u.start_line > line)
+            .min_by_key(|u| u.start_line)?;
+        (next.kind == CodeUnitKind::Function && next.req_refs.iter().any(|r| r == rule_id))
+            .then_some(next)
+    };
+    containing.or_else(annotated)?.name.as_deref()
+}
+
+fn segments(name: &str) -> impl Iterator<Item = &str> {
+    name.split("::").flat_map(|s| s.split('.'))
+}
+
+fn last_segment(name: &str) -> &str {
+    segments(name).last().unwrap_or(name)
+}
+
+/// Read an attribute value from the inside of an XML start tag.
+fn xml_attr(attrs: &str, name: &str) -> Option<String> {
+    let mut rest = attrs;
+    loop {
+        let idx = rest.find(name)?;
+        let before_ok = idx == 0 || rest[..idx].ends_with(char::is_whitespace);
+        let after = rest[idx + name.len()..].trim_start();
+        if before_ok && let Some(after_eq) = after.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            let quote = after_eq.chars().next()?;
+            if quote == '"' || quote == '\'' {
+                let value = &after_eq[1..];
+                let end = value.find(quote)?;
+                return Some(xml_unescape(&value[..end]));
+            }
+        }
+        rest = &rest[idx + name.len()..];
+    }
+}
+
+fn xml_unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    // r[verify test-results.formats]
+    #[test]
+    fn test_parse_libtest_json() {
+        let content = r#"{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "tests::test_login" }
+{ "type": "test", "name": "tests::test_login", "event": "ok" }
+{ "type": "test", "name": "tests::test_logout", "event": "failed", "stdout": "boom" }
+{ "type": "test", "name": "tests::test_session", "event": "ignored" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 1 }
+"#;
+        let results = TestResults::parse(content).unwrap();
+        assert_eq!(
+            results.tests,
+            vec![
+                ("tests::test_login".to_string(), TestOutcome::Passed),
+                ("tests::test_logout".to_string(), TestOutcome::Failed),
+                ("tests::test_session".to_string(), TestOutcome::Skipped),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_junit() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="nextest-run" tests="3" failures="1">
+  <testsuite name="tracey::check_tests" tests="3">
+    <testcase name="test_check_passes" classname="tracey::check_tests" time="0.1"/>
+    <testcase name="test_check_fails" classname="tracey::check_tests" time="0.2">
+      <failure message="assertion failed">thread panicked</failure>
+    </testcase>
+    <testcase name="test_check_&amp;_skip" classname="tracey::check_tests">
+      <skipped/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#;
+        let results = TestResults::parse(content).unwrap();
+        assert_eq!(
+            results.tests,
+            vec![
+                (
+                    "tracey::check_tests::test_check_passes".to_string(),
+                    TestOutcome::Passed
+                ),
+                (
+                    "tracey::check_tests::test_check_fails".to_string(),
+                    TestOutcome::Failed
+                ),
+                (
+                    "tracey::check_tests::test_check_&_skip".to_string(),
+                    TestOutcome::Skipped
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_format() {
+        assert!(TestResults::parse("running 3 tests\ntest foo ... ok\n").is_err());
+    }
+
+    // r[verify test-results.mapping]
+    #[test]
+    fn test_outcome_prefers_matching_file() {
+        let results = TestResults {
+            tests: vec![
+                ("auth::tests::test_login".to_string(), TestOutcome::Failed),
+                (
+                    "session::tests::test_login".to_string(),
+                    TestOutcome::Passed,
+                ),
+            ],
+        };
+        assert_eq!(
+            results.outcome_for("test_login", Path::new("src/session.rs")),
+            Some(TestOutcome::Passed)
+        );
+        // Ambiguous: a failure anywhere wins
+        assert_eq!(
+            results.outcome_for("test_login", Path::new("src/other.rs")),
+            Some(TestOutcome::Failed)
+        );
+        assert_eq!(
+            results.outcome_for("test_missing", Path::new("src/other.rs")),
+            None
+        );
+    }
+
+    // r[verify test-results.status]
+    #[test]
+    fn test_verify_status_maps_refs_to_functions() {
+        let file = PathBuf::from("src/tests.rs");
+        let unit = |name: &str, start: usize, end: usize, refs: &[&str]| CodeUnit {
+            kind: CodeUnitKind::Function,
+            name: Some(name.to_string()),
+            file: file.clone(),
+            start_line: start,
+            end_line: end,
+            start_byte: 0,
+            end_byte: 0,
+            req_refs: refs.iter().map(|r| r.to_string()).collect(),
+        };
+        let units = vec![
+            unit("test_login", 3, 6, &["auth.login"]),
+            unit("test_logout", 9, 12, &["auth.logout"]),
+        ];
+        let results = TestResults {
+            tests: vec![
+                ("tests::test_login".to_string(), TestOutcome::Passed),
+                ("tests::test_logout".to_string(), TestOutcome::Failed),
+            ],
+        };
+        let lookup = |_: &Path| Some(units.as_slice());
+
+        assert_eq!(
+            results.verify_status("auth.login", [(file.as_path(), 1)], lookup),
+            Some(VerifyStatus::Passing)
+        );
+        assert_eq!(
+            results.verify_status("auth.logout", [(file.as_path(), 8)], lookup),
+            Some(VerifyStatus::Failing)
+        );
+        assert_eq!(
+            TestResults::default().verify_status("auth.login", [(file.as_path(), 1)], lookup),
+            Some(VerifyStatus::NotRun)
+        );
+        assert_eq!(results.verify_status("auth.login", [], lookup), None);
+    }
+
+    // r[verify test-results.status]
+    #[test]
+    fn test_test_function_prefers_containing_function() {
+        let unit = |kind, name: &str, start: usize, end: usize, refs: &[&str]| CodeUnit {
+            kind,
+            name: Some(name.to_string()),
+            file: PathBuf::from("src/tests.rs"),
+            start_line: start,
+            end_line: end,
+            start_byte: 0,
+            end_byte: 0,
+            req_refs: refs.iter().map(|r| r.to_string()).collect(),
+        };
+        let units = vec![
+            unit(CodeUnitKind::Function, "outer", 1, 20, &[]),
+            unit(CodeUnitKind::Function, "inner", 5, 10, &[]),
+            unit(CodeUnitKind::Function, "later", 23, 30, &["auth.login"]),
+            unit(CodeUnitKind::Struct, "Fixture", 33, 35, &[]),
+            unit(CodeUnitKind::Function, "last", 37, 40, &["auth.login"]),
+        ];
+
+        // Inside a function, a later function annotated with the rule doesn't win
+        assert_eq!(test_function(&units, "auth.login", 3), Some("outer"));
+        assert_eq!(test_function(&units, "auth.login", 7), Some("inner"));
+        // Directly above a function whose doc comment lists the rule
+        assert_eq!(test_function(&units, "auth.login", 22), Some("later"));
+        assert_eq!(test_function(&units, "auth.logout", 22), None);
+        // Other code in between
+        assert_eq!(test_function(&units, "auth.login", 32), None);
+    }
+}
@@ -0,0 +1,508 @@
+//! Spec/implementation validation.
+//!
+//! Shared between the daemon's `validate` RPC and the daemon-free
+//! `tracey check` command, so both report exactly the same errors.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use tracey_api::{ValidationError, ValidationErrorCode, ValidationResult, VerifyStatus};
+
+use crate::data::{DashboardData, ImplKey};
+
+/// Validate a single spec/impl pair against the built dashboard data.
+///
+/// r[impl mcp.validation.check]
+pub fn validate(
+    data: &DashboardData,
+    project_root: &Path,
+    spec: &str,
+    impl_name: &str,
+) -> ValidationResult {
+    let key: ImplKey = (spec.to_string(), impl_name.to_string());
+    let mut errors = Vec::new();
+
+    // Get all rules for this spec/impl
+    if let Some(forward_data) = data.forward_by_impl.get(&key) {
+        // Build a map of rule IDs for quick lookup
+        let rule_ids: HashSet<_> = forward_data.rules.iter().map(|r| r.id.as_str()).collect();
+        // Rules a wildcard can match (deprecated and removed ones are skipped)
+        let wildcard_rule_ids: Vec<_> = forward_data
+            .rules
+            .iter()
+            .filter(|r| !r.is_deprecated() && !r.is_removed())
+            .map(|r| r.id.as_str())
+            .collect();
+        // Rules offered in place of unknown IDs
+        let suggestable_rule_ids: Vec<_> = forward_data
+            .rules
+            .iter()
+            .filter(|r| !r.is_removed())
+            .map(|r| r.id.as_str())
+            .collect();
+
+        // r[impl config.multi-spec.unique-within-spec]
+        // Check for duplicate rule IDs (within this spec)
+        let mut seen_ids: HashMap<&str, (&Option<String>, Option<usize>)> = HashMap::new();
+        for rule in &forward_data.rules {
+            if let Some((prev_file, prev_line)) = seen_ids.get(rule.id.as_str()) {
+                errors.push(ValidationError {
+                    code: ValidationErrorCode::DuplicateRequirement,
+                    message: format!(
+                        "Duplicate rule ID '{}' (first defined at {}:{})",
+                        rule.id,
+                        prev_file.as_deref().unwrap_or("?"),
+                        prev_line.unwrap_or(0)
+                    ),
+                    file: rule.source_file.clone(),
+                    line: rule.source_line,
+                    column: rule.source_column,
+                    related_rules: vec![rule.id.clone()],
+                });
+            } else {
+                seen_ids.insert(&rule.id, (&rule.source_file, rule.source_line));
+            }
+        }
+
+        // Check each rule
+        for rule in &forward_data.rules {
+            // Check naming convention (dot-separated segments)
+            if !is_valid_rule_id(&rule.id) {
+                errors.push(ValidationError {
+                    code: ValidationErrorCode::InvalidNaming,
+                    message: format!(
+                        "Rule ID '{}' doesn't follow naming convention (use dot-separated lowercase segments)",
+                        rule.id
+                    ),
+                    file: rule.source_file.clone(),
+                    line: rule.source_line,
+                    column: rule.source_column,
+                    related_rules: vec![],
+                });
+            }
+
+            // r[impl config.impl.test_include.verify-only]
+            // Check that impl references are not in test files
+            for impl_ref in &rule.impl_refs {
+                let ref_path = project_root.join(&impl_ref.file);
+                if data.test_files.contains(&ref_path) {
+                    errors.push(ValidationError {
+                        code: ValidationErrorCode::ImplInTestFile,
+                        message: format!(
+                            "Test file contains impl annotation for '{}' - test files may only contain verify annotations",
+                            rule.id
+                        ),
+                        file: Some(impl_ref.file.clone()),
+                        line: Some(impl_ref.line),
+                        column: None,
+                        related_rules: vec![rule.id.clone()],
+                    });
+                }
+            }
+
+            // r[impl validation.stale]
+            // References written against an older version of the rule
+            for stale_ref in &rule.stale_refs {
+                errors.push(ValidationError {
+                    code: ValidationErrorCode::StaleReference,
+                    message: format!(
+                        "Reference to '{}' predates its current version (+{}) - review the change and update the reference to '{}+{}'",
+                        rule.id, rule.version, rule.id, rule.version
+                    ),
+                    file: Some(stale_ref.file.clone()),
+                    line: Some(stale_ref.line),
+                    column: None,
+                    related_rules: vec![rule.id.clone()],
+                });
+            }
+
+            // r[impl status.deprecated]
+            // Implementing a deprecated rule is allowed, but worth a warning
+            if rule.is_deprecated() {
+                for impl_ref in &rule.impl_refs {
+                    errors.push(ValidationError {
+                        code: ValidationErrorCode::DeprecatedRequirement,
+                        message: format!("Implementation of deprecated rule '{}'", rule.id),
+                        file: Some(impl_ref.file.clone()),
+                        line: Some(impl_ref.line),
+                        column: None,
+                        related_rules: vec![rule.id.clone()],
+                    });
+                }
+            }
+
+            // r[impl status.removed]
+            // Removed rules are still defined, so they aren't unknown, but
+            // nothing should reference them any more
+            if rule.is_removed() {
+                let refs = rule
+                    .impl_refs
+                    .iter()
+                    .chain(&rule.verify_refs)
+                    .chain(&rule.depends_refs);
+                for removed_ref in refs {
+                    errors.push(ValidationError {
+                        code: ValidationErrorCode::RemovedRequirement,
+                        message: format!("Reference to removed rule '{}'", rule.id),
+                        file: Some(removed_ref.file.clone()),
+                        line: Some(removed_ref.line),
+                        column: None,
+                        related_rules: vec![rule.id.clone()],
+                    });
+                }
+            }
+
+            // r[impl validation.failing-verification]
+            // Tests behind verify references must pass
+            if rule.verify_status == Some(VerifyStatus::Failing) {
+                let first = rule.verify_refs.first();
+                errors.push(ValidationError {
+                    code: ValidationErrorCode::FailingVerification,
+                    message: format!("A test verifying '{}' is failing", rule.id),
+                    file: first.map(|r| r.file.clone()),
+                    line: first.map(|r| r.line),
+                    column: None,
+                    related_rules: vec![rule.id.clone()],
+                });
+            }
+        }
+
+        // r[impl ref.prefix.unknown]
+        // Check for references with unknown prefixes
+        // This requires checking the reverse data for any files that have
+        // references to rules not in the rule_ids set
+        if let Some(reverse_data) = data.reverse_by_impl.get(&key) {
+            // Get all prefixes from the config
+            let known_prefixes: HashSet<&str> = data
+                .config
+                .specs
+                .iter()
+                .map(|s| s.prefix.as_str())
+                .collect();
+
+            // r[impl ref.prefix.filter]
+            // Find the prefix for the current spec being validated
+            let current_spec_prefix: Option<&str> = data
+                .config
+                .specs
+                .iter()
+                .find(|s| s.name == spec)
+                .map(|s| s.prefix.as_str());
+
+            // Check files for unknown references
+            let extract_options = crate::config::extract_options(&data.config);
+            for file_entry in &reverse_data.files {
+                let file_path = project_root.join(&file_entry.path);
+                if let Ok(content) = std::fs::read_to_string(&file_path) {
+                    let reqs = tracey_core::Reqs::extract_from_content_with(
+                        &file_path,
+                        &content,
+                        &extract_options,
+                    );
+                    for reference in &reqs.references {
+                        // Check if prefix is known
+                        if !known_prefixes.contains(reference.prefix.as_str()) {
+                            let available: Vec<_> = known_prefixes.iter().copied().collect();
+                            errors.push(ValidationError {
+                                code: ValidationErrorCode::UnknownPrefix,
+                                message: format!(
+                                    "Unknown prefix '{}' - available prefixes: {}",
+                                    reference.prefix,
+                                    available.join(", ")
+                                ),
+                                file: Some(file_entry.path.clone()),
+                                line: Some(reference.line),
+                                column: None,
+                                related_rules: vec![],
+                            });
+                        }
+                        // r[impl ref.prefix.filter]
+                        // Only validate references whose prefix matches the current spec
+                        // Skip references that belong to a different spec (different prefix)
+                        else if current_spec_prefix == Some(reference.prefix.as_str()) {
+                            // r[impl ref.wildcard.unmatched]
+                            if tracey_core::is_wildcard(&reference.req_id) {
+                                if !wildcard_rule_ids
+                                    .iter()
+                                    .any(|id| tracey_core::wildcard_matches(&reference.req_id, id))
+                                {
+                                    errors.push(ValidationError {
+                                        code: ValidationErrorCode::UnmatchedWildcard,
+                                        message: format!(
+                                            "Wildcard reference '{}' matches no rules",
+                                            reference.req_id
+                                        ),
+                                        file: Some(file_entry.path.clone()),
+                                        line: Some(reference.line),
+                                        column: None,
+                                        related_rules: vec![],
+                                    });
+                                }
+                            }
+                            // Check if rule ID exists (for matching prefix only)
+                            else if !rule_ids.contains(reference.req_id.as_str()) {
+                                let suggestions = closest_rule_ids(
+                                    &reference.req_id,
+                                    suggestable_rule_ids.iter().copied(),
+                                );
+                                errors.push(ValidationError {
+                                    code: ValidationErrorCode::UnknownRequirement,
+                                    message: format!(
+                                        "Reference to unknown rule '{}'{}",
+                                        reference.req_id,
+                                        did_you_mean(&suggestions)
+                                    ),
+                                    file: Some(file_entry.path.clone()),
+                                    line: Some(reference.line),
+                                    column: None,
+                                    related_rules: vec![],
+                                });
+                            }
+                        }
+                        // References with different known prefixes are intentionally skipped
+                        // They belong to a different spec and will be validated when that spec is checked
+                    }
+                }
+            }
+        }
+
+        // r[impl validation.circular-deps]
+        // Check for circular dependencies
+        let graph = data.rule_dependencies(project_root, &key);
+        let cycles = detect_circular_dependencies(&graph);
+        for cycle in cycles {
+            errors.push(ValidationError {
+                code: ValidationErrorCode::CircularDependency,
+                message: format!("Circular dependency detected: {}", cycle.join(" → ")),
+                file: None,
+                line: None,
+                column: None,
+                related_rules: cycle,
+            });
+        }
+    }
+
+    let warning_count = errors.iter().filter(|e| e.code.is_warning()).count();
+    let error_count = errors.len() - warning_count;
+
+    ValidationResult {
+        spec: spec.to_string(),
+        impl_name: impl_name.to_string(),
+        errors,
+        warning_count,
+        error_count,
+    }
+}
+
+/// Check if a rule ID follows the naming convention
+pub(crate) fn is_valid_rule_id(id: &str) -> bool {
+    // Must have at least one segment
+    if id.is_empty() {
+        return false;
+    }
+
+    // Split by dots and check each segment
+    for segment in id.split('.') {
+        if segment.is_empty() {
+            return false;
+        }
+        // Each segment must contain only lowercase letters, digits, or hyphens
+        if !segment
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        {
+            return false;
+        }
+        // Segment must start with a letter
+        if !segment
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase())
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// How many known IDs are suggested in place of an unknown one
+const MAX_SUGGESTIONS: usize = 3;
+
+/// The known requirement IDs closest to an unknown one, best first.
+///
+/// Candidates within a third of the ID's length in edits (at least two) are
+/// ranked by edit distance, then by how many leading segments they share.
+///
+/// r[impl validation.broken-refs.suggestions]
+pub(crate) fn closest_rule_ids<'a>(
+    id: &str,
+    known: impl IntoIterator<Item = &'a str>,
+) -> Vec<&'a str> {
+    let max_distance = (id.chars().count() / 3).max(2);
+    let mut candidates: Vec<(usize, usize, &str)> = known
+        .into_iter()
+        .filter_map(|candidate| {
+            let distance = edit_distance(id, candidate);
+            (distance <= max_distance)
+                .then(|| (distance, shared_segments(id, candidate), candidate))
+        })
+        .collect();
+    candidates.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(b.2)));
+    candidates.dedup_by_key(|c| c.2);
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, _, id)| id)
+        .collect()
+}
+
+/// The ` - did you mean ...?` tail of an unknown-ID message (empty without
+/// suggestions)
+pub(crate) fn did_you_mean(suggestions: &[&str]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    let quoted: Vec<_> = suggestions.iter().map(|id| format!("'{}'", id)).collect();
+    format!(" - did you mean {}?", quoted.join(", "))
+}
+
+/// Number of leading dot-separated segments two requirement IDs share
+pub(crate) fn shared_segments(a: &str, b: &str) -> usize {
+    a.split('.')
+        .zip(b.split('.'))
+        .take_while(|(x, y)| x == y)
+        .count()
+}
+
+/// Levenshtein distance between two strings, counted in chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != *cb);
+            cur[j + 1] = substitute.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+/// Detect circular dependencies in a rule dependency graph (each rule ID
+/// mapped to the IDs it depends on, see [`DashboardData::rule_dependencies`])
+fn detect_circular_dependencies(graph: &BTreeMap<String, BTreeSet<String>>) -> Vec<Vec<String>> {
+    // Detect cycles using DFS
+    let mut cycles = Vec::new();
+    let mut visited = HashSet::new();
+    let mut rec_stack = HashSet::new();
+    let mut path = Vec::new();
+
+    fn dfs<'a>(
+        node: &'a str,
+        graph: &'a BTreeMap<String, BTreeSet<String>>,
+        visited: &mut HashSet<&'a str>,
+        rec_stack: &mut HashSet<&'a str>,
+        path: &mut Vec<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        visited.insert(node);
+        rec_stack.insert(node);
+        path.push(node.to_string());
+
+        if let Some(neighbors) = graph.get(node) {
+            for neighbor in neighbors {
+                let neighbor = neighbor.as_str();
+                if !visited.contains(neighbor) {
+                    dfs(neighbor, graph, visited, rec_stack, path, cycles);
+                } else if rec_stack.contains(neighbor) {
+                    // Found a cycle
+                    let cycle_start = path.iter().position(|n| n == neighbor).unwrap();
+                    let mut cycle: Vec<String> = path[cycle_start..].to_vec();
+                    cycle.push(neighbor.to_string());
+                    cycles.push(cycle);
+                }
+            }
+        }
+
+        path.pop();
+        rec_stack.remove(node);
+    }
+
+    for node in graph.keys() {
+        if !visited.contains(node.as_str()) {
+            dfs(
+                node,
+                graph,
+                &mut visited,
+                &mut rec_stack,
+                &mut path,
+                &mut cycles,
+            );
+        }
+    }
+
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // r[verify validation.circular-deps]
+    #[test]
+    fn test_detect_circular_dependencies() {
+        let mut graph: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for (rule, depends_on) in [
+            ("auth.login", "auth.session"),
+            ("auth.session", "auth.login"),
+            ("auth.logout", "auth.session"),
+        ] {
+            graph
+                .entry(rule.to_string())
+                .or_default()
+                .insert(depends_on.to_string());
+        }
+
+        assert_eq!(
+            detect_circular_dependencies(&graph),
+            vec![vec!["auth.login", "auth.session", "auth.login"]]
+        );
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("alocation", "allocation"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    // r[verify validation.broken-refs.suggestions]
+    #[test]
+    fn test_closest_rule_ids() {
+        let known = [
+            "channel.id.allocation",
+            "channel.id.parity",
+            "channel.close",
+            "auth.login",
+        ];
+        assert_eq!(
+            closest_rule_ids("channel.id.alocation", known),
+            vec!["channel.id.allocation"]
+        );
+        // Equally close IDs sharing more segments come first
+        assert_eq!(
+            closest_rule_ids(
+                "channel.io.parity",
+                ["channel.id.parity", "channel.io.parit"]
+            ),
+            vec!["channel.io.parit", "channel.id.parity"]
+        );
+        assert!(closest_rule_ids("storage.quota", known).is_empty());
+        assert_eq!(
+            did_you_mean(&["a.b", "a.c"]),
+            " - did you mean 'a.b', 'a.c'?"
+        );
+    }
+}
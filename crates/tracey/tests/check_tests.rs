@@ -0,0 +1,694 @@
+//! Tests for the daemon-free `tracey check` command.
+
+mod common;
+
+fn load_config(root: &std::path::Path) -> tracey::config::Config {
+    tracey::load_config(&root.join("config.styx")).expect("Failed to load config")
+}
+
+// r[verify cli.check]
+#[tokio::test]
+async fn test_check_passes_at_or_above_threshold() {
+    let temp = common::create_temp_project();
+    let config = load_config(temp.path());
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        threshold: Some(50.0),
+        lock_path: None,
+        since: None,
+        exclude_levels: Vec::new(),
+    };
+    let outcome = tracey::check::check(temp.path(), &config, &options)
+        .await
+        .expect("check should run");
+
+    assert_eq!(outcome.impls.len(), 1);
+    let check = &outcome.impls[0];
+    assert_eq!(check.spec, "test");
+    assert_eq!(check.impl_name, "rust");
+    assert!(
+        check.validation.errors.is_empty(),
+        "Fixture should validate cleanly, got: {:?}",
+        check.validation.errors
+    );
+    assert!(
+        outcome.is_passing(),
+        "75% coverage should pass a 50% threshold"
+    );
+}
+
+// r[verify cli.check.exit-code]
+// r[verify cli.check.threshold]
+#[tokio::test]
+async fn test_check_fails_below_threshold() {
+    let temp = common::create_temp_project();
+    let config = load_config(temp.path());
+
+    let options = tracey::check::CheckOptions {
+        threshold: Some(100.0),
+        ..Default::default()
+    };
+    let outcome = tracey::check::check(temp.path(), &config, &options)
+        .await
+        .expect("check should run");
+
+    assert_eq!(outcome.impls.len(), 2, "Both specs should be checked");
+    assert!(
+        !outcome.is_passing(),
+        "Fixture has uncovered rules, a 100% threshold should fail"
+    );
+}
+
+#[tokio::test]
+async fn test_check_unknown_spec_is_error() {
+    let temp = common::create_temp_project();
+    let config = load_config(temp.path());
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("does-not-exist".to_string()),
+        ..Default::default()
+    };
+    let result = tracey::check::check(temp.path(), &config, &options).await;
+
+    assert!(result.is_err(), "Filtering to an unknown spec should error");
+}
+
+// r[verify cli.check.no-daemon]
+#[tokio::test]
+async fn test_check_leaves_no_tracey_dir() {
+    let temp = common::create_temp_project();
+    let config = load_config(temp.path());
+
+    tracey::check::check(temp.path(), &config, &Default::default())
+        .await
+        .expect("check should run");
+
+    assert!(
+        !temp.path().join(".tracey").exists(),
+        "tracey check must not create a .tracey directory"
+    );
+}
+
+// r[verify ref.version.stale]
+// r[verify validation.stale]
+#[tokio::test]
+async fn test_check_reports_stale_references() {
+    let temp = common::create_temp_project();
+    let spec_path = temp.path().join("spec.md");
+    let spec = std::fs::read_to_string(&spec_path).unwrap();
+    std::fs::write(&spec_path, spec.replace("r[auth.login]", "r[auth.login+2]")).unwrap();
+    let config = load_config(temp.path());
+
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+    let key = ("test".to_string(), "rust".to_string());
+    let rule = data.forward_by_impl[&key]
+        .rules
+        .iter()
+        .find(|r| r.id == "auth.login")
+        .expect("auth.login should still be defined without its suffix");
+    assert_eq!(rule.version, 2);
+    assert!(
+        rule.impl_refs.is_empty(),
+        "Stale refs must not count as coverage"
+    );
+    assert!(rule.is_stale());
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        threshold: None,
+        lock_path: None,
+        since: None,
+        exclude_levels: Vec::new(),
+    };
+    let outcome =
+        tracey::check::check_data(&data, temp.path(), &config, &options).expect("check should run");
+    let check = &outcome.impls[0];
+    assert!(
+        check.validation.errors.iter().any(|e| e.code
+            == tracey_api::ValidationErrorCode::StaleReference
+            && e.related_rules == vec!["auth.login".to_string()]),
+        "Expected a stale reference error, got: {:?}",
+        check.validation.errors
+    );
+    assert!(!outcome.is_passing());
+}
+
+// r[verify markdown.html.link]
+#[tokio::test]
+async fn test_versioned_requirement_badge_links_base_id() {
+    let temp = common::create_temp_project();
+    let spec_path = temp.path().join("spec.md");
+    let spec = std::fs::read_to_string(&spec_path).unwrap();
+    std::fs::write(&spec_path, spec.replace("r[auth.login]", "r[auth.login+2]")).unwrap();
+    let config = load_config(temp.path());
+
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+    let key = ("test".to_string(), "rust".to_string());
+    let html: String = data.specs_content_by_impl[&key]
+        .sections
+        .iter()
+        .map(|s| s.html.as_str())
+        .collect();
+
+    for attr in [
+        r#"data-req-id="auth.login""#,
+        r#"href="/test/rust/spec#r--auth.login""#,
+        r#"data-rule="auth.login""#,
+        r#"title="auth.login""#,
+    ] {
+        assert!(html.contains(attr), "Expected {attr} in: {html}");
+    }
+    assert!(
+        !html.contains(r#"data-rule="auth.login+2""#),
+        "Version suffix leaked into links: {html}"
+    );
+    assert!(
+        html.contains("auth.<wbr>login+2"),
+        "Badge label should keep the version"
+    );
+}
+
+// r[verify cli.check.lock]
+// r[verify lock.drift]
+#[tokio::test]
+async fn test_check_reports_drift_from_lock() {
+    let temp = common::create_temp_project();
+    let config = load_config(temp.path());
+    let lock_path = tracey::lock::lock_path(&temp.path().join("config.styx"));
+
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+    tracey::lock::Lock::from_data(&data)
+        .save(&lock_path)
+        .expect("lock should save");
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        threshold: None,
+        lock_path: Some(lock_path.clone()),
+        since: None,
+        exclude_levels: Vec::new(),
+    };
+    let outcome = tracey::check::check(temp.path(), &config, &options)
+        .await
+        .expect("check should run");
+    assert!(outcome.impls[0].drifted.is_empty());
+
+    let spec_path = temp.path().join("spec.md");
+    let spec = std::fs::read_to_string(&spec_path).unwrap();
+    std::fs::write(
+        &spec_path,
+        spec.replace(
+            "Users MUST provide valid credentials to log in.",
+            "Users SHOULD provide valid credentials to log in.",
+        ),
+    )
+    .unwrap();
+
+    let outcome = tracey::check::check(temp.path(), &config, &options)
+        .await
+        .expect("check should run");
+    let drifted: Vec<_> = outcome.impls[0]
+        .drifted
+        .iter()
+        .map(|d| d.rule_id.as_str())
+        .collect();
+    assert_eq!(drifted, vec!["auth.login"]);
+    assert!(!outcome.is_passing());
+}
+
+// r[verify cli.check.test-results]
+// r[verify validation.failing-verification]
+#[tokio::test]
+async fn test_check_reports_failing_verification() {
+    let temp = common::create_temp_project();
+    let results_path = temp.path().join("results.json");
+    std::fs::write(
+        &results_path,
+        r#"{ "type": "test", "event": "ok", "name": "tests::test_login_success" }
+{ "type": "test", "event": "failed", "name": "tests::test_login_empty_credentials" }
+{ "type": "test", "event": "ok", "name": "tests::test_validate_required_fields" }
+"#,
+    )
+    .unwrap();
+    let mut config = load_config(temp.path());
+    for impl_config in config.specs.iter_mut().flat_map(|s| s.impls.iter_mut()) {
+        impl_config
+            .test_results
+            .push(results_path.display().to_string());
+    }
+
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+    let key = ("test".to_string(), "rust".to_string());
+    let status = |id: &str| {
+        data.forward_by_impl[&key]
+            .rules
+            .iter()
+            .find(|r| r.id == id)
+            .and_then(|r| r.verify_status)
+    };
+    assert_eq!(
+        status("auth.login"),
+        Some(tracey_api::VerifyStatus::Failing)
+    );
+    assert_eq!(
+        status("data.required-fields"),
+        Some(tracey_api::VerifyStatus::Passing)
+    );
+    assert_eq!(
+        status("error.codes"),
+        Some(tracey_api::VerifyStatus::NotRun)
+    );
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        ..Default::default()
+    };
+    let outcome =
+        tracey::check::check_data(&data, temp.path(), &config, &options).expect("check should run");
+    assert!(
+        outcome.impls[0].validation.errors.iter().any(|e| e.code
+            == tracey_api::ValidationErrorCode::FailingVerification
+            && e.related_rules == vec!["auth.login".to_string()]),
+        "Expected a failing verification error, got: {:?}",
+        outcome.impls[0].validation.errors
+    );
+    assert!(!outcome.is_passing());
+}
+
+// r[verify cli.check.since]
+// r[verify diff.rules]
+// r[verify diff.units]
+// r[verify diff.failure]
+#[tokio::test]
+async fn test_check_since_reports_branch_changes() {
+    let temp = common::create_temp_project();
+    common::git(temp.path(), &["init", "--quiet"]);
+    common::git(temp.path(), &["add", "-A"]);
+    common::git(temp.path(), &["commit", "--quiet", "-m", "baseline"]);
+
+    let spec_path = temp.path().join("spec.md");
+    let spec = std::fs::read_to_string(&spec_path).unwrap().replace(
+        "All errors MUST be logged with sufficient context for debugging.",
+        "All errors MUST be logged with a timestamp.",
+    );
+    std::fs::write(
+        &spec_path,
+        format!("{spec}\nr[error.retries]\nFailed requests MUST be retried.\n"),
+    )
+    .unwrap();
+    let lib_path = temp.path().join("src/lib.rs");
+    let lib = std::fs::read_to_string(&lib_path).unwrap();
+    std::fs::write(
+        &lib_path,
+        format!("{lib}\npub fn retry() {{\n    todo!()\n}}\n"),
+    )
+    .unwrap();
+
+    let config = load_config(temp.path());
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        since: Some("HEAD".to_string()),
+        ..Default::default()
+    };
+    let outcome = tracey::check::check(temp.path(), &config, &options)
+        .await
+        .expect("check should run");
+    let diff = outcome.impls[0].diff.as_ref().expect("diff scope");
+
+    let changed: Vec<_> = diff
+        .changed_rules
+        .iter()
+        .map(|r| (r.id.as_str(), r.added, r.covered))
+        .collect();
+    assert_eq!(
+        changed,
+        vec![
+            ("error.logging", false, false),
+            ("error.retries", true, false)
+        ]
+    );
+
+    let units: Vec<_> = diff
+        .unannotated_units
+        .iter()
+        .map(|u| {
+            (
+                u.path.as_str(),
+                u.name.as_deref(),
+                u.added,
+                u.in_covered_file,
+            )
+        })
+        .collect();
+    assert_eq!(units, vec![("src/lib.rs", Some("retry"), true, true)]);
+
+    assert!(!diff.passing);
+    assert!(!outcome.is_passing());
+}
+
+// r[verify level.headline]
+// r[verify level.thresholds]
+// r[verify config.impl.level_thresholds]
+// r[verify config.impl.exclude_levels]
+// r[verify cli.check.exclude-level]
+#[tokio::test]
+async fn test_check_enforces_level_thresholds() {
+    let temp = common::create_temp_project();
+    let spec_path = temp.path().join("spec.md");
+    let spec = std::fs::read_to_string(&spec_path).unwrap();
+    std::fs::write(
+        &spec_path,
+        spec.replace("Email addresses MUST", "Email addresses SHOULD")
+            .replace("All errors MUST be logged", "All errors MAY be logged"),
+    )
+    .unwrap();
+    let mut config = load_config(temp.path());
+    config.specs[0].impls[0].level_thresholds.must = Some(100.0);
+    config.specs[0].impls[0].exclude_levels = vec!["may".to_string()];
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        ..Default::default()
+    };
+    let outcome = tracey::check::check(temp.path(), &config, &options)
+        .await
+        .expect("check should run");
+    let check = &outcome.impls[0];
+    // data.format (SHOULD) and error.logging (MAY) are uncovered; MAY is left out
+    assert_eq!((check.covered_rules, check.total_rules), (6, 7));
+    assert_eq!(check.excluded_levels, vec!["may"]);
+    let levels: Vec<_> = check
+        .levels
+        .iter()
+        .map(|l| (l.level, l.covered_rules, l.total_rules))
+        .collect();
+    assert_eq!(
+        levels,
+        vec![("must", 6, 6), ("should", 0, 1), ("may", 0, 1)]
+    );
+    assert!(outcome.is_passing(), "Every MUST requirement is covered");
+
+    config.specs[0].impls[0].level_thresholds.should = Some(80.0);
+    let outcome = tracey::check::check(temp.path(), &config, &options)
+        .await
+        .expect("check should run");
+    assert!(!outcome.impls[0].levels[1].is_passing());
+    assert!(!outcome.is_passing(), "SHOULD coverage is below 80%");
+
+    // --exclude-level adds to the configured levels
+    let options = tracey::check::CheckOptions {
+        exclude_levels: vec!["SHOULD".to_string()],
+        ..options
+    };
+    let outcome = tracey::check::check(temp.path(), &config, &options)
+        .await
+        .expect("check should run");
+    let check = &outcome.impls[0];
+    assert_eq!(check.excluded_levels, vec!["may", "should"]);
+    assert_eq!((check.covered_rules, check.total_rules), (6, 6));
+}
+
+// r[verify level.headline]
+#[tokio::test]
+async fn test_status_leaves_out_excluded_levels() {
+    let temp = common::create_temp_project();
+    let spec_path = temp.path().join("spec.md");
+    let spec = std::fs::read_to_string(&spec_path).unwrap();
+    std::fs::write(
+        &spec_path,
+        spec.replace("All errors MUST be logged", "All errors MAY be logged"),
+    )
+    .unwrap();
+    let mut config = load_config(temp.path());
+    config.specs[0].impls[0].exclude_levels = vec!["MAY".to_string()];
+
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+    let key = ("test".to_string(), "rust".to_string());
+    assert_eq!(data.forward_by_impl[&key].exclude_levels, vec!["may"]);
+
+    // error.logging (MAY) is left out of the headline, as in `tracey check`
+    let status = tracey::server::QueryEngine::new(&data).status();
+    let (_, _, stats) = status
+        .iter()
+        .find(|(spec, impl_name, _)| spec == "test" && impl_name == "rust")
+        .expect("test/rust status");
+    assert_eq!((stats.impl_covered, stats.total_rules), (6, 7));
+
+    // Unknown levels are rejected like on the command line
+    config.specs[0].impls[0].exclude_levels = vec!["sometimes".to_string()];
+    assert!(
+        tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+            .await
+            .is_err()
+    );
+}
+
+// r[verify status.coverage]
+// r[verify status.deprecated]
+// r[verify status.removed]
+#[tokio::test]
+async fn test_check_respects_requirement_status() {
+    use tracey_api::ValidationErrorCode;
+
+    let temp = common::create_temp_project();
+    let config = load_config(temp.path());
+    let mut data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+    let key = ("test".to_string(), "rust".to_string());
+    for rule in &mut data.forward_by_impl.get_mut(&key).unwrap().rules {
+        rule.status = match rule.id.as_str() {
+            "auth.login" => Some("removed".to_string()),
+            "auth.session" => Some("deprecated".to_string()),
+            "data.format" => Some("draft".to_string()),
+            _ => None,
+        };
+    }
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        ..Default::default()
+    };
+    let outcome =
+        tracey::check::check_data(&data, temp.path(), &config, &options).expect("check should run");
+    let check = &outcome.impls[0];
+
+    // Only logout, required-fields, error.codes, error.messages and
+    // error.logging count; error.logging is uncovered
+    assert_eq!((check.covered_rules, check.total_rules), (4, 5));
+
+    let codes = |code| {
+        check
+            .validation
+            .errors
+            .iter()
+            .filter(|e| e.code == code)
+            .flat_map(|e| e.related_rules.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        codes(ValidationErrorCode::DeprecatedRequirement),
+        vec!["auth.session"]
+    );
+    assert!(
+        codes(ValidationErrorCode::RemovedRequirement)
+            .iter()
+            .all(|id| id == "auth.login")
+    );
+    assert_eq!(
+        codes(ValidationErrorCode::RemovedRequirement).len(),
+        3,
+        "auth.login has one impl and two verify references"
+    );
+    assert!(codes(ValidationErrorCode::UnknownRequirement).is_empty());
+    assert_eq!(check.validation.warning_count, 1);
+    assert_eq!(check.validation.error_count, 3);
+    assert!(!outcome.is_passing(), "References to removed rules fail");
+}
+
+// r[verify ref.wildcard.expand]
+// r[verify ref.wildcard.unmatched]
+#[tokio::test]
+async fn test_check_expands_wildcard_references() {
+    use tracey_api::ValidationErrorCode;
+
+    let temp = common::create_temp_project();
+    std::fs::write(
+        temp.path().join("src/wildcards.rs"),
+        "// r[verify error.*]\n// r[verify missing.*]\nfn test_errors() {}\n",
+    )
+    .expect("Failed to write wildcards.rs");
+    let config = load_config(temp.path());
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+
+    let key = ("test".to_string(), "rust".to_string());
+    let forward = &data.forward_by_impl[&key];
+    for id in ["error.codes", "error.messages", "error.logging"] {
+        let rule = forward.rules.iter().find(|r| r.id == id).unwrap();
+        assert!(
+            rule.verify_refs
+                .iter()
+                .any(|r| r.file == "src/wildcards.rs"),
+            "{id} should be verified by the wildcard"
+        );
+    }
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        ..Default::default()
+    };
+    let outcome =
+        tracey::check::check_data(&data, temp.path(), &config, &options).expect("check should run");
+    let check = &outcome.impls[0];
+
+    // error.logging is now covered; data.format still isn't
+    assert_eq!((check.covered_rules, check.total_rules), (7, 8));
+
+    let unmatched: Vec<_> = check
+        .validation
+        .errors
+        .iter()
+        .filter(|e| e.code == ValidationErrorCode::UnmatchedWildcard)
+        .collect();
+    assert_eq!(unmatched.len(), 1);
+    assert!(unmatched[0].message.contains("missing.*"));
+    assert!(
+        !check
+            .validation
+            .errors
+            .iter()
+            .any(|e| e.code == ValidationErrorCode::UnknownRequirement)
+    );
+}
+
+// r[verify validation.broken-refs.suggestions]
+#[tokio::test]
+async fn test_check_suggests_ids_for_unknown_references() {
+    use tracey_api::ValidationErrorCode;
+
+    let temp = common::create_temp_project();
+    std::fs::write(
+        temp.path().join("src/typo.rs"),
+        "// r[impl auth.logn]\nfn login() {}\n// r[impl storage.quota]\nfn quota() {}\n",
+    )
+    .expect("Failed to write typo.rs");
+    let config = load_config(temp.path());
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        ..Default::default()
+    };
+    let outcome =
+        tracey::check::check_data(&data, temp.path(), &config, &options).expect("check should run");
+    let messages: Vec<_> = outcome.impls[0]
+        .validation
+        .errors
+        .iter()
+        .filter(|e| e.code == ValidationErrorCode::UnknownRequirement)
+        .map(|e| e.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        vec![
+            "Reference to unknown rule 'auth.logn' - did you mean 'auth.login', 'auth.logout'?",
+            "Reference to unknown rule 'storage.quota'",
+        ]
+    );
+}
+
+/// Declare `bench` (tracked only) and `fuzz` (counts as verify) for the test spec.
+fn declare_custom_verbs(root: &std::path::Path, fuzz_counts_as: &str) {
+    let config_path = root.join("config.styx");
+    let config = std::fs::read_to_string(&config_path).expect("Failed to read config");
+    let config = config.replacen(
+        "include (spec.md)",
+        &format!(
+            "include (spec.md)\n    verbs ({{name bench}} {{name fuzz, counts_as {fuzz_counts_as}}})"
+        ),
+        1,
+    );
+    std::fs::write(&config_path, config).expect("Failed to write config");
+}
+
+// r[verify ref.verb.custom]
+// r[verify config.spec.verbs]
+#[tokio::test]
+async fn test_check_counts_custom_verbs() {
+    let temp = common::create_temp_project();
+    declare_custom_verbs(temp.path(), "verify");
+    std::fs::write(
+        temp.path().join("src/extra.rs"),
+        "// r[bench data.format]\n// r[fuzz error.logging]\n// o[bench data.format]\nfn fuzz_errors() {}\n",
+    )
+    .expect("Failed to write extra.rs");
+    let config = load_config(temp.path());
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+
+    let key = ("test".to_string(), "rust".to_string());
+    let forward = &data.forward_by_impl[&key];
+    let rule = |id: &str| forward.rules.iter().find(|r| r.id == id).unwrap();
+
+    // bench is tracked in its own column but doesn't count toward coverage
+    let format = rule("data.format");
+    assert_eq!(format.custom_refs("bench").len(), 1);
+    assert!(format.impl_refs.is_empty() && format.verify_refs.is_empty());
+
+    // fuzz counts as verify
+    let logging = rule("error.logging");
+    assert_eq!(logging.custom_refs("fuzz").len(), 1);
+    assert!(logging.verify_refs.iter().any(|r| r.file == "src/extra.rs"));
+
+    let options = tracey::check::CheckOptions {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        ..Default::default()
+    };
+    let outcome =
+        tracey::check::check_data(&data, temp.path(), &config, &options).expect("check should run");
+    let check = &outcome.impls[0];
+    assert_eq!((check.covered_rules, check.total_rules), (7, 8));
+    assert!(
+        check.validation.errors.is_empty(),
+        "Custom verbs should validate cleanly, got: {:?}",
+        check.validation.errors
+    );
+}
+
+#[tokio::test]
+async fn test_custom_verb_with_unknown_counts_as_is_error() {
+    let temp = common::create_temp_project();
+    declare_custom_verbs(temp.path(), "tests");
+    let config = load_config(temp.path());
+
+    let err = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect_err("counts_as must be impl or verify");
+    assert!(err.to_string().contains("counts_as"), "{err}");
+}
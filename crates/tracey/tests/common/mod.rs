@@ -29,6 +29,13 @@ pub fn create_temp_project() -> tempfile::TempDir {
     )
     .expect("Failed to copy config.styx");
 
+    // Copy other-spec.md, the second spec in config.styx
+    std::fs::copy(
+        fixtures.join("other-spec.md"),
+        temp.path().join("other-spec.md"),
+    )
+    .expect("Failed to copy other-spec.md");
+
     // Create src directory and copy source files
     std::fs::create_dir_all(temp.path().join("src")).expect("Failed to create src dir");
     std::fs::copy(fixtures.join("src/lib.rs"), temp.path().join("src/lib.rs"))
@@ -41,3 +48,19 @@ pub fn create_temp_project() -> tempfile::TempDir {
 
     temp
 }
+
+/// Run git in `root` with a fixed identity, panicking on failure.
+pub fn git(root: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .args([
+            "-c",
+            "user.name=tracey",
+            "-c",
+            "user.email=tracey@example.com",
+        ])
+        .args(args)
+        .current_dir(root)
+        .status()
+        .expect("git should run");
+    assert!(status.success(), "git {:?} failed", args);
+}
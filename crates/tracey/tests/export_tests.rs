@@ -0,0 +1,66 @@
+//! Tests for `tracey export-site`.
+
+mod common;
+
+use tracey::search::{ResultKind, SimpleEntry};
+
+fn read(path: std::path::PathBuf) -> String {
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+// r[verify cli.export-site]
+// r[verify export.layout]
+// r[verify export.deep-links]
+#[tokio::test]
+async fn test_export_site_writes_static_dashboard() {
+    let temp = common::create_temp_project();
+    let config = tracey::load_config(&temp.path().join("config.styx")).unwrap();
+    let out = temp.path().join("site");
+
+    let summary = tracey::export::export_site(temp.path(), &config, &out)
+        .await
+        .expect("export should succeed");
+    assert_eq!(summary.pairs, 2);
+    assert!(summary.files > 0);
+
+    // The page switches the dashboard to read-only mode
+    let index = read(out.join("index.html"));
+    assert!(index.contains(tracey::export::STATIC_MODE_META));
+    // 404.html is served at any depth, so its assets are root-absolute
+    assert_eq!(
+        read(out.join("404.html")),
+        index.replace("\"./assets/", "\"/assets/")
+    );
+    assert!(out.join("assets/index.js").is_file());
+    assert!(out.join("assets/index.css").is_file());
+
+    let api = out.join("api");
+    assert!(read(api.join("config.json")).contains("\"specs\""));
+    let pair = api.join("test/rust");
+    assert!(read(pair.join("forward.json")).contains("auth.login"));
+    assert!(pair.join("reverse.json").is_file());
+    assert!(pair.join("spec.json").is_file());
+    assert!(pair.join("history.json").is_file());
+
+    // Source files are numbered, and files.json maps paths to numbers
+    let files: std::collections::BTreeMap<String, usize> =
+        facet_json::from_str(&read(pair.join("files.json"))).expect("files.json should parse");
+    let n = files
+        .get("src/lib.rs")
+        .expect("src/lib.rs should be exported");
+    let file = read(pair.join("files").join(format!("{}.json", n)));
+    assert!(file.contains("r[impl auth.login]"));
+
+    let search: Vec<SimpleEntry> =
+        facet_json::from_str(&read(api.join("search.json"))).expect("search.json should parse");
+    assert!(
+        search
+            .iter()
+            .any(|e| e.kind == ResultKind::Rule && e.id == "auth.login")
+    );
+    assert!(
+        search
+            .iter()
+            .any(|e| e.kind == ResultKind::Source && e.id == "src/lib.rs")
+    );
+}
@@ -0,0 +1,156 @@
+//! Tests for coverage history and backfilling from git.
+
+mod common;
+
+use tracey::history::{self, History};
+
+/// Fixture project in a git repo with two commits; the second drops the
+/// impl reference for `auth.login`.
+fn create_history_project() -> tempfile::TempDir {
+    let temp = common::create_temp_project();
+
+    common::git(temp.path(), &["init", "--quiet"]);
+    common::git(temp.path(), &["add", "-A"]);
+    common::git(temp.path(), &["commit", "--quiet", "-m", "baseline"]);
+
+    let lib_path = temp.path().join("src/lib.rs");
+    let lib = std::fs::read_to_string(&lib_path).unwrap();
+    std::fs::write(&lib_path, lib.replace("/// r[impl auth.login]", "///")).unwrap();
+    common::git(
+        temp.path(),
+        &["commit", "--quiet", "-am", "drop login impl"],
+    );
+
+    temp
+}
+
+// r[verify history.backfill]
+// r[verify history.regressions]
+#[tokio::test]
+async fn test_backfill_matches_full_build_and_flags_regression() {
+    let temp = create_history_project();
+    let config = tracey::load_config(&temp.path().join("config.styx")).unwrap();
+
+    // Leave an uncommitted change: backfill must only see committed trees
+    let lib_path = temp.path().join("src/lib.rs");
+    let lib = std::fs::read_to_string(&lib_path).unwrap();
+    std::fs::write(&lib_path, format!("{lib}\n// r[impl auth.login]\n")).unwrap();
+
+    let entries = history::backfill(temp.path(), &config, "HEAD", 10, &History::default())
+        .await
+        .expect("backfill should run");
+    assert_eq!(entries.len(), 4, "2 commits x 2 spec/impl pairs");
+
+    let mut store = History::default();
+    store.record(entries);
+    let trend = store.for_impl("test", "rust");
+    assert_eq!(trend.entries.len(), 2);
+    let (before, after) = (&trend.entries[0], &trend.entries[1]);
+    assert_eq!(after.impl_covered + 1, before.impl_covered);
+    assert_eq!(trend.regressions.len(), 1);
+    assert_eq!(trend.regressions[0].metric, "impl");
+    assert_eq!(trend.regressions[0].commit, after.commit);
+
+    // The working tree wasn't touched
+    assert!(
+        std::fs::read_to_string(&lib_path)
+            .unwrap()
+            .ends_with("// r[impl auth.login]\n")
+    );
+
+    // Commits already in the history are skipped
+    let again = history::backfill(temp.path(), &config, "HEAD", 10, &store)
+        .await
+        .expect("backfill should run");
+    assert!(again.is_empty());
+
+    // Backfilled HEAD agrees with a full build of the same tree
+    std::fs::write(&lib_path, lib).unwrap();
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+    let (commit, timestamp) = history::head_commit(temp.path()).unwrap();
+    assert_eq!(commit, after.commit);
+    for snapshot in history::snapshot(&data, &commit, timestamp) {
+        let backfilled = store
+            .entries
+            .iter()
+            .find(|e| {
+                e.commit == commit && e.spec == snapshot.spec && e.impl_name == snapshot.impl_name
+            })
+            .expect("backfilled entry for every pair");
+        assert_eq!(backfilled, &snapshot);
+    }
+}
+
+// r[verify history.backfill]
+// r[verify status.coverage]
+#[tokio::test]
+async fn test_backfill_leaves_out_draft_requirements() {
+    let temp = create_history_project();
+    let spec_path = temp.path().join("spec.md");
+    let spec = std::fs::read_to_string(&spec_path).unwrap();
+    std::fs::write(
+        &spec_path,
+        format!("{spec}\nr[auth.draft status=draft]\nDraft requirements MAY be referenced.\n"),
+    )
+    .unwrap();
+    let lib_path = temp.path().join("src/lib.rs");
+    let lib = std::fs::read_to_string(&lib_path).unwrap();
+    // A wildcard reference must not pull the draft requirement in either
+    std::fs::write(
+        &lib_path,
+        format!("{lib}\n// r[impl auth.*]\nfn auth() {{}}\n"),
+    )
+    .unwrap();
+    common::git(temp.path(), &["commit", "--quiet", "-am", "add draft"]);
+    let config = tracey::load_config(&temp.path().join("config.styx")).unwrap();
+
+    let entries = history::backfill(temp.path(), &config, "HEAD", 1, &History::default())
+        .await
+        .expect("backfill should run");
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+    let (commit, timestamp) = history::head_commit(temp.path()).unwrap();
+    let snapshots = history::snapshot(&data, &commit, timestamp);
+    assert_eq!(entries.len(), snapshots.len());
+    for snapshot in &snapshots {
+        let backfilled = entries
+            .iter()
+            .find(|e| e.spec == snapshot.spec && e.impl_name == snapshot.impl_name)
+            .expect("backfilled entry for every pair");
+        assert_eq!(backfilled, snapshot);
+    }
+
+    let key = ("test".to_string(), "rust".to_string());
+    let draft = data.forward_by_impl[&key]
+        .rules
+        .iter()
+        .find(|r| r.id == "auth.draft")
+        .expect("draft requirement is extracted");
+    assert!(!draft.counts_toward_coverage());
+}
+
+// r[verify history.store]
+// r[verify history.record]
+#[tokio::test]
+async fn test_history_store_roundtrip() {
+    let temp = create_history_project();
+    let config = tracey::load_config(&temp.path().join("config.styx")).unwrap();
+    let data = tracey::data::build_dashboard_data(temp.path(), &config, 1, true)
+        .await
+        .expect("data should build");
+    let (commit, timestamp) = history::head_commit(temp.path()).unwrap();
+
+    let path = history::history_path(temp.path());
+    let mut store = History::load(&path).expect("missing store is empty");
+    assert!(store.entries.is_empty());
+    store.record(history::snapshot(&data, &commit, timestamp));
+    store.record(history::snapshot(&data, &commit, timestamp));
+    store.save(&path).expect("store should save");
+
+    let loaded = History::load(&path).expect("store should load");
+    assert_eq!(loaded.entries.len(), 2, "one entry per spec/impl pair");
+    assert!(loaded.contains_commit(&commit));
+}
@@ -100,9 +100,10 @@ async fn test_uncovered_returns_rules() {
         spec: Some("test".to_string()),
         impl_name: Some("rust".to_string()),
         prefix: None,
+        level: None,
     };
 
-    let response = service.uncovered(req).await;
+    let response = service.uncovered(req).await.unwrap();
 
     assert_eq!(response.spec, "test");
     assert_eq!(response.impl_name, "rust");
@@ -122,9 +123,10 @@ async fn test_uncovered_with_prefix_filter() {
         spec: Some("test".to_string()),
         impl_name: Some("rust".to_string()),
         prefix: Some("auth".to_string()),
+        level: None,
     };
 
-    let response = service.uncovered(req).await;
+    let response = service.uncovered(req).await.unwrap();
 
     // All returned rules should start with "auth."
     for section in &response.by_section {
@@ -147,9 +149,10 @@ async fn test_untested_returns_rules() {
         spec: Some("test".to_string()),
         impl_name: Some("rust".to_string()),
         prefix: None,
+        level: None,
     };
 
-    let response = service.untested(req).await;
+    let response = service.untested(req).await.unwrap();
 
     assert_eq!(response.spec, "test");
     assert_eq!(response.impl_name, "rust");
@@ -157,6 +160,43 @@ async fn test_untested_returns_rules() {
     assert!(response.untested_count > 0, "Expected some untested rules");
 }
 
+// r[verify level.filter]
+// r[verify level.breakdown]
+#[tokio::test]
+async fn test_uncovered_with_level_filter() {
+    use tracey_proto::TraceyDaemon;
+
+    let service = create_test_service().await;
+    let uncovered = |level: &str| UncoveredRequest {
+        spec: Some("test".to_string()),
+        impl_name: Some("rust".to_string()),
+        prefix: None,
+        level: Some(level.to_string()),
+    };
+
+    // Every fixture requirement is a MUST
+    let must = service.uncovered(uncovered("MUST")).await.unwrap();
+    assert!(must.uncovered_count > 0);
+    assert_eq!(must.total_rules, 8);
+
+    let may = service.uncovered(uncovered("may")).await.unwrap();
+    assert_eq!(may.uncovered_count, 0);
+    assert_eq!(may.total_rules, 0);
+
+    // Unknown levels are rejected rather than silently matching nothing
+    assert!(service.uncovered(uncovered("sometimes")).await.is_err());
+
+    let status = service.status().await;
+    let test_rust = status
+        .impls
+        .iter()
+        .find(|s| s.spec == "test" && s.impl_name == "rust")
+        .expect("test/rust status");
+    assert_eq!(test_rust.by_level.len(), 1);
+    assert_eq!(test_rust.by_level[0].level, "must");
+    assert_eq!(test_rust.by_level[0].total_rules, test_rust.total_rules);
+}
+
 // ============================================================================
 // Rule Details API Tests
 // ============================================================================
@@ -294,6 +334,85 @@ fn test_func() {}"#;
     );
 }
 
+// r[verify lsp.hover.wildcard]
+#[tokio::test]
+async fn test_lsp_hover_on_wildcard_reference() {
+    use tracey_proto::TraceyDaemon;
+
+    let service = create_test_service().await;
+
+    let content = r#"/// r[verify auth.*]
+fn test_func() {}"#;
+
+    let req = LspPositionRequest {
+        path: fixtures_dir().join("src/test.rs").display().to_string(),
+        content: content.to_string(),
+        line: 0,
+        character: 14, // Position within "auth.*"
+    };
+
+    let info = service
+        .lsp_hover(req)
+        .await
+        .expect("Expected hover info for auth.*");
+    assert_eq!(info.rule_id, "auth.*");
+    let matched: Vec<_> = info
+        .matched_rules
+        .iter()
+        .map(|m| m.rule_id.as_str())
+        .collect();
+    assert_eq!(matched, vec!["auth.login", "auth.logout", "auth.session"]);
+}
+
+// r[verify lsp.impl.wildcard]
+#[tokio::test]
+async fn test_lsp_implementation_on_wildcard_reference() {
+    use tracey_proto::TraceyDaemon;
+
+    let service = create_test_service().await;
+
+    let content = r#"/// r[verify auth.*]
+fn test_func() {}"#;
+
+    let req = LspPositionRequest {
+        path: fixtures_dir().join("src/test.rs").display().to_string(),
+        content: content.to_string(),
+        line: 0,
+        character: 14,
+    };
+
+    // auth.login, auth.session and auth.logout are each implemented once
+    let locations = service.lsp_implementation(req).await;
+    assert_eq!(locations.len(), 3, "Expected one impl per matched rule");
+    assert!(locations.iter().all(|l| l.path.ends_with("lib.rs")));
+}
+
+// r[verify lsp.diagnostics.unmatched-wildcard]
+#[tokio::test]
+async fn test_lsp_diagnostics_unmatched_wildcard() {
+    use tracey_proto::TraceyDaemon;
+
+    let service = create_test_service().await;
+
+    let content = r#"/// r[verify auth.*]
+/// r[verify nothing.*]
+fn test_func() {}"#;
+
+    let req = LspDocumentRequest {
+        path: fixtures_dir().join("src/test.rs").display().to_string(),
+        content: content.to_string(),
+    };
+
+    let diagnostics = service.lsp_diagnostics(req).await;
+    let unmatched: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code == "unmatched-wildcard")
+        .collect();
+    assert_eq!(unmatched.len(), 1, "Only nothing.* should be flagged");
+    assert_eq!(unmatched[0].start_line, 1);
+    assert!(!diagnostics.iter().any(|d| d.code == "orphaned"));
+}
+
 #[tokio::test]
 async fn test_lsp_completions() {
     use tracey_proto::TraceyDaemon;
@@ -347,6 +466,51 @@ fn test_func() {}"#;
     assert!(orphaned.is_some(), "Expected orphaned diagnostic");
 }
 
+// r[verify ref.comments.html]
+#[tokio::test]
+async fn test_lsp_markdown_test_plan_is_a_source_file() {
+    use tracey_proto::TraceyDaemon;
+
+    let service = create_test_service().await;
+
+    // A markdown file outside the spec includes holds references, not definitions
+    let content = r#"# Test plan
+
+<!-- r[verify auth.login] -->
+Log in with valid credentials.
+
+<!-- r[verify nonexistent.rule] -->
+"#;
+    let path = fixtures_dir()
+        .join("docs/test-plan.md")
+        .display()
+        .to_string();
+
+    let hover = service
+        .lsp_hover(LspPositionRequest {
+            path: path.clone(),
+            content: content.to_string(),
+            line: 2,
+            character: 16, // Position within "auth.login"
+        })
+        .await
+        .expect("Expected hover info for auth.login");
+    assert_eq!(hover.rule_id, "auth.login");
+
+    let diagnostics = service
+        .lsp_diagnostics(LspDocumentRequest {
+            path,
+            content: content.to_string(),
+        })
+        .await;
+    let orphaned: Vec<_> = diagnostics
+        .iter()
+        .filter(|d| d.code == "orphaned")
+        .collect();
+    assert_eq!(orphaned.len(), 1, "Expected nonexistent.rule to be flagged");
+    assert_eq!(orphaned[0].start_line, 5);
+}
+
 #[tokio::test]
 async fn test_lsp_document_symbols() {
     use tracey_proto::TraceyDaemon;
@@ -402,6 +566,40 @@ async fn test_lsp_workspace_symbols() {
     }
 }
 
+// r[verify lsp.workspace-symbols.prefixes]
+#[tokio::test]
+async fn test_lsp_workspace_symbols_include_prefixes() {
+    use tracey_proto::TraceyDaemon;
+
+    let service = create_test_service().await;
+
+    let symbols = service.lsp_workspace_symbols("auth".to_string()).await;
+
+    let prefix = symbols
+        .iter()
+        .find(|s| s.kind == "prefix")
+        .expect("Expected a symbol for the auth prefix");
+    assert_eq!(prefix.name, "auth");
+    assert!(
+        prefix
+            .path
+            .as_deref()
+            .is_some_and(|p| p.ends_with("spec.md")),
+        "Prefix should be located in the spec, got: {:?}",
+        prefix.path
+    );
+    assert_eq!(
+        prefix.container.as_deref(),
+        Some("test/rust: 3/3 implemented, 1/3 verified")
+    );
+
+    let login = symbols
+        .iter()
+        .find(|s| s.name == "auth.login")
+        .expect("Expected auth.login symbol");
+    assert_eq!(login.container.as_deref(), Some("auth"));
+}
+
 #[tokio::test]
 async fn test_lsp_references() {
     use tracey_proto::TraceyDaemon;
@@ -474,6 +672,46 @@ fn test_login() {}"#;
     assert!(!tokens.is_empty(), "Expected semantic tokens");
 }
 
+// r[verify lsp.semantic-tokens.prefix]
+// r[verify lsp.semantic-tokens.custom-verb]
+#[tokio::test]
+async fn test_lsp_semantic_tokens_custom_verb() {
+    use tracey_proto::TraceyDaemon;
+
+    let temp = common::create_temp_project();
+    let config_path = temp.path().join("config.styx");
+    let config = std::fs::read_to_string(&config_path).unwrap();
+    std::fs::write(
+        &config_path,
+        config.replacen(
+            "include (spec.md)",
+            "include (spec.md)\n    verbs ({name bench})",
+            1,
+        ),
+    )
+    .unwrap();
+    let engine = Arc::new(
+        tracey::daemon::Engine::new(temp.path().to_path_buf(), config_path)
+            .await
+            .expect("Failed to create engine"),
+    );
+    let service = tracey::daemon::TraceyService::new(engine);
+
+    // "é" is two bytes but one UTF-16 code unit
+    let req = LspDocumentRequest {
+        path: temp.path().join("src/bench.rs").display().to_string(),
+        content: "/// é r[bench auth.login]\nfn bench_login() {}".to_string(),
+    };
+
+    let tokens = service.lsp_semantic_tokens(req).await;
+    let spans: Vec<_> = tokens
+        .iter()
+        .map(|t| (t.line, t.start_char, t.length, t.token_type))
+        .collect();
+    // Prefix as namespace, the verb as customVerb, then the ID as variable
+    assert_eq!(spans, vec![(0, 6, 2, 0), (0, 8, 5, 3), (0, 13, 12, 2)]);
+}
+
 // ============================================================================
 // Code Lens Tests
 // ============================================================================
@@ -508,6 +746,108 @@ pub fn login() {}"#;
     assert_eq!(lenses[0].command, "tracey.showReferences");
 }
 
+#[tokio::test]
+async fn test_lsp_code_actions_suggest_annotations() {
+    // r[verify lsp.actions.annotate]
+    use tracey_proto::TraceyDaemon;
+
+    let service = create_test_service().await;
+
+    let content = r#"/// Check that an email address is well-formed
+pub fn validate_email(email: &str) -> bool {
+    email.contains('@')
+}
+"#;
+    let req = LspPositionRequest {
+        path: fixtures_dir().join("src/lib.rs").display().to_string(),
+        content: content.to_string(),
+        line: 2,
+        character: 4,
+    };
+
+    let actions = service.lsp_code_actions(req).await;
+
+    let first = actions
+        .first()
+        .expect("Expected annotation suggestions for validate_email");
+    assert_eq!(
+        first.title,
+        "Annotate `validate_email` with r[impl data.format]"
+    );
+    let edit = first
+        .edit
+        .as_ref()
+        .expect("Annotation actions carry an edit");
+    assert_eq!(edit.start_line, 0);
+    assert_eq!(edit.new_text, "/// r[impl data.format]\n");
+
+    // Units that are already annotated get no suggestions
+    let req = LspPositionRequest {
+        path: fixtures_dir().join("src/lib.rs").display().to_string(),
+        content: "/// r[impl data.format]\npub fn validate_email() {}\n".to_string(),
+        line: 1,
+        character: 8,
+    };
+    assert!(service.lsp_code_actions(req).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_lsp_code_actions_fix_unknown_reference() {
+    // r[verify lsp.actions.fix-unknown]
+    use tracey_proto::TraceyDaemon;
+
+    let service = create_test_service().await;
+
+    let req = LspPositionRequest {
+        path: fixtures_dir().join("src/lib.rs").display().to_string(),
+        content: "//! r[impl auth.logn]\n".to_string(),
+        line: 0,
+        character: 12,
+    };
+    let actions = service.lsp_code_actions(req).await;
+
+    let fix = &actions[0];
+    assert_eq!(fix.title, "Change to 'auth.login'");
+    assert!(fix.is_preferred);
+    let edit = fix.edit.as_ref().expect("Fixes carry an edit");
+    assert_eq!((edit.start_line, edit.start_char), (0, 11));
+    assert_eq!((edit.end_line, edit.end_char), (0, 20));
+    assert_eq!(edit.new_text, "auth.login");
+
+    // Creating the requirement is still offered, but not preferred
+    let create = actions
+        .iter()
+        .find(|a| a.command == "tracey.createRequirement")
+        .expect("Expected a create requirement action");
+    assert!(!create.is_preferred);
+}
+
+#[tokio::test]
+async fn test_lsp_requirement_stubs() {
+    // r[verify lsp.commands.create-requirement]
+    use tracey_proto::TraceyDaemon;
+
+    let service = create_test_service().await;
+
+    let stubs = service
+        .lsp_requirement_stubs("auth.refresh".to_string())
+        .await;
+
+    // One stub per section, the one sharing the `auth` prefix first
+    assert!(stubs.len() >= 3, "Expected a stub per section: {:?}", stubs);
+    let first = &stubs[0];
+    assert_eq!(first.section_title.as_deref(), Some("Authentication"));
+    assert!(first.edit.path.ends_with("spec.md"));
+
+    // Inserted right before the "## Data Validation" heading
+    assert_eq!(first.edit.start_line, 15);
+    assert_eq!(first.edit.start_char, 0);
+    assert_eq!(
+        first.edit.new_text,
+        "r[auth.refresh]\nTODO: describe this requirement.\n\n"
+    );
+}
+
 // ============================================================================
 // Multi-Spec Prefix Filtering Tests
 // r[verify ref.prefix.filter]
@@ -652,3 +992,190 @@ fn test_func() {}"#;
         "Expected orphaned diagnostic for r[impl nonexistent.rule]"
     );
 }
+
+// ============================================================================
+// Extraction Cache Tests
+// ============================================================================
+
+// r[verify daemon.state.extraction-cache]
+#[tokio::test]
+async fn test_cached_rebuild_matches_full_build() {
+    let temp = common::create_temp_project();
+    let root = temp.path();
+    let config = tracey::load_config(&root.join("config.styx")).expect("Failed to load config");
+    let overlay = tracey::data::FileOverlay::new();
+    let mut cache = tracey::cache::BuildCache::new();
+
+    tracey::data::build_dashboard_data_cached(root, &config, 1, true, &overlay, &mut cache)
+        .await
+        .expect("initial build");
+
+    // Nothing changed: everything comes from the cache
+    tracey::data::build_dashboard_data_cached(root, &config, 2, true, &overlay, &mut cache)
+        .await
+        .expect("unchanged rebuild");
+    assert_eq!(
+        cache.stats().misses,
+        0,
+        "Unchanged rebuild should not parse"
+    );
+    assert!(cache.stats().hits > 0);
+
+    // Drop a reference from one source file
+    let lib = root.join("src/lib.rs");
+    let content = std::fs::read_to_string(&lib).unwrap();
+    std::fs::write(&lib, content.replace("r[impl auth.logout]", "")).unwrap();
+
+    let cached =
+        tracey::data::build_dashboard_data_cached(root, &config, 3, true, &overlay, &mut cache)
+            .await
+            .expect("cached rebuild");
+    assert!(cache.stats().hits > 0, "Unchanged files should be reused");
+
+    let full = tracey::data::build_dashboard_data(root, &config, 3, true)
+        .await
+        .expect("full build");
+    assert_eq!(cached.content_hash, full.content_hash);
+
+    let key = ("test".to_string(), "rust".to_string());
+    let logout = cached.forward_by_impl[&key]
+        .rules
+        .iter()
+        .find(|r| r.id == "auth.logout")
+        .unwrap();
+    assert!(logout.impl_refs.is_empty());
+}
+
+// r[verify daemon.state.incremental-rebuild]
+#[tokio::test]
+async fn test_patched_build_matches_full_build() {
+    let temp = common::create_temp_project();
+    let root = temp.path();
+    let config = tracey::load_config(&root.join("config.styx")).expect("Failed to load config");
+    let mut cache = tracey::cache::BuildCache::new();
+
+    let initial = tracey::data::build_dashboard_data_cached(
+        root,
+        &config,
+        1,
+        true,
+        &tracey::data::FileOverlay::new(),
+        &mut cache,
+    )
+    .await
+    .expect("initial build");
+
+    // Edit one source file in the overlay, as the LSP would
+    let lib = root.join("src/lib.rs").canonicalize().unwrap();
+    let content = std::fs::read_to_string(&lib).unwrap();
+    let mut overlay = tracey::data::FileOverlay::new();
+    overlay.insert(
+        lib.clone(),
+        content.replace("r[impl auth.logout]", "r[verify auth.logout]"),
+    );
+
+    let patched = tracey::data::patch_dashboard_data(
+        &initial,
+        root,
+        &config,
+        2,
+        &overlay,
+        std::slice::from_ref(&lib),
+        &mut cache,
+    )
+    .await
+    .expect("patch")
+    .expect("A source file edit should be patched in");
+
+    let full = tracey::data::build_dashboard_data_with_overlay(root, &config, 2, true, &overlay)
+        .await
+        .expect("full build");
+    assert_eq!(patched.content_hash, full.content_hash);
+
+    let key = ("test".to_string(), "rust".to_string());
+    assert_eq!(
+        format!("{:?}", patched.reqs_by_impl[&key]),
+        format!("{:?}", full.reqs_by_impl[&key])
+    );
+    assert_eq!(
+        patched.specs_content_by_impl[&key].sections[0].html,
+        full.specs_content_by_impl[&key].sections[0].html
+    );
+    let logout = patched.forward_by_impl[&key]
+        .rules
+        .iter()
+        .find(|r| r.id == "auth.logout")
+        .unwrap();
+    assert!(logout.impl_refs.is_empty());
+    assert_eq!(logout.verify_refs.len(), 1);
+
+    // Spec changes need a full rebuild
+    let spec = root.join("spec.md");
+    let result = tracey::data::patch_dashboard_data(
+        &patched,
+        root,
+        &config,
+        3,
+        &overlay,
+        &[spec],
+        &mut cache,
+    )
+    .await
+    .expect("patch");
+    assert!(result.is_none());
+}
+
+// ============================================================================
+// Call Hierarchy Tests
+// ============================================================================
+
+// r[verify lsp.call-hierarchy.prepare]
+// r[verify lsp.call-hierarchy.outgoing]
+// r[verify lsp.call-hierarchy.incoming]
+#[tokio::test]
+async fn test_lsp_call_hierarchy_walks_dependencies() {
+    use tracey_proto::TraceyDaemon;
+
+    let temp = common::create_temp_project();
+    let root = temp.path();
+    std::fs::write(
+        root.join("src/deps.rs"),
+        "/// r[impl auth.logout]\n/// r[depends auth.session]\npub fn end_session() {}\n",
+    )
+    .expect("Failed to write deps.rs");
+    let engine = Arc::new(
+        tracey::daemon::Engine::new(root.to_path_buf(), root.join("config.styx"))
+            .await
+            .expect("Failed to create engine"),
+    );
+    let service = tracey::daemon::TraceyService::new(engine);
+
+    let req = LspPositionRequest {
+        path: root.join("src/deps.rs").display().to_string(),
+        content: std::fs::read_to_string(root.join("src/deps.rs")).unwrap(),
+        line: 0,
+        character: 14,
+    };
+    let items = service.lsp_prepare_call_hierarchy(req).await;
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].name, "auth.logout");
+    assert_eq!(items[0].path.as_deref(), Some("spec.md"));
+    assert_eq!(items[0].start_line, 12);
+
+    let dependencies = service.lsp_dependencies("auth.logout".to_string()).await;
+    let names: Vec<_> = dependencies.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["auth.session"]);
+    assert_eq!(dependencies[0].start_line, 9);
+
+    let dependents = service.lsp_dependents("auth.session".to_string()).await;
+    let names: Vec<_> = dependents.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["auth.logout"]);
+
+    // auth.login's code has no depends references
+    assert!(
+        service
+            .lsp_dependencies("auth.login".to_string())
+            .await
+            .is_empty()
+    );
+}
@@ -231,6 +231,37 @@ fn login_impl() {}"#;
     );
 }
 
+/// Pulled diagnostics are keyed by the data version: it must hold still
+/// while nothing changes, and move when a change affects diagnostics.
+#[tokio::test]
+async fn test_version_tracks_diagnostic_changes() {
+    use tracey_proto::TraceyDaemon;
+
+    let (temp, service) = create_isolated_test_service().await;
+    let test_file = temp.path().join("src/vfs_test.rs");
+
+    service
+        .vfs_open(
+            test_file.display().to_string(),
+            "/// r[impl auth.logn]\nfn login_impl() {}".to_string(),
+        )
+        .await;
+    let broken = service.version().await;
+    assert_eq!(service.version().await, broken, "Nothing changed");
+
+    service
+        .vfs_change(
+            test_file.display().to_string(),
+            "/// r[impl auth.login]\nfn login_impl() {}".to_string(),
+        )
+        .await;
+    assert_ne!(
+        service.version().await,
+        broken,
+        "Fixing the reference should produce a new version"
+    );
+}
+
 /// Test multiple fix-and-break cycles in the same session.
 #[tokio::test]
 async fn test_vfs_multiple_fix_break_cycles() {
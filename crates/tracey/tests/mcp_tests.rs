@@ -73,9 +73,10 @@ async fn test_mcp_uncovered_tool_no_filter() {
         spec: Some("test".to_string()),
         impl_name: Some("rust".to_string()),
         prefix: None,
+        level: None,
     };
 
-    let response = service.uncovered(req).await;
+    let response = service.uncovered(req).await.unwrap();
 
     // Should return list of uncovered rules
     assert_eq!(response.spec, "test");
@@ -93,9 +94,10 @@ async fn test_mcp_uncovered_tool_with_prefix() {
         spec: Some("test".to_string()),
         impl_name: Some("rust".to_string()),
         prefix: Some("data".to_string()),
+        level: None,
     };
 
-    let response = service.uncovered(req).await;
+    let response = service.uncovered(req).await.unwrap();
 
     // Filtered by prefix - all rules should start with "data."
     for section in &response.by_section {
@@ -120,9 +122,10 @@ async fn test_mcp_uncovered_tool_auto_select() {
         spec: None,
         impl_name: None,
         prefix: None,
+        level: None,
     };
 
-    let response = service.uncovered(req).await;
+    let response = service.uncovered(req).await.unwrap();
 
     // Should auto-select the only available spec/impl
     assert_eq!(response.spec, "test");
@@ -142,9 +145,10 @@ async fn test_mcp_untested_tool() {
         spec: Some("test".to_string()),
         impl_name: Some("rust".to_string()),
         prefix: None,
+        level: None,
     };
 
-    let response = service.untested(req).await;
+    let response = service.untested(req).await.unwrap();
 
     // Should return rules that have impl but no verify
     assert_eq!(response.spec, "test");